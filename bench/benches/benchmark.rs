@@ -1,5 +1,6 @@
 use criterion::{
     criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, BenchmarkId, Criterion,
+    Throughput,
 };
 // use prost::Message;
 use serde::{Deserialize, Serialize};
@@ -94,6 +95,29 @@ fn get_example_longnum() -> ExType {
     }
 }
 
+/// Many records, so that pretty-printed most of the bytes are indentation.
+fn get_example_doc() -> Doc {
+    Doc {
+        id: 42,
+        items: (0..1000).map(|_| get_example_longnum()).collect(),
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Doc {
+    #[serde(rename = "0")]
+    id: u64,
+    #[serde(rename = "1")]
+    items: Vec<A1>,
+}
+
+/// Only the id of a [`Doc`], skipping its items.
+#[derive(Deserialize, Debug, PartialEq)]
+struct DocId {
+    #[serde(rename = "0")]
+    id: u64,
+}
+
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
 struct A1 {
     #[serde(rename = "1")]
@@ -222,6 +246,64 @@ fn bench_my_deserialize_value(bench_group: &mut BenchmarkGroup<WallTime>) {
     );
 }
 
+fn bench_my_deserialize_pretty(bench_group: &mut BenchmarkGroup<WallTime>, data: &str) {
+    bench_group.bench_with_input(
+        BenchmarkId::new("deserialize pretty", 1),
+        data.as_bytes(),
+        |b, val| b.iter(|| serde_encom::from_slice::<Doc>(val).unwrap()),
+    );
+}
+
+fn bench_my_deserialize_pretty_scalar(bench_group: &mut BenchmarkGroup<WallTime>, data: &str) {
+    serde_encom::set_simd_scan(false);
+    bench_group.bench_with_input(
+        BenchmarkId::new("deserialize pretty scalar", 1),
+        data.as_bytes(),
+        |b, val| b.iter(|| serde_encom::from_slice::<Doc>(val).unwrap()),
+    );
+    serde_encom::set_simd_scan(true);
+}
+
+fn bench_my_deserialize_value_pretty(bench_group: &mut BenchmarkGroup<WallTime>, data: &str) {
+    bench_group.bench_with_input(
+        BenchmarkId::new("deserialize value pretty", 1),
+        data.as_bytes(),
+        |b, val| b.iter(|| serde_encom::from_slice::<Value>(val).unwrap()),
+    );
+}
+
+fn bench_my_deserialize_large(bench_group: &mut BenchmarkGroup<WallTime>, data: &str) {
+    bench_group.bench_with_input(
+        BenchmarkId::new("deserialize large", 1),
+        data.as_bytes(),
+        |b, val| b.iter(|| serde_encom::from_slice::<Doc>(val).unwrap()),
+    );
+}
+
+fn bench_my_deserialize_skip(bench_group: &mut BenchmarkGroup<WallTime>, data: &str) {
+    bench_group.bench_with_input(
+        BenchmarkId::new("deserialize small from large", 1),
+        data.as_bytes(),
+        |b, val| b.iter(|| serde_encom::from_slice::<DocId>(val).unwrap()),
+    );
+}
+
+fn bench_my_deserialize_array(bench_group: &mut BenchmarkGroup<WallTime>, data: &str) {
+    bench_group.bench_with_input(
+        BenchmarkId::new("deserialize array", 1),
+        data.as_bytes(),
+        |b, val| b.iter(|| serde_encom::from_slice::<Vec<A1>>(val).unwrap()),
+    );
+}
+
+fn bench_my_deserialize_array_par(bench_group: &mut BenchmarkGroup<WallTime>, data: &str) {
+    bench_group.bench_with_input(
+        BenchmarkId::new("deserialize array par", 1),
+        data.as_bytes(),
+        |b, val| b.iter(|| serde_encom::par::from_slice_vec::<A1>(val).unwrap()),
+    );
+}
+
 fn benchmark(c: &mut Criterion) {
     /* {
         let mut bench_group = c.benchmark_group("serde_json");
@@ -306,6 +388,43 @@ fn benchmark(c: &mut Criterion) {
             b.iter(|| val.parse::<u64>().unwrap())
         }); */
 
+        bench_group.finish();
+    }
+    {
+        let mut bench_group = c.benchmark_group("my pretty");
+        let doc = get_example_doc();
+        let data = serde_encom::to_string_pretty(&doc).unwrap();
+        bench_group.throughput(Throughput::Bytes(data.len() as u64));
+        bench_my_deserialize_pretty(&mut bench_group, &data);
+        bench_my_deserialize_pretty_scalar(&mut bench_group, &data);
+
+        // A `Value` writes the enums as maps, which it can read back.
+        let value = serde_encom::to_value(&doc).unwrap();
+        let data = serde_encom::to_string_pretty(&value).unwrap();
+        bench_group.throughput(Throughput::Bytes(data.len() as u64));
+        bench_my_deserialize_value_pretty(&mut bench_group, &data);
+
+        bench_group.finish();
+    }
+    {
+        let mut bench_group = c.benchmark_group("my skip");
+        let data = serde_encom::to_string(&get_example_doc()).unwrap();
+        bench_group.throughput(Throughput::Bytes(data.len() as u64));
+
+        bench_my_deserialize_large(&mut bench_group, &data);
+        bench_my_deserialize_skip(&mut bench_group, &data);
+
+        bench_group.finish();
+    }
+    {
+        let mut bench_group = c.benchmark_group("my par");
+        let items: Vec<A1> = (0..10000).map(|_| get_example_longnum()).collect();
+        let data = serde_encom::to_string(&items).unwrap();
+        bench_group.throughput(Throughput::Bytes(data.len() as u64));
+
+        bench_my_deserialize_array(&mut bench_group, &data);
+        bench_my_deserialize_array_par(&mut bench_group, &data);
+
         bench_group.finish();
     }
 }
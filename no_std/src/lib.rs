@@ -0,0 +1,100 @@
+//! Serializes and deserializes EnCom without `std` and `alloc`.
+//!
+//! Build it for a target without `std` to check that nothing allocates:
+//!
+//! ```sh
+//! rustup target add thumbv7em-none-eabihf
+//! cargo build --target thumbv7em-none-eabihf
+//! ```
+//!
+//! `cargo test` runs the same code on the host.
+
+#![cfg_attr(not(test), no_std)]
+
+use serde::{Deserialize, Serialize};
+use serde_encom::Result;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub enum Mode {
+    Idle,
+    Run(u16),
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Reading<'a> {
+    pub id: u32,
+    pub name: &'a str,
+    #[serde(borrow)]
+    pub key: &'a serde_bytes::Bytes,
+    pub temp: f32,
+    pub mode: Mode,
+    pub alarm: Option<i64>,
+    pub big: u128,
+    pub ratios: [u8; 3],
+    #[serde(with = "serde_encom::heapless::string")]
+    pub unit: heapless::String<4>,
+    pub history: heapless::Vec<i16, 4>,
+}
+
+/// Reads a reading that borrows its strings and bytes from `input`.
+pub fn parse(input: &[u8]) -> Result<Reading<'_>> {
+    serde_encom::from_slice(input)
+}
+
+/// Writes a reading into `buf`, returning the number of bytes written.
+pub fn write(buf: &mut [u8], reading: &Reading) -> Result<usize> {
+    serde_encom::to_slice(buf, reading)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &[u8] = b"id:7 name:6=boiler key:2~\x01\x02 temp:21.5 mode{Run:300} alarm:-3 big:340282366920938463463374607431768211455 ratios[1 2 3] unit:2=mV history[-2 5]";
+
+    fn reading() -> Reading<'static> {
+        Reading {
+            id: 7,
+            name: "boiler",
+            key: serde_bytes::Bytes::new(b"\x01\x02"),
+            temp: 21.5,
+            mode: Mode::Run(300),
+            alarm: Some(-3),
+            big: u128::MAX,
+            ratios: [1, 2, 3],
+            unit: heapless::String::try_from("mV").unwrap(),
+            history: heapless::Vec::from_slice(&[-2, 5]).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse(INPUT).unwrap(), reading());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut buf = [0; 256];
+        let len = write(&mut buf, &reading()).unwrap();
+        assert_eq!(parse(&buf[..len]).unwrap(), reading());
+    }
+
+    #[test]
+    fn test_errors() {
+        let err = parse(b"id:7 name:9=boiler").unwrap_err();
+        assert!(err.is_eof());
+        assert_eq!(err.line(), 1);
+
+        let err = parse(b"id:x").unwrap_err();
+        assert!(err.is_syntax());
+
+        let err = parse(b"id:7").unwrap_err();
+        assert!(err.is_data());
+
+        let err = parse(b"id:7 name:6=boiler key:0~ temp:1 mode{Run:1} alarm:n big:0 ratios[1 2 3] unit:5=volts").unwrap_err();
+        assert!(err.is_capacity_exceeded());
+
+        let err = write(&mut [0; 8], &reading()).unwrap_err();
+        assert!(err.is_buffer_full());
+    }
+}
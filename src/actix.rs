@@ -0,0 +1,360 @@
+//! EnCom request and response bodies for [`actix-web`](actix_web).
+//!
+//! [`Encom<T>`] works like actix-web's `web::Json<T>`: as an extractor it
+//! deserializes the request body, configured by [`EncomConfig`], and as a
+//! responder it serializes the value and sets the `content-type` to
+//! `application/encom`.
+//!
+//! # Example
+//!
+//! ```
+//! use actix_web::{error, web, App, HttpResponse};
+//! use serde::{Deserialize, Serialize};
+//! use serde_encom::actix::{Encom, EncomConfig};
+//!
+//! #[derive(Deserialize)]
+//! struct CreateUser {
+//!     name: String,
+//! }
+//!
+//! #[derive(Serialize)]
+//! struct User {
+//!     id: u64,
+//!     name: String,
+//! }
+//!
+//! async fn create_user(payload: Encom<CreateUser>) -> Encom<User> {
+//!     Encom(User {
+//!         id: 1,
+//!         name: payload.into_inner().name,
+//!     })
+//! }
+//!
+//! let config = EncomConfig::default()
+//!     .limit(4096)
+//!     .error_handler(|err, _req| {
+//!         let message = format!("{}", err);
+//!         error::InternalError::from_response(err, HttpResponse::Conflict().body(message)).into()
+//!     });
+//!
+//! let app = App::new()
+//!     .app_data(config)
+//!     .route("/users", web::post().to(create_user));
+//! ```
+
+use crate::error::{Error, ErrorCategory};
+use actix_web::body::BoxBody;
+use actix_web::dev::Payload;
+use actix_web::http::header::{ContentLength, Header};
+use actix_web::http::StatusCode;
+use actix_web::{mime, web, FromRequest, HttpMessage, HttpRequest, HttpResponse};
+use actix_web::{Responder, ResponseError};
+use core::fmt::{self, Display};
+use core::future::Future;
+use core::ops::{Deref, DerefMut};
+use core::pin::Pin;
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+use std::sync::Arc;
+
+/// The media type of EnCom bodies.
+pub const CONTENT_TYPE: &str = "application/encom";
+
+/// An EnCom request or response body.
+///
+/// As an extractor, it reads the body up to the [`EncomConfig`] limit and
+/// deserializes it with [`from_slice`](crate::from_slice), so `T` can't
+/// borrow from it. Unless the config says otherwise, the `content-type` has
+/// to be `application/encom`, or a `+encom` type such as
+/// `application/vnd.api+encom`. See [`EncomPayloadError`] for the status
+/// codes of the errors.
+///
+/// As a responder, it is serialized with [`to_vec`](crate::to_vec), so
+/// bytes are written raw. If serialization fails, the response is a
+/// `500 Internal Server Error`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Encom<T>(pub T);
+
+impl<T> Encom<T> {
+    /// Unwraps into the inner value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T> From<T> for Encom<T> {
+    fn from(inner: T) -> Self {
+        Encom(inner)
+    }
+}
+
+impl<T> Deref for Encom<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Encom<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T> FromRequest for Encom<T>
+where
+    T: DeserializeOwned,
+{
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let req = req.clone();
+        let payload = web::Payload::from_request(&req, payload);
+        Box::pin(async move {
+            let payload = payload.await?;
+            let config = EncomConfig::from_req(&req);
+            match read_body(&req, payload, config).await {
+                Ok(value) => Ok(Encom(value)),
+                Err(err) => Err(match &config.err_handler {
+                    Some(err_handler) => err_handler(err, &req),
+                    None => err.into(),
+                }),
+            }
+        })
+    }
+}
+
+impl<T> Responder for Encom<T>
+where
+    T: Serialize,
+{
+    type Body = BoxBody;
+
+    fn respond_to(self, _: &HttpRequest) -> HttpResponse {
+        match crate::to_vec(&self.0) {
+            Ok(body) => HttpResponse::Ok().content_type(CONTENT_TYPE).body(body),
+            Err(err) => HttpResponse::from_error(EncomPayloadError::Serialize(err)),
+        }
+    }
+}
+
+async fn read_body<T>(
+    req: &HttpRequest,
+    payload: web::Payload,
+    config: &EncomConfig,
+) -> Result<T, EncomPayloadError>
+where
+    T: DeserializeOwned,
+{
+    if config.content_type_required {
+        let allowed = match req.mime_type() {
+            Ok(Some(mime)) => {
+                (mime.type_() == mime::APPLICATION
+                    && (mime.subtype() == "encom" || mime.suffix().is_some_and(|s| s == "encom")))
+                    || config
+                        .content_type
+                        .as_deref()
+                        .is_some_and(|predicate| predicate(mime))
+            }
+            _ => false,
+        };
+        if !allowed {
+            return Err(EncomPayloadError::ContentType);
+        }
+    }
+
+    let limit = config.limit;
+    if let Ok(ContentLength(length)) = ContentLength::parse(req) {
+        if length > limit {
+            return Err(EncomPayloadError::OverflowKnownLength { length, limit });
+        }
+    }
+    let body = payload
+        .to_bytes_limited(limit)
+        .await
+        .map_err(|_| EncomPayloadError::Overflow { limit })?
+        .map_err(EncomPayloadError::Payload)?;
+    crate::from_slice(&body).map_err(EncomPayloadError::Deserialize)
+}
+
+type ErrorHandler = Arc<dyn Fn(EncomPayloadError, &HttpRequest) -> actix_web::Error + Send + Sync>;
+
+/// Configuration of the [`Encom`] extractor, like actix-web's `JsonConfig`.
+///
+/// Add it to an `App`, a `Scope` or a `Resource` with `app_data`, either as
+/// it is or in a `web::Data`. Without it, the limit is 2 MB and the
+/// `content-type` has to be EnCom.
+///
+/// # Examples
+///
+/// ```
+/// use actix_web::{error, mime, web, App, HttpResponse};
+/// use serde_encom::actix::EncomConfig;
+///
+/// let config = EncomConfig::default()
+///     .limit(1024)
+///     // Also accept `text/plain`.
+///     .content_type(|mime| mime == mime::TEXT_PLAIN)
+///     // Reply with the line and column of syntax errors as a header.
+///     .error_handler(|err, _req| {
+///         let mut res = HttpResponse::build(actix_web::ResponseError::status_code(&err));
+///         if let Some(e) = err.encom_error() {
+///             res.insert_header(("x-error-position", format!("{}:{}", e.line(), e.column())));
+///         }
+///         error::InternalError::from_response(err, res.finish()).into()
+///     });
+///
+/// let app = App::new().app_data(config);
+/// ```
+#[derive(Clone)]
+pub struct EncomConfig {
+    limit: usize,
+    err_handler: Option<ErrorHandler>,
+    content_type: Option<Arc<dyn Fn(mime::Mime) -> bool + Send + Sync>>,
+    content_type_required: bool,
+}
+
+const DEFAULT_CONFIG: EncomConfig = EncomConfig {
+    limit: 2_097_152,
+    err_handler: None,
+    content_type: None,
+    content_type_required: true,
+};
+
+impl EncomConfig {
+    /// Sets the maximum size of the body in bytes. The default is 2 MB.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Sets a function that turns an extraction error into the error that
+    /// is returned from the handler, to customize the response.
+    pub fn error_handler<F>(mut self, f: F) -> Self
+    where
+        F: Fn(EncomPayloadError, &HttpRequest) -> actix_web::Error + Send + Sync + 'static,
+    {
+        self.err_handler = Some(Arc::new(f));
+        self
+    }
+
+    /// Sets a predicate for `content-type`s to accept besides the EnCom
+    /// ones.
+    pub fn content_type<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(mime::Mime) -> bool + Send + Sync + 'static,
+    {
+        self.content_type = Some(Arc::new(predicate));
+        self
+    }
+
+    /// Sets whether the `content-type` is checked at all. If not, any body,
+    /// even one without a `content-type`, is parsed as EnCom.
+    pub fn content_type_required(mut self, content_type_required: bool) -> Self {
+        self.content_type_required = content_type_required;
+        self
+    }
+
+    /// The config of the request, either `Self` or `web::Data<Self>`, or the
+    /// default.
+    fn from_req(req: &HttpRequest) -> &Self {
+        req.app_data::<Self>()
+            .or_else(|| req.app_data::<web::Data<Self>>().map(|data| data.as_ref()))
+            .unwrap_or(&DEFAULT_CONFIG)
+    }
+}
+
+impl Default for EncomConfig {
+    fn default() -> Self {
+        DEFAULT_CONFIG
+    }
+}
+
+/// Why an [`Encom`] body couldn't be extracted or responded with.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EncomPayloadError {
+    /// The `Content-Length` is over the limit: `413 Payload Too Large`.
+    OverflowKnownLength {
+        /// The `Content-Length`.
+        length: usize,
+        /// The limit of the config.
+        limit: usize,
+    },
+    /// The body turned out to be over the limit: `413 Payload Too Large`.
+    Overflow {
+        /// The limit of the config.
+        limit: usize,
+    },
+    /// The `content-type` is missing or not accepted:
+    /// `415 Unsupported Media Type`.
+    ContentType,
+    /// The body couldn't be deserialized: `422 Unprocessable Entity` if it
+    /// is valid EnCom that doesn't match `T`, according to
+    /// [`Error::classify`], or `400 Bad Request` otherwise.
+    Deserialize(Error),
+    /// The value couldn't be serialized: `500 Internal Server Error`.
+    Serialize(Error),
+    /// The body couldn't be read, with the status of the error.
+    Payload(actix_web::Error),
+}
+
+impl EncomPayloadError {
+    /// The EnCom error, with its line and column, if this is one.
+    pub fn encom_error(&self) -> Option<&Error> {
+        match self {
+            EncomPayloadError::Deserialize(err) | EncomPayloadError::Serialize(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for EncomPayloadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EncomPayloadError::OverflowKnownLength { length, limit } => write!(
+                f,
+                "EnCom payload ({} bytes) is larger than allowed (limit: {} bytes).",
+                length, limit
+            ),
+            EncomPayloadError::Overflow { limit } => {
+                write!(f, "EnCom payload has exceeded limit ({} bytes).", limit)
+            }
+            EncomPayloadError::ContentType => {
+                write!(f, "Expected request with `Content-Type: {}`", CONTENT_TYPE)
+            }
+            EncomPayloadError::Deserialize(err) => write!(f, "EnCom deserialize error: {}", err),
+            EncomPayloadError::Serialize(err) => write!(f, "EnCom serialize error: {}", err),
+            EncomPayloadError::Payload(err) => {
+                write!(f, "Error that occur during reading payload: {}", err)
+            }
+        }
+    }
+}
+
+impl std::error::Error for EncomPayloadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.encom_error().map(|err| err as _)
+    }
+}
+
+impl ResponseError for EncomPayloadError {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            EncomPayloadError::OverflowKnownLength { .. } | EncomPayloadError::Overflow { .. } => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            EncomPayloadError::ContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            EncomPayloadError::Deserialize(err) => match err.classify() {
+                ErrorCategory::Data => StatusCode::UNPROCESSABLE_ENTITY,
+                ErrorCategory::Io | ErrorCategory::Syntax | ErrorCategory::Eof => {
+                    StatusCode::BAD_REQUEST
+                }
+            },
+            EncomPayloadError::Serialize(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            EncomPayloadError::Payload(err) => err.as_response_error().status_code(),
+        }
+    }
+}
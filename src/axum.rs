@@ -0,0 +1,233 @@
+//! EnCom request and response bodies for [`axum`](::axum).
+//!
+//! [`Encom<T>`] works like axum's `Json<T>`: as an extractor it
+//! deserializes the request body, and as a response it serializes the value
+//! and sets the `content-type` to `application/encom`.
+//!
+//! # Example
+//!
+//! ```
+//! use axum::{routing::post, Router};
+//! use serde::{Deserialize, Serialize};
+//! use serde_encom::axum::Encom;
+//!
+//! #[derive(Deserialize)]
+//! struct CreateUser {
+//!     name: String,
+//! }
+//!
+//! #[derive(Serialize)]
+//! struct User {
+//!     id: u64,
+//!     name: String,
+//! }
+//!
+//! async fn create_user(Encom(payload): Encom<CreateUser>) -> Encom<User> {
+//!     Encom(User {
+//!         id: 1,
+//!         name: payload.name,
+//!     })
+//! }
+//!
+//! let app: Router = Router::new().route("/users", post(create_user));
+//! ```
+
+use crate::error::{Error, ErrorCategory};
+use axum::body::Bytes;
+use axum::extract::rejection::BytesRejection;
+use axum::extract::{FromRequest, Request};
+use axum::http::header::{self, HeaderMap, HeaderValue};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use core::fmt::{self, Display};
+use core::ops::{Deref, DerefMut};
+use serde::de::DeserializeOwned;
+use serde::ser::Serialize;
+
+/// The media type of EnCom bodies.
+pub const CONTENT_TYPE: &str = "application/encom";
+
+/// An EnCom request or response body.
+///
+/// As an extractor, it rejects the request unless its `content-type` is
+/// `application/encom`, or a `+encom` type such as
+/// `application/vnd.api+encom`. The body is read up to the limit of axum's
+/// `DefaultBodyLimit`, 2 MB unless a layer changes it, and deserialized
+/// with [`from_slice`](crate::from_slice), so `T` can't borrow from it. See
+/// [`EncomRejection`] for the status codes of the rejections.
+///
+/// As a response, it is serialized with [`to_vec`](crate::to_vec), so bytes
+/// are written raw. If serialization fails, the response is a
+/// `500 Internal Server Error` with the error as plain text.
+#[derive(Debug, Clone, Copy, Default)]
+#[must_use]
+pub struct Encom<T>(pub T);
+
+impl<T, S> FromRequest<S> for Encom<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = EncomRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        if !encom_content_type(req.headers()) {
+            return Err(EncomRejection::MissingContentType);
+        }
+        let bytes = Bytes::from_request(req, state)
+            .await
+            .map_err(EncomRejection::Body)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl<T> Encom<T>
+where
+    T: DeserializeOwned,
+{
+    /// Deserializes a body that has already been read, with the same
+    /// rejections as the extractor.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, EncomRejection> {
+        crate::from_slice(bytes)
+            .map(Encom)
+            .map_err(EncomRejection::from)
+    }
+}
+
+impl<T> IntoResponse for Encom<T>
+where
+    T: Serialize,
+{
+    fn into_response(self) -> Response {
+        match crate::to_vec(&self.0) {
+            Ok(buf) => (
+                [(header::CONTENT_TYPE, HeaderValue::from_static(CONTENT_TYPE))],
+                buf,
+            )
+                .into_response(),
+            Err(err) => (StatusCode::INTERNAL_SERVER_ERROR, err.to_string()).into_response(),
+        }
+    }
+}
+
+impl<T> From<T> for Encom<T> {
+    fn from(inner: T) -> Self {
+        Encom(inner)
+    }
+}
+
+impl<T> Deref for Encom<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Encom<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+/// Whether the `content-type` is `application/encom` or
+/// `application/*+encom`, ignoring parameters such as `charset`.
+fn encom_content_type(headers: &HeaderMap) -> bool {
+    let Some(content_type) = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+    else {
+        return false;
+    };
+    let essence = content_type.split(';').next().unwrap_or_default().trim();
+    let Some((kind, subtype)) = essence.split_once('/') else {
+        return false;
+    };
+    kind.eq_ignore_ascii_case("application")
+        && (subtype.eq_ignore_ascii_case("encom")
+            || subtype
+                .rsplit_once('+')
+                .is_some_and(|(_, suffix)| suffix.eq_ignore_ascii_case("encom")))
+}
+
+/// Why an [`Encom`] extractor rejected a request.
+///
+/// As a response, the rejection has its [`status`](Self::status) and its
+/// message as plain text.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum EncomRejection {
+    /// The `content-type` is missing or not EnCom:
+    /// `415 Unsupported Media Type`.
+    MissingContentType,
+    /// The body couldn't be read, e.g. `413 Payload Too Large` if it is over
+    /// the body limit.
+    Body(BytesRejection),
+    /// The body isn't valid EnCom or ends early: `400 Bad Request`.
+    Syntax(Error),
+    /// The body is valid EnCom that doesn't match `T`, such as a missing
+    /// field or a number out of range: `422 Unprocessable Entity`.
+    Data(Error),
+}
+
+impl EncomRejection {
+    /// The status code of the response.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            EncomRejection::MissingContentType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            EncomRejection::Body(rejection) => rejection.status(),
+            EncomRejection::Syntax(_) => StatusCode::BAD_REQUEST,
+            EncomRejection::Data(_) => StatusCode::UNPROCESSABLE_ENTITY,
+        }
+    }
+}
+
+impl From<Error> for EncomRejection {
+    /// Sorts a deserialization error by [`Error::classify`].
+    fn from(err: Error) -> Self {
+        match err.classify() {
+            ErrorCategory::Data => EncomRejection::Data(err),
+            ErrorCategory::Io | ErrorCategory::Syntax | ErrorCategory::Eof => {
+                EncomRejection::Syntax(err)
+            }
+        }
+    }
+}
+
+impl Display for EncomRejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EncomRejection::MissingContentType => {
+                write!(f, "Expected request with `Content-Type: {}`", CONTENT_TYPE)
+            }
+            EncomRejection::Body(rejection) => Display::fmt(rejection, f),
+            EncomRejection::Syntax(err) => {
+                write!(f, "Failed to parse the request body as EnCom: {}", err)
+            }
+            EncomRejection::Data(err) => write!(
+                f,
+                "Failed to deserialize the EnCom body into the target type: {}",
+                err
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EncomRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EncomRejection::MissingContentType => None,
+            EncomRejection::Body(rejection) => Some(rejection),
+            EncomRejection::Syntax(err) | EncomRejection::Data(err) => Some(err),
+        }
+    }
+}
+
+impl IntoResponse for EncomRejection {
+    fn into_response(self) -> Response {
+        match self {
+            EncomRejection::Body(rejection) => rejection.into_response(),
+            rejection => (rejection.status(), rejection.to_string()).into_response(),
+        }
+    }
+}
@@ -0,0 +1,303 @@
+//! The `encom` command-line tool.
+//!
+//! Formats, converts and validates EnCom documents. Every command reads a
+//! file, or stdin when there is none or it is `-`, and writes to stdout, so
+//! that it can be used in shell pipelines.
+
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use serde_encom::schema::Schema;
+use serde_encom::value::BytesPolicy;
+use serde_encom::Value;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(
+    name = "encom",
+    version,
+    about = "Format, convert and validate EnCom documents"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Reformat a document.
+    Fmt {
+        #[command(flatten)]
+        input: Input,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Convert a JSON document to EnCom.
+    FromJson {
+        #[command(flatten)]
+        input: Input,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Convert a document to JSON.
+    ToJson {
+        #[command(flatten)]
+        input: Input,
+        #[command(flatten)]
+        output: Output,
+        /// How to write byte strings, which JSON has no type for.
+        #[arg(long, value_enum, default_value_t = Bytes::Error)]
+        bytes: Bytes,
+    },
+    /// Check that a document is valid EnCom, and that it matches a schema.
+    Validate {
+        #[command(flatten)]
+        input: Input,
+        /// File with the EnCom schema to check the document against.
+        #[arg(long)]
+        schema: Option<PathBuf>,
+    },
+    /// Print the value at an EnCom Pointer, such as `/users/0/name`.
+    Get {
+        /// The pointer; an empty one is the whole document.
+        pointer: String,
+        #[command(flatten)]
+        input: Input,
+        #[command(flatten)]
+        output: Output,
+        /// Print a string as its plain text.
+        #[arg(short, long)]
+        raw: bool,
+    },
+    /// Print the size, depth and number of values of each type of a document.
+    Stats {
+        #[command(flatten)]
+        input: Input,
+    },
+}
+
+#[derive(Args)]
+struct Input {
+    /// File to read, or `-` for stdin [default: stdin]
+    file: Option<PathBuf>,
+}
+
+impl Input {
+    fn name(&self) -> String {
+        match &self.file {
+            Some(path) if path.as_os_str() != "-" => path.display().to_string(),
+            _ => "<stdin>".to_string(),
+        }
+    }
+
+    fn read(&self) -> Result<Vec<u8>, String> {
+        let mut data = Vec::new();
+        let result = match &self.file {
+            Some(path) if path.as_os_str() != "-" => {
+                fs::File::open(path).and_then(|mut file| file.read_to_end(&mut data))
+            }
+            _ => io::stdin().lock().read_to_end(&mut data),
+        };
+        result.map_err(|err| format!("{}: {}", self.name(), err))?;
+        Ok(data)
+    }
+
+    fn parse(&self) -> Result<(Value, usize), String> {
+        let data = self.read()?;
+        let value =
+            serde_encom::from_slice(&data).map_err(|err| format!("{}: {}", self.name(), err))?;
+        Ok((value, data.len()))
+    }
+}
+
+#[derive(Args)]
+struct Output {
+    /// Write the output on one line.
+    #[arg(short, long)]
+    compact: bool,
+}
+
+impl Output {
+    fn encom(&self, value: &Value) -> Result<String, String> {
+        let text = if self.compact {
+            serde_encom::to_string(value)
+        } else {
+            serde_encom::to_string_pretty(value)
+        };
+        text.map_err(|err| err.to_string())
+    }
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Bytes {
+    /// Fail.
+    Error,
+    /// Write a string of base64.
+    Base64,
+    /// Write an array of numbers.
+    Array,
+}
+
+impl From<Bytes> for BytesPolicy {
+    fn from(bytes: Bytes) -> Self {
+        match bytes {
+            Bytes::Error => BytesPolicy::Error,
+            Bytes::Base64 => BytesPolicy::Base64,
+            Bytes::Array => BytesPolicy::Array,
+        }
+    }
+}
+
+/// Counts of the values in a document.
+#[derive(Default, Serialize)]
+struct Stats {
+    /// Size of the document in bytes.
+    size: usize,
+    /// Deepest nesting of arrays and maps.
+    depth: usize,
+    values: u64,
+    /// Entries of all maps.
+    keys: u64,
+    null: u64,
+    bool: u64,
+    int: u64,
+    float: u64,
+    string: u64,
+    bytes: u64,
+    datetime: u64,
+    array: u64,
+    object: u64,
+}
+
+impl Stats {
+    fn count(&mut self, value: &Value, depth: usize) {
+        self.values += 1;
+        self.depth = self.depth.max(depth);
+        match value {
+            Value::Null => self.null += 1,
+            Value::Bool(_) => self.bool += 1,
+            Value::Number(n) if n.is_f64() => self.float += 1,
+            Value::Number(_) => self.int += 1,
+            Value::String(_) => self.string += 1,
+            Value::Bytes(_) => self.bytes += 1,
+            Value::DateTime(_) => self.datetime += 1,
+            Value::Array(list) => {
+                self.array += 1;
+                for item in list {
+                    self.count(item, depth + 1);
+                }
+            }
+            Value::Object(map) => {
+                self.object += 1;
+                self.keys += map.len() as u64;
+                for value in map.values() {
+                    self.count(value, depth + 1);
+                }
+            }
+        }
+    }
+}
+
+/// Runs a command and returns what to print, which may be nothing, or
+/// `None` if the command failed after reporting why.
+fn run(command: Command) -> Result<Option<String>, String> {
+    Ok(Some(match command {
+        Command::Fmt { input, output } => output.encom(&input.parse()?.0)?,
+        Command::FromJson { input, output } => {
+            let data = input.read()?;
+            let json: serde_json::Value = serde_json::from_slice(&data)
+                .map_err(|err| format!("{}: {}", input.name(), err))?;
+            output.encom(&Value::from(json))?
+        }
+        Command::ToJson {
+            input,
+            output,
+            bytes,
+        } => {
+            let json = input
+                .parse()?
+                .0
+                .into_json(bytes.into())
+                .map_err(|err| format!("{}: {}", input.name(), err))?;
+            let text = if output.compact {
+                serde_json::to_string(&json)
+            } else {
+                serde_json::to_string_pretty(&json)
+            };
+            text.map_err(|err| err.to_string())?
+        }
+        Command::Validate { input, schema } => {
+            let data = input.read()?;
+            let Some(path) = schema else {
+                serde_encom::from_slice::<Value>(&data)
+                    .map_err(|err| format!("{}: {}", input.name(), err))?;
+                return Ok(Some(String::new()));
+            };
+            let text =
+                fs::read_to_string(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+            let schema: Schema = text
+                .parse()
+                .map_err(|err| format!("{}: {}", path.display(), err))?;
+            match schema.validate_slice(&data) {
+                Ok(()) => String::new(),
+                Err(errors) => {
+                    for error in errors {
+                        eprintln!("{}: {}", input.name(), error);
+                    }
+                    return Ok(None);
+                }
+            }
+        }
+        Command::Get {
+            pointer,
+            input,
+            output,
+            raw,
+        } => {
+            let (value, _) = input.parse()?;
+            let Some(value) = value.pointer(&pointer) else {
+                return Err(format!("{}: no value at `{}`", input.name(), pointer));
+            };
+            match value {
+                Value::String(s) if raw => s.clone(),
+                value => output.encom(value)?,
+            }
+        }
+        Command::Stats { input } => {
+            let (value, size) = input.parse()?;
+            let mut stats = Stats {
+                size,
+                ..Stats::default()
+            };
+            stats.count(&value, 0);
+            serde_encom::to_string_pretty(&stats).map_err(|err| err.to_string())?
+        }
+    }))
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let text = match run(cli.command) {
+        Ok(Some(text)) => text,
+        Ok(None) => return ExitCode::FAILURE,
+        Err(err) => {
+            eprintln!("encom: {}", err);
+            return ExitCode::FAILURE;
+        }
+    };
+    if text.is_empty() {
+        return ExitCode::SUCCESS;
+    }
+
+    let mut stdout = io::stdout().lock();
+    match writeln!(stdout, "{}", text).and_then(|()| stdout.flush()) {
+        // The reader of a pipeline has stopped reading.
+        Err(err) if err.kind() != io::ErrorKind::BrokenPipe => {
+            eprintln!("encom: {}", err);
+            ExitCode::FAILURE
+        }
+        _ => ExitCode::SUCCESS,
+    }
+}
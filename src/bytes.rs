@@ -0,0 +1,136 @@
+//! How bytes are written after their length prefix.
+
+use crate::io;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use base64::{engine::general_purpose::STANDARD_NO_PAD, Engine as _};
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// How bytes, like `serde_bytes` fields or [`Value::Bytes`], are written.
+///
+/// The length prefix counts what follows the marker, so a reader can skip
+/// bytes of every mode without decoding them. All modes are read back by
+/// the deserializer, whichever mode the input was written with, but only
+/// raw bytes can be borrowed as `&[u8]`, and decoding hex or base64 needs
+/// the `alloc` feature.
+///
+/// Raw bytes that aren't valid UTF-8 can't be part of a `String`, so
+/// [`to_string`](crate::to_string) and the `Display` impl of [`Value`] write
+/// base64. See [`Options::bytes`](crate::Options::bytes).
+///
+/// [`Value`]: crate::Value
+/// [`Value::Bytes`]: crate::Value::Bytes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum BytesMode {
+    /// `~` and the bytes as they are, e.g. `2~` and the bytes `0x01 0xff`.
+    Raw,
+    /// `#` and two lowercase hex digits per byte, e.g. `4#01ff`.
+    Hex,
+    /// `$` and base64 with the standard alphabet and without padding, e.g.
+    /// `3$Af8`.
+    Base64,
+}
+
+impl BytesMode {
+    /// The mode of the bytes whose length prefix is followed by `marker`.
+    #[inline]
+    pub(crate) fn from_marker(marker: u8) -> Option<Self> {
+        match marker {
+            b'~' => Some(BytesMode::Raw),
+            b'#' => Some(BytesMode::Hex),
+            b'$' => Some(BytesMode::Base64),
+            _ => None,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn marker(self) -> u8 {
+        match self {
+            BytesMode::Raw => b'~',
+            BytesMode::Hex => b'#',
+            BytesMode::Base64 => b'$',
+        }
+    }
+
+    /// The length prefix of `len` bytes.
+    pub(crate) fn encoded_len(self, len: usize) -> usize {
+        match self {
+            BytesMode::Raw => len,
+            BytesMode::Hex => len * 2,
+            BytesMode::Base64 => len / 3 * 4 + [0, 2, 3][len % 3],
+        }
+    }
+
+    /// The number of bytes behind a length prefix of `len`, or `None` if no
+    /// bytes are encoded to that length.
+    pub(crate) fn decoded_len(self, len: usize) -> Option<usize> {
+        match (self, len % 4) {
+            (BytesMode::Raw, _) => Some(len),
+            (BytesMode::Hex, _) if len.is_multiple_of(2) => Some(len / 2),
+            (BytesMode::Base64, rem) if rem != 1 => Some(len / 4 * 3 + rem.saturating_sub(1)),
+            _ => None,
+        }
+    }
+
+    /// Writes `value` encoded, without the length prefix and the marker.
+    pub(crate) fn encode<W>(self, writer: &mut W, value: &[u8]) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        let mut buf = [0; 128];
+        match self {
+            BytesMode::Raw => writer.write_all(value),
+            BytesMode::Hex => {
+                for chunk in value.chunks(buf.len() / 2) {
+                    for (&byte, digits) in chunk.iter().zip(buf.chunks_exact_mut(2)) {
+                        digits[0] = HEX_DIGITS[(byte >> 4) as usize];
+                        digits[1] = HEX_DIGITS[(byte & 0xf) as usize];
+                    }
+                    writer.write_all(&buf[..chunk.len() * 2])?;
+                }
+                Ok(())
+            }
+            BytesMode::Base64 => {
+                // Whole groups of 3 bytes, so that only the last chunk is
+                // without padding.
+                for chunk in value.chunks(buf.len() / 4 * 3) {
+                    let len = STANDARD_NO_PAD
+                        .encode_slice(chunk, &mut buf)
+                        .map_err(|_| io::Error::other("base64 buffer is too small"))?;
+                    writer.write_all(&buf[..len])?;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Decodes the bytes that follow the marker, or returns `None` if they
+    /// are not valid in this mode.
+    #[cfg(feature = "alloc")]
+    pub(crate) fn decode(self, encoded: &[u8]) -> Option<Vec<u8>> {
+        match self {
+            BytesMode::Raw => Some(encoded.to_vec()),
+            BytesMode::Hex => {
+                if !encoded.len().is_multiple_of(2) {
+                    return None;
+                }
+                encoded
+                    .chunks_exact(2)
+                    .map(|digits| Some(hex_digit(digits[0])? << 4 | hex_digit(digits[1])?))
+                    .collect()
+            }
+            BytesMode::Base64 => STANDARD_NO_PAD.decode(encoded).ok(),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+fn hex_digit(ch: u8) -> Option<u8> {
+    match ch {
+        b'0'..=b'9' => Some(ch - b'0'),
+        b'a'..=b'f' => Some(ch - b'a' + 10),
+        b'A'..=b'F' => Some(ch - b'A' + 10),
+        _ => None,
+    }
+}
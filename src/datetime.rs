@@ -0,0 +1,560 @@
+//! Timestamps, written as `N@` and an RFC 3339 date-time.
+
+use crate::error::{Error, ErrorCode, Result};
+use core::fmt::{self, Debug, Display};
+use core::str::{self, FromStr};
+use serde::de::value::{BorrowedStrDeserializer, StrDeserializer};
+use serde::de::{self, DeserializeSeed, Deserializer, MapAccess, Unexpected, Visitor};
+use serde::ser::{Serialize, Serializer};
+
+/// Name of the newtype struct that a [`DateTime`] serializes as. The EnCom
+/// serializers and deserializers know it, other formats see a string.
+pub(crate) const TOKEN: &str = "$serde_encom::private::DateTime";
+
+/// The longest text of a date-time, `9999-12-31T23:59:59.999999999+23:59`.
+pub(crate) const MAX_LEN: usize = 35;
+
+const SECS_PER_DAY: i64 = 86_400;
+const NANOS_PER_SEC: u32 = 1_000_000_000;
+/// `0000-01-01T00:00:00Z`.
+const MIN_SECS: i64 = -62_167_219_200;
+/// `9999-12-31T23:59:59Z`.
+const MAX_SECS: i64 = 253_402_300_799;
+
+/// A date and time with a UTC offset, the timestamp value of EnCom.
+///
+/// It is written as a length prefix, `@` and an [RFC 3339] date-time, e.g.
+/// `20@2023-11-14T22:13:20Z`, with as many fractional digits as needed and
+/// `Z` for UTC. Like a string, the length prefix has to match, and the
+/// date-time is parsed strictly: a four-digit year from 0000 to 9999, an
+/// uppercase `T` and `Z`, up to nine fractional digits, no leap seconds,
+/// and an offset of whole minutes less than a day.
+///
+/// A `DateTime` reads into a [`Value::DateTime`](crate::Value::DateTime)
+/// and is written back the same, so timestamps don't turn into strings.
+/// Other formats see a string, from which a `DateTime` deserializes too.
+/// With the `chrono` and `time` features it converts to and from
+/// `chrono::DateTime` and `time::OffsetDateTime`, and the adapters in
+/// [`with`](crate::with) write those as EnCom timestamps.
+///
+/// Two date-times are equal if they are the same instant with the same
+/// offset, and are ordered by instant first. EnCom map keys can't contain a
+/// `:`, so maps keyed by date-time need [`with::pairs`](crate::with::pairs).
+///
+/// # Examples
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use serde_encom::DateTime;
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Event {
+///     at: DateTime,
+/// }
+///
+/// let event = Event {
+///     at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+/// };
+/// let encom = serde_encom::to_string(&event).unwrap();
+/// assert_eq!(encom, "at:20@2023-11-14T22:13:20Z");
+/// assert_eq!(serde_encom::from_str::<Event>(&encom).unwrap(), event);
+///
+/// // The length prefix has to match.
+/// assert!(serde_encom::from_str::<Event>("at:19@2023-11-14T22:13:20Z").is_err());
+/// ```
+///
+/// [RFC 3339]: https://www.rfc-editor.org/rfc/rfc3339
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateTime {
+    /// Seconds since the Unix epoch, in UTC.
+    secs: i64,
+    nanos: u32,
+    /// Minutes east of UTC.
+    offset: i16,
+}
+
+impl DateTime {
+    /// The earliest date-time, `0000-01-01T00:00:00Z`.
+    pub const MIN: DateTime = DateTime {
+        secs: MIN_SECS,
+        nanos: 0,
+        offset: 0,
+    };
+
+    /// The latest date-time, `9999-12-31T23:59:59.999999999Z`.
+    pub const MAX: DateTime = DateTime {
+        secs: MAX_SECS,
+        nanos: NANOS_PER_SEC - 1,
+        offset: 0,
+    };
+
+    /// The Unix epoch, `1970-01-01T00:00:00Z`.
+    pub const UNIX_EPOCH: DateTime = DateTime {
+        secs: 0,
+        nanos: 0,
+        offset: 0,
+    };
+
+    /// Creates a UTC date-time from seconds and nanoseconds since the Unix
+    /// epoch, or returns `None` if it is outside of the years 0000 to 9999
+    /// or `nanos` isn't less than a second.
+    ///
+    /// ```
+    /// use serde_encom::DateTime;
+    ///
+    /// let dt = DateTime::from_timestamp(-1, 500_000_000).unwrap();
+    /// assert_eq!(dt.to_string(), "1969-12-31T23:59:59.5Z");
+    ///
+    /// assert_eq!(DateTime::from_timestamp(0, 1_000_000_000), None);
+    /// ```
+    pub fn from_timestamp(secs: i64, nanos: u32) -> Option<DateTime> {
+        if nanos >= NANOS_PER_SEC || !(MIN_SECS..=MAX_SECS).contains(&secs) {
+            return None;
+        }
+        Some(DateTime {
+            secs,
+            nanos,
+            offset: 0,
+        })
+    }
+
+    /// Returns the same instant at an offset of `minutes` east of UTC, or
+    /// `None` if the offset isn't less than a day or the local date-time is
+    /// outside of the years 0000 to 9999.
+    ///
+    /// ```
+    /// use serde_encom::DateTime;
+    ///
+    /// let dt = DateTime::from_timestamp(1_700_000_000, 0).unwrap();
+    /// let local = dt.with_offset(-330).unwrap();
+    /// assert_eq!(local.to_string(), "2023-11-14T16:43:20-05:30");
+    /// assert_eq!(local.timestamp(), dt.timestamp());
+    /// ```
+    pub fn with_offset(self, minutes: i16) -> Option<DateTime> {
+        let local = self.secs + i64::from(minutes) * 60;
+        if minutes.unsigned_abs() >= 24 * 60 || !(MIN_SECS..=MAX_SECS).contains(&local) {
+            return None;
+        }
+        Some(DateTime {
+            offset: minutes,
+            ..self
+        })
+    }
+
+    /// Seconds since the Unix epoch.
+    pub fn timestamp(&self) -> i64 {
+        self.secs
+    }
+
+    /// Nanoseconds after [`timestamp`](Self::timestamp).
+    pub fn timestamp_subsec_nanos(&self) -> u32 {
+        self.nanos
+    }
+
+    /// The offset from UTC in minutes, positive east of UTC.
+    pub fn offset_minutes(&self) -> i16 {
+        self.offset
+    }
+
+    /// Writes the RFC 3339 text into `buf`.
+    pub(crate) fn format<'b>(&self, buf: &'b mut [u8; MAX_LEN]) -> &'b str {
+        let local = self.secs + i64::from(self.offset) * 60;
+        let (year, month, day) = civil_from_days(local.div_euclid(SECS_PER_DAY));
+        let secs = local.rem_euclid(SECS_PER_DAY) as u32;
+
+        *buf = *b"0000-00-00T00:00:00.000000000+00:00";
+        put_digits(&mut buf[0..4], year);
+        put_digits(&mut buf[5..7], month);
+        put_digits(&mut buf[8..10], day);
+        put_digits(&mut buf[11..13], secs / 3600);
+        put_digits(&mut buf[14..16], secs / 60 % 60);
+        put_digits(&mut buf[17..19], secs % 60);
+
+        let mut len = 19;
+        if self.nanos != 0 {
+            put_digits(&mut buf[20..29], self.nanos);
+            len = 29;
+            while buf[len - 1] == b'0' {
+                len -= 1;
+            }
+        }
+        if self.offset == 0 {
+            buf[len] = b'Z';
+            len += 1;
+        } else {
+            let minutes = u32::from(self.offset.unsigned_abs());
+            buf[len] = if self.offset < 0 { b'-' } else { b'+' };
+            put_digits(&mut buf[len + 1..len + 3], minutes / 60);
+            buf[len + 3] = b':';
+            put_digits(&mut buf[len + 4..len + 6], minutes % 60);
+            len += 6;
+        }
+
+        // SAFETY: the text is ASCII.
+        unsafe { str::from_utf8_unchecked(&buf[..len]) }
+    }
+}
+
+/// Fills `out` with the last decimal digits of `n`.
+fn put_digits(out: &mut [u8], mut n: u32) {
+    for digit in out.iter_mut().rev() {
+        *digit = b'0' + (n % 10) as u8;
+        n /= 10;
+    }
+}
+
+/// The decimal number of `digits`, or `None` if one isn't a digit.
+fn parse_digits(digits: &[u8]) -> Option<u32> {
+    digits.iter().try_fold(0u32, |n, &ch| match ch {
+        b'0'..=b'9' => Some(n * 10 + u32::from(ch - b'0')),
+        _ => None,
+    })
+}
+
+fn is_leap_year(year: u32) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Days since the Unix epoch of a date in the proleptic Gregorian calendar,
+/// after Howard Hinnant's `days_from_civil`.
+fn days_from_civil(year: u32, month: u32, day: u32) -> i64 {
+    let year = i64::from(year) - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (i64::from(month) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// The date of a number of days since the Unix epoch, the inverse of
+/// `days_from_civil`.
+fn civil_from_days(days: i64) -> (u32, u32, u32) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let doe = days - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year as u32, month as u32, day as u32)
+}
+
+/// Parses `YYYY-MM-DDTHH:MM:SS[.F]{Z|+HH:MM|-HH:MM}`.
+fn parse(s: &[u8]) -> Option<DateTime> {
+    if s.len() < 20
+        || s[4] != b'-'
+        || s[7] != b'-'
+        || s[10] != b'T'
+        || s[13] != b':'
+        || s[16] != b':'
+    {
+        return None;
+    }
+    let year = parse_digits(&s[0..4])?;
+    let month = parse_digits(&s[5..7])?;
+    let day = parse_digits(&s[8..10])?;
+    let hour = parse_digits(&s[11..13])?;
+    let minute = parse_digits(&s[14..16])?;
+    let second = parse_digits(&s[17..19])?;
+    if !(1..=12).contains(&month)
+        || day == 0
+        || day > days_in_month(year, month)
+        || hour > 23
+        || minute > 59
+        || second > 59
+    {
+        return None;
+    }
+
+    let mut rest = &s[19..];
+    let mut nanos = 0;
+    if let [b'.', fraction @ ..] = rest {
+        let len = fraction.iter().take_while(|ch| ch.is_ascii_digit()).count();
+        if len == 0 || len > 9 {
+            return None;
+        }
+        nanos = parse_digits(&fraction[..len])? * 10u32.pow(9 - len as u32);
+        rest = &fraction[len..];
+    }
+
+    let offset = match *rest {
+        [b'Z'] => 0,
+        [sign @ (b'+' | b'-'), h1, h2, b':', m1, m2] => {
+            let hours = parse_digits(&[h1, h2])?;
+            let minutes = parse_digits(&[m1, m2])?;
+            if hours > 23 || minutes > 59 {
+                return None;
+            }
+            let offset = (hours * 60 + minutes) as i16;
+            if sign == b'-' {
+                -offset
+            } else {
+                offset
+            }
+        }
+        _ => return None,
+    };
+
+    let local = days_from_civil(year, month, day) * SECS_PER_DAY
+        + i64::from(hour * 3600 + minute * 60 + second);
+    DateTime::from_timestamp(local - i64::from(offset) * 60, nanos)?.with_offset(offset)
+}
+
+impl FromStr for DateTime {
+    type Err = Error;
+
+    /// Parses an RFC 3339 date-time as strictly as it is read from EnCom.
+    ///
+    /// ```
+    /// use serde_encom::DateTime;
+    ///
+    /// let dt: DateTime = "2023-11-14T23:13:20.25+01:00".parse().unwrap();
+    /// assert_eq!(dt.timestamp(), 1_700_000_000);
+    /// assert_eq!(dt.timestamp_subsec_nanos(), 250_000_000);
+    /// assert_eq!(dt.offset_minutes(), 60);
+    ///
+    /// assert!("2023-02-29T00:00:00Z".parse::<DateTime>().is_err());
+    /// assert!("2023-11-14 23:13:20Z".parse::<DateTime>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<DateTime> {
+        parse(s.as_bytes()).ok_or_else(|| Error::syntax(ErrorCode::InvalidDateTime, 0, 0))
+    }
+}
+
+impl Display for DateTime {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str(self.format(&mut [0; MAX_LEN]))
+    }
+}
+
+impl Debug for DateTime {
+    fn fmt(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        write!(formatter, "DateTime({})", self)
+    }
+}
+
+impl Serialize for DateTime {
+    fn serialize<S>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(TOKEN, self.format(&mut [0; MAX_LEN]))
+    }
+}
+
+impl<'de> de::Deserialize<'de> for DateTime {
+    fn deserialize<D>(deserializer: D) -> core::result::Result<DateTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(TOKEN, DateTimeVisitor)
+    }
+}
+
+/// Reads a date-time from its text, or from the map of a single [`TOKEN`]
+/// entry that the EnCom deserializers give to `deserialize_any`.
+pub(crate) struct DateTimeVisitor;
+
+impl<'de> Visitor<'de> for DateTimeVisitor {
+    type Value = DateTime;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an RFC 3339 date-time")
+    }
+
+    fn visit_str<E>(self, v: &str) -> core::result::Result<DateTime, E>
+    where
+        E: de::Error,
+    {
+        parse(v.as_bytes()).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> core::result::Result<DateTime, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        match map.next_key_seed(TokenKey)? {
+            Some(true) => map.next_value_seed(self),
+            _ => Err(de::Error::invalid_type(Unexpected::Map, &self)),
+        }
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> core::result::Result<DateTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        // A buffered EnCom date-time, as in an untagged enum, is a map, but
+        // binary formats can't tell what is next.
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(self)
+        } else {
+            deserializer.deserialize_str(self)
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for DateTimeVisitor {
+    type Value = DateTime;
+
+    fn deserialize<D>(self, deserializer: D) -> core::result::Result<DateTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+/// Whether a map key is [`TOKEN`].
+struct TokenKey;
+
+impl<'de> DeserializeSeed<'de> for TokenKey {
+    type Value = bool;
+
+    fn deserialize<D>(self, deserializer: D) -> core::result::Result<bool, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de> Visitor<'de> for TokenKey {
+    type Value = bool;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a string key")
+    }
+
+    fn visit_str<E>(self, s: &str) -> core::result::Result<bool, E>
+    where
+        E: de::Error,
+    {
+        Ok(s == TOKEN)
+    }
+}
+
+/// The map of a single [`TOKEN`] entry with the text of a date-time.
+pub(crate) struct DateTimeAccess<'a> {
+    text: Option<&'a str>,
+    key: bool,
+}
+
+impl<'a> DateTimeAccess<'a> {
+    pub(crate) fn new(text: &'a str) -> Self {
+        DateTimeAccess {
+            text: Some(text),
+            key: true,
+        }
+    }
+}
+
+impl<'de> MapAccess<'de> for DateTimeAccess<'_> {
+    type Error = Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        if !self.key {
+            return Ok(None);
+        }
+        self.key = false;
+        seed.deserialize(BorrowedStrDeserializer::new(TOKEN))
+            .map(Some)
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        match self.text.take() {
+            Some(text) => seed.deserialize(StrDeserializer::new(text)),
+            None => Err(de::Error::custom("value is missing")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(usize::from(self.key))
+    }
+}
+
+fn out_of_range() -> Error {
+    Error::syntax(ErrorCode::DateTimeOutOfRange, 0, 0)
+}
+
+#[cfg(feature = "chrono")]
+impl<Tz: chrono::TimeZone> TryFrom<chrono::DateTime<Tz>> for DateTime {
+    type Error = Error;
+
+    /// Fails for years outside of 0000 to 9999, leap seconds and offsets
+    /// that aren't whole minutes.
+    fn try_from(value: chrono::DateTime<Tz>) -> Result<DateTime> {
+        use chrono::Offset;
+
+        let offset = value.offset().fix().local_minus_utc();
+        if offset % 60 != 0 {
+            return Err(out_of_range());
+        }
+        DateTime::from_timestamp(value.timestamp(), value.timestamp_subsec_nanos())
+            .and_then(|dt| dt.with_offset((offset / 60) as i16))
+            .ok_or_else(out_of_range)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<DateTime> for chrono::DateTime<chrono::FixedOffset> {
+    fn from(value: DateTime) -> Self {
+        let offset = chrono::FixedOffset::east_opt(i32::from(value.offset) * 60)
+            .expect("offset is less than a day");
+        chrono::DateTime::<chrono::Utc>::from(value).with_timezone(&offset)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl From<DateTime> for chrono::DateTime<chrono::Utc> {
+    /// Converts to the same instant in UTC.
+    fn from(value: DateTime) -> Self {
+        chrono::DateTime::from_timestamp(value.secs, value.nanos)
+            .expect("years 0000 to 9999 are in range")
+    }
+}
+
+#[cfg(feature = "time")]
+impl TryFrom<time::OffsetDateTime> for DateTime {
+    type Error = Error;
+
+    /// Fails for years before 0000 and offsets that aren't whole minutes.
+    fn try_from(value: time::OffsetDateTime) -> Result<DateTime> {
+        let offset = value.offset().whole_seconds();
+        if offset % 60 != 0 {
+            return Err(out_of_range());
+        }
+        DateTime::from_timestamp(value.unix_timestamp(), value.nanosecond())
+            .and_then(|dt| dt.with_offset((offset / 60) as i16))
+            .ok_or_else(out_of_range)
+    }
+}
+
+#[cfg(feature = "time")]
+impl From<DateTime> for time::OffsetDateTime {
+    fn from(value: DateTime) -> Self {
+        let nanos = i128::from(value.secs) * i128::from(NANOS_PER_SEC) + i128::from(value.nanos);
+        let offset = time::UtcOffset::from_whole_seconds(i32::from(value.offset) * 60)
+            .expect("offset is less than a day");
+        time::OffsetDateTime::from_unix_timestamp_nanos(nanos)
+            .expect("years 0000 to 9999 are in range")
+            .to_offset(offset)
+    }
+}
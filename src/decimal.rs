@@ -0,0 +1,124 @@
+//! Exact decimals, written as EnCom numbers with the digits of a
+//! `rust_decimal::Decimal`.
+
+use core::fmt::{self, Write};
+use core::str;
+use rust_decimal::Decimal;
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+
+/// Name of the newtype struct that [`with::rust_decimal`] serializes a
+/// decimal as. The EnCom serializers and deserializers know it, other formats
+/// see a string.
+///
+/// [`with::rust_decimal`]: crate::with::rust_decimal
+pub(crate) const TOKEN: &str = "$serde_encom::private::Decimal";
+
+/// The longest text of a decimal, `-0.0000000000000000000000000001`.
+pub(crate) const MAX_LEN: usize = 31;
+
+/// Writes the text of `value` into `buf`, with as many fractional digits as
+/// its scale, e.g. `12.50`.
+pub(crate) fn format<'b>(value: &Decimal, buf: &'b mut [u8; MAX_LEN]) -> &'b str {
+    struct Buf<'b> {
+        bytes: &'b mut [u8; MAX_LEN],
+        len: usize,
+    }
+
+    impl Write for Buf<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let end = self.len + s.len();
+            self.bytes
+                .get_mut(self.len..end)
+                .ok_or(fmt::Error)?
+                .copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    let mut out = Buf { bytes: buf, len: 0 };
+    write!(out, "{}", value).expect("a decimal fits in MAX_LEN bytes");
+    let len = out.len;
+    // Only ASCII digits, `-` and `.` were written.
+    unsafe { str::from_utf8_unchecked(&buf[..len]) }
+}
+
+/// Reads a decimal from the text of a number or a string, and from integers
+/// and floats of formats that have no text for them.
+pub(crate) struct DecimalVisitor;
+
+impl<'de> Visitor<'de> for DecimalVisitor {
+    type Value = Decimal;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a decimal")
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Decimal, E>
+    where
+        E: de::Error,
+    {
+        Ok(Decimal::from(v))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Decimal, E>
+    where
+        E: de::Error,
+    {
+        Ok(Decimal::from(v))
+    }
+
+    fn visit_i128<E>(self, v: i128) -> Result<Decimal, E>
+    where
+        E: de::Error,
+    {
+        Decimal::try_from_i128_with_scale(v, 0)
+            .map_err(|_| E::invalid_value(Unexpected::Other("integer"), &self))
+    }
+
+    fn visit_u128<E>(self, v: u128) -> Result<Decimal, E>
+    where
+        E: de::Error,
+    {
+        i128::try_from(v)
+            .ok()
+            .and_then(|v| Decimal::try_from_i128_with_scale(v, 0).ok())
+            .ok_or_else(|| E::invalid_value(Unexpected::Other("integer"), &self))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Decimal, E>
+    where
+        E: de::Error,
+    {
+        // The shortest text that reads back as `v`, not its binary expansion.
+        let mut buf = ryu::Buffer::new();
+        Some(v)
+            .filter(|v| v.is_finite())
+            .and_then(|v| parse(buf.format_finite(v)))
+            .ok_or_else(|| E::invalid_value(Unexpected::Float(v), &self))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Decimal, E>
+    where
+        E: de::Error,
+    {
+        parse(v).ok_or_else(|| E::invalid_value(Unexpected::Str(v), &self))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Decimal, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+/// Parses the text of a number, failing rather than rounding digits that
+/// don't fit.
+fn parse(text: &str) -> Option<Decimal> {
+    if text.contains(['e', 'E']) {
+        Decimal::from_scientific(text).ok()
+    } else {
+        Decimal::from_str_exact(text).ok()
+    }
+}
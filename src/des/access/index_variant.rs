@@ -0,0 +1,87 @@
+use crate::{
+    des::{deserializer::Deserializer, read::Read},
+    error::{Error, Result},
+};
+use serde::de::{self, value::U64Deserializer, IntoDeserializer, Unexpected};
+
+/// Enum variant written by its index, like `2` for a unit variant or `2:value`
+/// otherwise. The index is already parsed.
+pub(crate) struct IndexVariantAccess<'a, R: 'a> {
+    des: &'a mut Deserializer<R>,
+    index: u64,
+    unit: bool,
+}
+
+impl<'a, R: 'a> IndexVariantAccess<'a, R> {
+    pub(crate) fn new(des: &'a mut Deserializer<R>, index: u64, unit: bool) -> Self {
+        IndexVariantAccess { des, index, unit }
+    }
+}
+
+impl<'de, 'a, R: Read<'de> + 'a> de::EnumAccess<'de> for IndexVariantAccess<'a, R> {
+    type Error = Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self)>
+    where
+        V: de::DeserializeSeed<'de>,
+    {
+        let index: U64Deserializer<Error> = self.index.into_deserializer();
+        let val = seed.deserialize(index)?;
+        if !self.unit {
+            self.des.parse_object_colon()?;
+        }
+        Ok((val, self))
+    }
+}
+
+impl<'de, 'a, R: Read<'de> + 'a> de::VariantAccess<'de> for IndexVariantAccess<'a, R> {
+    type Error = Error;
+
+    fn unit_variant(self) -> Result<()> {
+        if self.unit {
+            Ok(())
+        } else {
+            de::Deserialize::deserialize(self.des)
+        }
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value>
+    where
+        T: de::DeserializeSeed<'de>,
+    {
+        if self.unit {
+            return Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"newtype variant",
+            ));
+        }
+        seed.deserialize(self.des)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        if self.unit {
+            return Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"tuple variant",
+            ));
+        }
+        de::Deserializer::deserialize_seq(self.des, visitor)
+    }
+
+    fn struct_variant<V>(self, fields: &'static [&'static str], visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        if self.unit {
+            return Err(de::Error::invalid_type(
+                Unexpected::UnitVariant,
+                &"struct variant",
+            ));
+        }
+        de::Deserializer::deserialize_struct(self.des, "", fields, visitor)
+    }
+}
@@ -6,11 +6,19 @@ use serde::de;
 
 pub(crate) struct InitMapAccess<'a, R: 'a> {
     pub(super) des: &'a mut Deserializer<R>,
+    /// Keys are struct field names, which may be written by position.
+    fields: bool,
 }
 
 impl<'a, R: 'a> InitMapAccess<'a, R> {
     pub(crate) fn new(des: &'a mut Deserializer<R>) -> Self {
-        InitMapAccess { des }
+        InitMapAccess { des, fields: false }
+    }
+
+    /// Access to the fields of a struct.
+    pub(crate) fn fields(des: &'a mut Deserializer<R>) -> Self {
+        let fields = des.field_index;
+        InitMapAccess { des, fields }
     }
 }
 
@@ -36,7 +44,10 @@ impl<'de, 'a, R: Read<'de> + 'a> de::MapAccess<'de> for InitMapAccess<'a, R> {
             } */
             Some(b'}') => Err(self.des.peek_error(ErrorCode::TrailingComma)),
             Some(_) => seed
-                .deserialize(MapKeyDeserializer { des: self.des })
+                .deserialize(MapKeyDeserializer {
+                    des: self.des,
+                    fields: self.fields,
+                })
                 .map(Some),
             None => Ok(None),
         }
@@ -1,17 +1,18 @@
+mod index_variant;
 mod init_map;
 mod init_seq;
 mod map;
 mod saved;
 mod seq;
-// mod unit_variant;
+mod unit_variant;
 mod variant;
 
 pub(crate) use self::{
+    index_variant::IndexVariantAccess,
     init_map::InitMapAccess,
     init_seq::InitSeqAccess,
     map::MapAccess,
     saved::{SavedInitMapAccess, SavedInitSeqAccess, SavedMapAccess, SavedSeqAccess},
     seq::SeqAccess,
 };
-// pub(crate) self::use unit_variant::UnitVariantAccess;
-pub(crate) use self::variant::VariantAccess;
+pub(crate) use self::{unit_variant::UnitVariantAccess, variant::VariantAccess};
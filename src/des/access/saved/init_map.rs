@@ -12,7 +12,7 @@ pub(crate) struct SavedInitMapAccess<'a, R: 'a> {
 impl<'a, R: 'a> SavedInitMapAccess<'a, R> {
     pub(crate) fn new(des: &'a mut Deserializer<R>) -> Self {
         SavedInitMapAccess {
-            des: InitMapAccess { des },
+            des: InitMapAccess::new(des),
         }
     }
 }
@@ -12,7 +12,7 @@ pub(crate) struct SavedMapAccess<'a, R: 'a> {
 impl<'a, R: 'a> SavedMapAccess<'a, R> {
     pub(crate) fn new(des: &'a mut Deserializer<R>) -> Self {
         SavedMapAccess {
-            des: MapAccess { des },
+            des: MapAccess::new(des),
         }
     }
 }
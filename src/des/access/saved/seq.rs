@@ -27,11 +27,7 @@ impl<'de, 'a, R: Read<'de> + 'a> de::SeqAccess<'de> for SavedSeqAccess<'a, R> {
         T: de::DeserializeSeed<'de>,
     {
         match self.des.parse_whitespace()? {
-            Some(b']') => {
-                if self.saved_type == SavedType::None {
-                    return Ok(None);
-                }
-            }
+            Some(b']') if self.saved_type == SavedType::None => return Ok(None),
             None => return Err(self.des.peek_error(ErrorCode::EofWhileParsingList)),
             _ => {}
         }
@@ -1,14 +1,22 @@
 #[cfg(feature = "std")]
 use super::read::IoRead;
 use super::{
-    access::{MapAccess, SavedMapAccess, SavedSeqAccess, SeqAccess, VariantAccess},
+    access::{
+        IndexVariantAccess, MapAccess, SavedMapAccess, SavedSeqAccess, SeqAccess,
+        UnitVariantAccess, VariantAccess,
+    },
     parser_number::ParserNumber,
     read::{Read, Reference, SliceRead, StrRead},
     stream_deserializer::StreamDeserializer,
 };
-use crate::error::{Error, ErrorCode, Result};
 #[cfg(feature = "float_roundtrip")]
 use crate::lexical;
+use crate::{
+    bytes::BytesMode,
+    datetime::{DateTime, DateTimeAccess},
+    error::{Error, ErrorCode, Result},
+};
+#[cfg(feature = "arbitrary_precision")]
 use alloc::string::String;
 #[cfg(feature = "float_roundtrip")]
 use core::iter;
@@ -114,6 +122,8 @@ static POW10: [f64; 309] = [
 pub struct Deserializer<R> {
     pub(crate) read: R,
     pub(crate) remaining_depth: u8,
+    variant_index: bool,
+    pub(crate) field_index: bool,
     #[cfg(feature = "float_roundtrip")]
     single_precision: bool,
     #[cfg(feature = "unbounded_depth")]
@@ -136,6 +146,8 @@ where
         Deserializer {
             read,
             remaining_depth: 128,
+            variant_index: false,
+            field_index: false,
             #[cfg(feature = "float_roundtrip")]
             single_precision: false,
             #[cfg(feature = "unbounded_depth")]
@@ -183,6 +195,7 @@ pub(crate) enum PreParser {
 pub(crate) enum SavedType {
     Str,
     Bytes,
+    DateTime,
     Number,
     FloatNumber,
     // ExponentNumber,
@@ -267,6 +280,58 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         self.disable_recursion_limit = true;
     }
 
+    /// Reads enum variants by their index instead of their name, as written
+    /// by [`Options::variants_by_index`].
+    ///
+    /// [`Options::variants_by_index`]: crate::Options::variants_by_index
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Debug, PartialEq)]
+    /// enum Shape {
+    ///     Dot,
+    ///     Circle(u64),
+    /// }
+    ///
+    /// let mut deserializer = serde_encom::Deserializer::from_str("1:5");
+    /// deserializer.variants_by_index(true);
+    ///
+    /// let shape = Shape::deserialize(&mut deserializer).unwrap();
+    /// assert_eq!(shape, Shape::Circle(5));
+    /// ```
+    pub fn variants_by_index(&mut self, yes: bool) {
+        self.variant_index = yes;
+    }
+
+    /// Reads struct fields by their position instead of their name, as
+    /// written by [`Options::fields_by_index`].
+    ///
+    /// [`Options::fields_by_index`]: crate::Options::fields_by_index
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde::Deserialize;
+    ///
+    /// #[derive(Deserialize, Debug, PartialEq)]
+    /// struct Point {
+    ///     x: i64,
+    ///     y: i64,
+    /// }
+    ///
+    /// let mut deserializer = serde_encom::Deserializer::from_str("{0:3 1:-4}");
+    /// deserializer.fields_by_index(true);
+    ///
+    /// let point = Point::deserialize(&mut deserializer).unwrap();
+    /// assert_eq!(point, Point { x: 3, y: -4 });
+    /// ```
+    pub fn fields_by_index(&mut self, yes: bool) {
+        self.field_index = yes;
+    }
+
     #[inline]
     pub(crate) fn peek(&mut self) -> Result<Option<u8>> {
         self.read.peek()
@@ -308,46 +373,40 @@ impl<'de, R: Read<'de>> Deserializer<R> {
 
     /// Returns the first non-whitespace byte without consuming it, or `None` if
     /// EOF is encountered.
+    #[inline]
     pub(crate) fn parse_whitespace(&mut self) -> Result<Option<u8>> {
-        loop {
-            match self.peek()? {
-                /* Some(b' ' | b'\n' | b'\t' | b'\r') */
-                Some(ch) if ch < 0x21 => {
-                    self.eat_char();
-                }
-                other => {
-                    return Ok(other);
-                }
-            }
-        }
+        self.read.skip_whitespace()
     }
 
+    /// What the value is, by the delimiter after its first token. A number
+    /// or a boolean is only known at its end, and is a boolean if it has a
+    /// `t` or an `f`.
     #[inline]
     fn pre_parser_match(&mut self) -> Result<PreParser> {
-        let mut typ = SavedType::Number;
-        loop {
-            match self.peek()? {
-                Some(b':' | b'{' | b'[') => {
-                    return Ok(PreParser::SavedMap);
-                }
-                Some(b'=') => return Ok(PreParser::SavedSeq(SavedType::Str)),
-                Some(b'~') => return Ok(PreParser::SavedSeq(SavedType::Bytes)),
-                Some(b'.') => return Ok(PreParser::SavedSeq(SavedType::FloatNumber)),
-                /* Some(b'e' | b'E') => { // todo????
-                    return Ok(PreParser::SavedSeq(SavedState::ExponentNumber))
-                } */
-                Some(b't' | b'f') => {
-                    typ = SavedType::Boolean;
-                    self.eat_char();
-                }
-                Some(b'}' | b']' | b' ' | b'\n' | b'\t' | b'\r') | None => {
-                    return Ok(PreParser::SavedSeq(typ));
-                }
-                Some(_) => {
-                    self.eat_char();
+        let typ = match self.read.skip_to_delimiter()? {
+            Some(b':' | b'{' | b'[') => return Ok(PreParser::SavedMap),
+            Some(b'=') => SavedType::Str,
+            Some(b'~' | b'#' | b'$') => SavedType::Bytes,
+            Some(b'@') => SavedType::DateTime,
+            Some(b'.') => SavedType::FloatNumber,
+            /* Some(b'e' | b'E') => { // todo????
+                return Ok(PreParser::SavedSeq(SavedState::ExponentNumber))
+            } */
+            _ => {
+                self.read.save_end();
+                if self
+                    .read
+                    .get_saved()
+                    .iter()
+                    .any(|&ch| ch == b't' || ch == b'f')
+                {
+                    SavedType::Boolean
+                } else {
+                    SavedType::Number
                 }
             }
-        }
+        };
+        Ok(PreParser::SavedSeq(typ))
     }
 
     /// `deserialize_any()` what value is after b'{' or init
@@ -366,6 +425,25 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         ret
     }
 
+    /// Visits an enum variant written as `Variant:value`, `Variant[..]`,
+    /// `Variant{..}` or, for a unit variant, as a string. With
+    /// `variants_by_index` the variant can be written as its index instead.
+    pub(crate) fn visit_variant<V>(&mut self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        let peek = self.parse_whitespace()?;
+        if self.read.is_str_ahead()? {
+            return visitor.visit_enum(UnitVariantAccess::new(self));
+        }
+        if self.variant_index && matches!(peek, Some(b'0'..=b'9')) {
+            let index = self.read.parse_int_any_pos()?;
+            let unit = !matches!(self.peek()?, Some(b':' | b'{' | b'['));
+            return visitor.visit_enum(IndexVariantAccess::new(self, index, unit));
+        }
+        visitor.visit_enum(VariantAccess::new(self))
+    }
+
     /// checks end of string or bytes
     #[inline]
     pub(crate) fn end_of_str_or_bytes(&mut self) -> Result<()> {
@@ -380,32 +458,126 @@ impl<'de, R: Read<'de>> Deserializer<R> {
     where
         V: de::Visitor<'de>,
     {
-        self.eat_char();
+        match self.peek()? {
+            Some(b'=' | b'~') => self.eat_char(),
+            Some(b'@') => return self.visit_datetime_by_len(visitor, len),
+            Some(b'#' | b'$') => {
+                return Err(de::Error::invalid_type(
+                    Unexpected::Other("hex or base64 bytes"),
+                    &visitor,
+                ))
+            }
+            // Not a length prefix but a number.
+            _ => {
+                return Err(de::Error::invalid_type(
+                    Unexpected::Unsigned(len as u64),
+                    &visitor,
+                ))
+            }
+        }
         let res = visitor.visit_borrowed_str(self.read.read_str(len)?);
         self.end_of_str_or_bytes()?;
         res
     }
 
+    /// Reads bytes of any mode, or a string as bytes.
     #[inline]
     pub(crate) fn deserialize_bytes_by_len<V>(&mut self, visitor: V, len: usize) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        let mode = match self.peek()? {
+            Some(b'=') => Some(BytesMode::Raw),
+            Some(b'@') => return self.visit_datetime_by_len(visitor, len),
+            Some(marker) => BytesMode::from_marker(marker),
+            None => None,
+        };
+        // Not a length prefix but a number.
+        let Some(mode) = mode else {
+            return Err(de::Error::invalid_type(
+                Unexpected::Unsigned(len as u64),
+                &visitor,
+            ));
+        };
+        self.eat_char();
+        let bytes = self.read.read_slice(len)?;
+        let res = match mode {
+            BytesMode::Raw => visitor.visit_borrowed_bytes(bytes),
+            #[cfg(feature = "alloc")]
+            mode => match mode.decode(bytes) {
+                Some(bytes) => visitor.visit_byte_buf(bytes),
+                None => return Err(self.error(ErrorCode::InvalidEncodedBytes)),
+            },
+            #[cfg(not(feature = "alloc"))]
+            _ => return Err(self.error(ErrorCode::EncodedBytesWithoutAlloc)),
+        };
+        self.end_of_str_or_bytes()?;
+        res
+    }
+
+    /// Reads a date-time after its length prefix and visits it as the map of
+    /// a single `datetime::TOKEN` entry, which `DateTime` and `Value` know.
+    pub(crate) fn visit_datetime_by_len<V>(&mut self, visitor: V, len: usize) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
         self.eat_char();
-        let res = visitor.visit_borrowed_bytes(self.read.read_slice(len)?);
+        let text = self.read.read_str(len)?;
+        if text.parse::<DateTime>().is_err() {
+            return Err(self.error(ErrorCode::InvalidDateTime));
+        }
+        let res = visitor.visit_map(DateTimeAccess::new(text));
         self.end_of_str_or_bytes()?;
         res
     }
 
+    /// Deserializes a string or bytes, failing at the length prefix if it is
+    /// bigger than `capacity`, before the value is read.
+    #[cfg(feature = "heapless")]
+    fn deserialize_with_capacity<V>(&mut self, visitor: V, capacity: usize) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        let value = match self.parse_integer(true)? {
+            ParserNumber::U64(len) => match self.peek()? {
+                Some(marker @ (b'~' | b'#' | b'$')) => {
+                    let mode = BytesMode::from_marker(marker).unwrap_or(BytesMode::Raw);
+                    match mode.decoded_len(len as usize) {
+                        Some(decoded) if decoded > capacity => {
+                            Err(self.peek_error(ErrorCode::CapacityExceeded))
+                        }
+                        Some(_) => self.deserialize_bytes_by_len(visitor, len as usize),
+                        None => Err(self.peek_error(ErrorCode::InvalidEncodedBytes)),
+                    }
+                }
+                _ if len > capacity as u64 => Err(self.peek_error(ErrorCode::CapacityExceeded)),
+                _ => self.deserialize_str_by_len(visitor, len as usize),
+            },
+            _ => Err(self.peek_invalid_type(&visitor)),
+        };
+
+        match value {
+            Ok(value) => Ok(value),
+            Err(err) => Err(self.fix_position(err)),
+        }
+    }
+
     /// what value is in main deserializer `deserialize_any()`
     pub(crate) fn any_map_value<V>(&mut self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        let parsed_int = self.read.parse_int_any_pos()?;
+        let parsed_int = match self.read.parse_int_any_pos() {
+            Ok(int) => int,
+            Err(err) if err.is_number_out_of_range() => {
+                return self.parse_integer128(true)?.visit(visitor);
+            }
+            Err(err) => return Err(err),
+        };
         let ret = match self.peek()? {
             Some(b'=') => self.deserialize_str_by_len(visitor, parsed_int as usize),
-            Some(b'~') => self.deserialize_bytes_by_len(visitor, parsed_int as usize),
+            Some(b'~' | b'#' | b'$') => self.deserialize_bytes_by_len(visitor, parsed_int as usize),
+            Some(b'@') => self.visit_datetime_by_len(visitor, parsed_int as usize),
             Some(b'.') => visitor.visit_f64(self.parse_decimal(true, parsed_int, 0)?),
             /* Some(b'e' | b'E') => {
                 ParserNumber::F64(self.parse_exponent(true, integer, 0)?).visit(visitor)
@@ -507,29 +679,27 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         val
     }
 
-    // TODO: rewrite
     pub(crate) fn do_deserialize_i128<'any, V>(&mut self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'any>,
     {
-        let mut buf = String::new();
-
-        match self.parse_whitespace()? {
+        let positive = match self.parse_whitespace()? {
             Some(b'-') => {
                 self.eat_char();
-                buf.push('-');
+                false
             }
-            Some(_) => {}
+            Some(_) => true,
             None => {
                 return Err(self.peek_error(ErrorCode::EofWhileParsingValue));
             }
         };
 
-        self.scan_integer128(&mut buf)?;
-
-        let value = match buf.parse() {
-            Ok(int) => visitor.visit_i128(int),
-            Err(_) => {
+        let value = match self.scan_integer128()? {
+            Some(int) if positive && int <= i128::MAX as u128 => visitor.visit_i128(int as i128),
+            Some(int) if !positive && int <= i128::MIN.unsigned_abs() => {
+                visitor.visit_i128(0u128.wrapping_sub(int) as i128)
+            }
+            _ => {
                 return Err(self.error(ErrorCode::NumberOutOfRange));
             }
         };
@@ -540,7 +710,6 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    // TODO: rewrite
     pub(crate) fn do_deserialize_u128<'any, V>(&mut self, visitor: V) -> Result<V::Value>
     where
         V: de::Visitor<'any>,
@@ -555,12 +724,9 @@ impl<'de, R: Read<'de>> Deserializer<R> {
             }
         }
 
-        let mut buf = String::new();
-        self.scan_integer128(&mut buf)?;
-
-        let value = match buf.parse() {
-            Ok(int) => visitor.visit_u128(int),
-            Err(_) => {
+        let value = match self.scan_integer128()? {
+            Some(int) => visitor.visit_u128(int),
+            None => {
                 return Err(self.error(ErrorCode::NumberOutOfRange));
             }
         };
@@ -571,29 +737,51 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    // TODO: remove?
-    fn scan_integer128(&mut self, buf: &mut String) -> Result<()> {
+    /// Reads the digits of an integer, or `None` if it doesn't fit in `u128`.
+    /// All of the digits are consumed either way.
+    fn scan_integer128(&mut self) -> Result<Option<u128>> {
         match self.next_char_or_null()? {
             b'0' => {
-                buf.push('0');
                 // There can be only one leading '0'.
                 match self.peek_or_null()? {
                     b'0'..=b'9' => Err(self.peek_error(ErrorCode::InvalidNumber)),
-                    _ => Ok(()),
+                    _ => Ok(Some(0)),
                 }
             }
             c @ b'1'..=b'9' => {
-                buf.push(c as char);
+                let mut int = Some((c - b'0') as u128);
                 while let c @ b'0'..=b'9' = self.peek_or_null()? {
                     self.eat_char();
-                    buf.push(c as char);
+                    int = int
+                        .and_then(|int| int.checked_mul(10))
+                        .and_then(|int| int.checked_add((c - b'0') as u128));
                 }
-                Ok(())
+                Ok(int)
             }
             _ => Err(self.error(ErrorCode::InvalidNumber)),
         }
     }
 
+    /// Reads an integer that doesn't fit in 64 bits, for `deserialize_any`,
+    /// so that it keeps its exact value instead of failing or becoming a
+    /// float.
+    #[cold]
+    #[inline(never)]
+    fn parse_integer128(&mut self, positive: bool) -> Result<ParserNumber> {
+        let int = self.scan_integer128()?;
+        match self.peek()? {
+            Some(b'}' | b']' | b' ' | b'\n' | b'\t' | b'\r') | None => {}
+            Some(_) => return Err(self.error(ErrorCode::NumberOutOfRange)),
+        }
+        match int {
+            Some(int) if positive => Ok(ParserNumber::U128(int)),
+            Some(int) if int <= i128::MIN.unsigned_abs() => {
+                Ok(ParserNumber::I128(0u128.wrapping_sub(int) as i128))
+            }
+            _ => Err(self.error(ErrorCode::NumberOutOfRange)),
+        }
+    }
+
     #[cold]
     pub(crate) fn fix_position(&self, err: Error) -> Error {
         err.fix_position(move |code| self.error(code))
@@ -1115,7 +1303,19 @@ impl<'de, R: Read<'de>> Deserializer<R> {
     #[cfg(not(feature = "arbitrary_precision"))]
     #[inline]
     pub(crate) fn parse_any_number(&mut self, positive: bool) -> Result<ParserNumber> {
-        self.parse_integer(positive)
+        let significand = match self.read.parse_int_any_pos() {
+            Ok(significand) => significand,
+            Err(err) if err.is_number_out_of_range() => return self.parse_integer128(positive),
+            Err(err) => return Err(err),
+        };
+        // Below `i64::MIN`, an integer is kept exactly rather than as a float.
+        if !positive
+            && significand > i64::MIN.unsigned_abs()
+            && !matches!(self.peek_or_null()?, b'.' | b'e' | b'E')
+        {
+            return Ok(ParserNumber::I128(-(significand as i128)));
+        }
+        self.parse_number(positive, significand)
     }
 
     #[cfg(feature = "arbitrary_precision")]
@@ -1298,188 +1498,84 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         }
     }
 
-    fn ignore_value(&mut self) -> Result<()> {
+    /// Skips a value, looking at no more of it than its structure needs:
+    /// strings, bytes and date-times are jumped over by their length prefix,
+    /// and other tokens up to their delimiter.
+    pub(crate) fn ignore_value(&mut self) -> Result<()> {
         self.read.clear_saved();
-        let mut enclosing = None;
 
         loop {
-            let peek = match self.parse_whitespace()? {
-                Some(b) => b,
-                None => {
-                    return Err(self.peek_error(ErrorCode::EofWhileParsingValue));
-                }
-            };
-
-            let frame = match peek {
-                b'n' => {
-                    self.eat_char();
-                    // self.parse_ident(b"ull")?;
-                    None
-                }
-                b't' => {
-                    self.eat_char();
-                    // self.parse_ident(b"rue")?;
-                    None
-                }
-                b'f' => {
-                    self.eat_char();
-                    // self.parse_ident(b"alse")?;
-                    None
-                }
-                b'-' => {
-                    self.eat_char();
-                    self.ignore_integer()?;
-                    None
-                }
-                b'0'..=b'9' => {
-                    self.ignore_integer()?;
-                    None
-                }
-                /* b'"' => {
-                    self.eat_char();
-                    self.read.ignore_str()?;
-                    None
-                } */
-                frame @ b'{' | frame @ b'[' => {
-                    /* if let Some(v) = enclosing.take() {
-                        self.scratch.push(v);
-                    } */
-                    self.eat_char();
-                    Some(frame)
-                }
-                _ => return Err(self.peek_error(ErrorCode::ExpectedSomeValue)),
-            };
-
-            let (mut accept_comma, frame) = match frame {
-                Some(frame) => (false, frame),
-                None => match enclosing.take() {
-                    Some(frame) => (true, frame),
-                    // None => match self.scratch.pop() {
-                    // Some(frame) => (true, frame),
-                    None => return Ok(()),
-                    // },
-                },
-            };
-
-            loop {
-                match self.parse_whitespace()? {
-                    Some(b' ') if accept_comma => {
+            match self.parse_whitespace()? {
+                Some(close @ (b'[' | b'{')) => {
+                    check_recursion! {
                         self.eat_char();
-                        break;
-                    }
-                    Some(b']') if frame == b'[' => {}
-                    Some(b'}') if frame == b'{' => {}
-                    Some(_) => {
-                        if accept_comma {
-                            return Err(self.peek_error(match frame {
-                                b'[' => ErrorCode::ExpectedListCommaOrEnd,
-                                b'{' => ErrorCode::ExpectedObjectCommaOrEnd,
-                                _ => unreachable!(),
-                            }));
-                        } else {
-                            break;
-                        }
-                    }
-                    None => {
-                        return Err(self.peek_error(match frame {
-                            b'[' => ErrorCode::EofWhileParsingList,
-                            b'{' => ErrorCode::EofWhileParsingObject,
-                            _ => unreachable!(),
-                        }));
+                        self.ignore_elements(if close == b'[' { b']' } else { b'}' })?;
                     }
+                    return Ok(());
                 }
-
-                self.eat_char();
-                /* frame = match self.scratch.pop() {
-                    Some(frame) => frame,
-                    None => return Ok(()),
-                }; */
-                accept_comma = true;
-            }
-
-            if frame == b'{' || frame == b'[' {
-                match self.parse_whitespace()? {
-                    Some(b'"') => self.eat_char(),
-                    Some(_) => return Err(self.peek_error(ErrorCode::KeyMustBeAString)),
-                    None => return Err(self.peek_error(ErrorCode::EofWhileParsingObject)),
-                }
-                self.read.ignore_str()?;
-                match self.parse_whitespace()? {
-                    Some(b':') => self.eat_char(),
-                    Some(_) => return Err(self.peek_error(ErrorCode::ExpectedColon)),
-                    None => return Err(self.peek_error(ErrorCode::EofWhileParsingObject)),
+                Some(b']' | b'}') => return Err(self.peek_error(ErrorCode::ExpectedSomeValue)),
+                Some(_) => {
+                    if !self.ignore_token()? {
+                        return Ok(());
+                    }
                 }
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsingValue)),
             }
-
-            enclosing = Some(frame);
         }
     }
 
-    fn ignore_integer(&mut self) -> Result<()> {
-        match self.next_char_or_null()? {
-            b'0' => {
-                // There can be only one leading '0'.
-                if let b'0'..=b'9' = self.peek_or_null()? {
-                    return Err(self.peek_error(ErrorCode::InvalidNumber));
-                }
-            }
-            b'1'..=b'9' => {
-                while let b'0'..=b'9' = self.peek_or_null()? {
+    /// Skips the elements of an array or the entries of a map, and `close`
+    /// after them.
+    fn ignore_elements(&mut self, close: u8) -> Result<()> {
+        loop {
+            match self.parse_whitespace()? {
+                Some(ch) if ch == close => {
                     self.eat_char();
+                    return Ok(());
                 }
+                Some(_) => self.ignore_value()?,
+                None if close == b']' => {
+                    return Err(self.peek_error(ErrorCode::EofWhileParsingList))
+                }
+                None => return Err(self.peek_error(ErrorCode::EofWhileParsingObject)),
             }
-            _ => {
-                return Err(self.error(ErrorCode::InvalidNumber));
-            }
-        }
-
-        match self.peek_or_null()? {
-            b'.' => self.ignore_decimal(),
-            b'e' | b'E' => self.ignore_exponent(),
-            _ => Ok(()),
-        }
-    }
-
-    fn ignore_decimal(&mut self) -> Result<()> {
-        self.eat_char();
-
-        let mut at_least_one_digit = false;
-        while let b'0'..=b'9' = self.peek_or_null()? {
-            self.eat_char();
-            at_least_one_digit = true;
-        }
-
-        if !at_least_one_digit {
-            return Err(self.peek_error(ErrorCode::InvalidNumber));
-        }
-
-        match self.peek_or_null()? {
-            b'e' | b'E' => self.ignore_exponent(),
-            _ => Ok(()),
         }
     }
 
-    fn ignore_exponent(&mut self) -> Result<()> {
-        self.eat_char();
-
-        match self.peek_or_null()? {
-            b'+' | b'-' => self.eat_char(),
-            _ => {}
-        }
-
-        // Make sure a digit follows the exponent place.
-        match self.next_char_or_null()? {
-            b'0'..=b'9' => {}
-            _ => {
-                return Err(self.error(ErrorCode::InvalidNumber));
+    /// Skips a number, a boolean, null, a length prefixed value or a map key,
+    /// and returns whether a value follows, as it does a map key.
+    fn ignore_token(&mut self) -> Result<bool> {
+        if let Some(b'0'..=b'9') = self.peek()? {
+            match self.read.parse_int_any_pos() {
+                Ok(len) => {
+                    if let Some(b'=' | b'~' | b'#' | b'$' | b'@') = self.peek()? {
+                        self.eat_char();
+                        self.read.skip_bytes(len as usize)?;
+                        self.end_of_str_or_bytes()?;
+                        return Ok(false);
+                    }
+                }
+                // An integer too long for a length, skipped like any other.
+                Err(err) if err.is_number_out_of_range() => {}
+                Err(err) => return Err(err),
             }
         }
 
-        while let b'0'..=b'9' = self.peek_or_null()? {
-            self.eat_char();
+        loop {
+            match self.read.skip_to_delimiter()? {
+                Some(b':') => {
+                    self.eat_char();
+                    return Ok(true);
+                }
+                Some(b'[' | b'{') => return Ok(true),
+                // The point of a float, or one in a map key.
+                Some(b'.') => self.eat_char(),
+                Some(b'=' | b'~' | b'#' | b'$' | b'@') => {
+                    return Err(self.peek_error(ErrorCode::ExpectedSomeValue))
+                }
+                _ => return Ok(false),
+            }
         }
-
-        Ok(())
     }
 
     #[cfg(feature = "raw_value")]
@@ -1492,6 +1588,37 @@ impl<'de, R: Read<'de>> Deserializer<R> {
         self.ignore_value()?;
         self.read.end_raw_buffering(visitor)
     }
+
+    /// Visits the text of a number as it is written, or a string, for a type
+    /// that parses its own digits, like a decimal.
+    #[cfg(feature = "rust_decimal")]
+    fn deserialize_number_text<V>(&mut self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        match self.parse_whitespace()? {
+            Some(b'0'..=b'9') if self.read.is_str_ahead()? => {
+                return de::Deserializer::deserialize_str(self, visitor);
+            }
+            Some(b'-' | b'0'..=b'9') => {}
+            Some(_) => return Err(self.peek_invalid_type(&visitor)),
+            None => return Err(self.peek_error(ErrorCode::EofWhileParsingValue)),
+        }
+
+        let mut buf = [0; 64];
+        let mut len = 0;
+        while let Some(ch @ (b'-' | b'+' | b'.' | b'e' | b'E' | b'0'..=b'9')) = self.peek()? {
+            *buf.get_mut(len)
+                .ok_or_else(|| self.peek_error(ErrorCode::NumberOutOfRange))? = ch;
+            len += 1;
+            self.eat_char();
+        }
+        // Only ASCII bytes were taken.
+        let text = unsafe { core::str::from_utf8_unchecked(&buf[..len]) };
+        visitor
+            .visit_str(text)
+            .map_err(|err| self.fix_position(err))
+    }
 }
 
 impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
@@ -1845,7 +1972,16 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
             }
         }
 
-        let _ = name;
+        // Reads a date-time, or a string that the visitor parses.
+        if name == crate::datetime::TOKEN {
+            return self.deserialize_str(visitor);
+        }
+
+        #[cfg(feature = "rust_decimal")]
+        if name == crate::decimal::TOKEN {
+            return self.deserialize_number_text(visitor);
+        }
+
         visitor.visit_newtype_struct(self)
     }
 
@@ -1892,13 +2028,21 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
     #[inline]
     fn deserialize_tuple_struct<V>(
         self,
-        _name: &'static str,
-        _len: usize,
+        name: &'static str,
+        len: usize,
         visitor: V,
     ) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
+        #[cfg(feature = "heapless")]
+        {
+            if name == crate::heapless::TOKEN {
+                return self.deserialize_with_capacity(visitor, len);
+            }
+        }
+
+        let _ = (name, len);
         self.deserialize_seq(visitor)
     }
 
@@ -1965,7 +2109,7 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
             b'{' => {
                 check_recursion! {
                     self.eat_char();
-                    let ret = visitor.visit_map(MapAccess::new(self));
+                    let ret = visitor.visit_map(MapAccess::fields(self));
                 }
 
                 match (ret, self.end_map()) {
@@ -1998,7 +2142,7 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
             Some(b'{') => {
                 check_recursion! {
                     self.eat_char();
-                    let value = visitor.visit_enum(VariantAccess::new(self))?;
+                    let value = self.visit_variant(visitor)?;
                 }
 
                 match self.parse_whitespace()? {
@@ -2010,7 +2154,7 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
                     None => Err(self.error(ErrorCode::EofWhileParsingObject)),
                 }
             }
-            Some(_) => visitor.visit_enum(VariantAccess::new(self)), // it skips extra {} of enum
+            Some(_) => self.visit_variant(visitor), // it skips extra {} of enum
             // Some(b'"') => visitor.visit_enum(UnitVariantAccess::new(self)),
             // Some(_) => Err(self.peek_error(ErrorCode::ExpectedSomeValue)),
             None => Err(self.peek_error(ErrorCode::EofWhileParsingValue)),
@@ -2026,7 +2170,12 @@ impl<'de, R: Read<'de>> de::Deserializer<'de> for &mut Deserializer<R> {
             return Err(self.peek_error(ErrorCode::EofWhileParsingValue));
         };
 
-        let value = {
+        let value = if self.read.is_str_ahead()? {
+            // A variant name in value position, like the tag of an internally
+            // tagged enum, is written as a string.
+            let len = self.read.parse_int_any_pos()?;
+            self.deserialize_str_by_len(visitor, len as usize)
+        } else {
             // self.read.clear_saved();
             match self.read.parse_str()? {
                 Reference::Borrowed(s) => visitor.visit_borrowed_str(s),
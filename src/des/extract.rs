@@ -0,0 +1,159 @@
+use super::{deserializer::Deserializer, from_slice, read::SliceRead};
+use crate::{
+    error::{ErrorCode, Result},
+    value::parse_index,
+};
+use serde::de;
+
+/// Deserialize the value addressed by an EnCom Pointer without parsing the
+/// rest of the document.
+///
+/// Only the maps and arrays on the path are walked. Their other entries are
+/// skipped, jumping over strings and bytes by their length prefix, and only
+/// the addressed value is deserialized, borrowing from `input` where `T`
+/// allows it.
+///
+/// The pointer syntax is the same as for [`Value::pointer`]. Returns `None`
+/// if nothing is found at `pointer`.
+///
+/// [`Value::pointer`]: crate::Value::pointer
+///
+/// # Example
+///
+/// ```
+/// let data = b"header{route:6=orders id:42} body:11=hello world";
+///
+/// let route: Option<&str> = serde_encom::extract(data, "/header/route").unwrap();
+/// assert_eq!(route, Some("orders"));
+///
+/// let id: Option<u64> = serde_encom::extract(data, "/header/id").unwrap();
+/// assert_eq!(id, Some(42));
+///
+/// let missing: Option<u64> = serde_encom::extract(data, "/header/user").unwrap();
+/// assert_eq!(missing, None);
+/// ```
+///
+/// # Errors
+///
+/// Fails if the input is not valid EnCom on the walked path, or if the
+/// addressed value can't be deserialized as `T`.
+pub fn extract<'a, T>(input: &'a [u8], pointer: &str) -> Result<Option<T>>
+where
+    T: de::Deserialize<'a>,
+{
+    if pointer.is_empty() {
+        return from_slice(input).map(Some);
+    }
+    if !pointer.starts_with('/') {
+        return Ok(None);
+    }
+
+    let mut de = Deserializer::new(SliceRead::new(input));
+    if !de.seek_pointer(pointer, true)? {
+        return Ok(None);
+    }
+
+    let value = de::Deserialize::deserialize(&mut de)?;
+    match de.peek()? {
+        Some(b'}' | b']') | None => Ok(Some(value)),
+        Some(ch) if ch < 0x21 => Ok(Some(value)),
+        Some(_) => Err(de.peek_error(ErrorCode::TrailingCharacters)),
+    }
+}
+
+impl<'a> Deserializer<SliceRead<'a>> {
+    /// Moves to the value addressed by `pointer`, starting either at the top
+    /// level of the document or at a value.
+    pub(crate) fn seek_pointer(&mut self, pointer: &str, mut top: bool) -> Result<bool> {
+        for token in pointer.split('/').skip(1) {
+            let token = token.replace("~1", "/").replace("~0", "~");
+            let found = if top {
+                self.seek_entry(&token, None)?
+            } else {
+                match self.parse_whitespace()? {
+                    Some(b'{') => {
+                        self.eat_char();
+                        self.seek_entry(&token, None)?
+                    }
+                    Some(b'[') => {
+                        self.eat_char();
+                        self.seek_entry(&token, Some(false))?
+                    }
+                    // A single entry like an enum variant `Variant:value`.
+                    Some(_) => self.scan_key(false) == Some(token.as_bytes()),
+                    None => false,
+                }
+            };
+            if !found {
+                return Ok(false);
+            }
+            top = false;
+        }
+        Ok(true)
+    }
+
+    /// Moves to the value of the entry addressed by `token`, inside a map or
+    /// an array whose opening bracket is already consumed.
+    ///
+    /// Whether it's a map is decided by the first entry, as in
+    /// `any_after_x7b()`, unless `is_map` is given.
+    pub(crate) fn seek_entry(&mut self, token: &str, mut is_map: Option<bool>) -> Result<bool> {
+        let index = parse_index(token);
+        let mut i = 0;
+        loop {
+            match self.parse_whitespace()? {
+                None | Some(b'}' | b']') => return Ok(false),
+                Some(_) => {}
+            }
+
+            if is_map == Some(false) {
+                if index == Some(i) {
+                    return Ok(true);
+                }
+                self.ignore_value()?;
+                i += 1;
+                continue;
+            }
+
+            let key = self.scan_key(is_map.is_some());
+            if is_map.is_none() {
+                is_map = Some(key.is_some());
+                if key.is_none() {
+                    continue;
+                }
+            }
+            if key == Some(token.as_bytes()) {
+                return Ok(true);
+            }
+            self.ignore_value()?;
+        }
+    }
+
+    /// Reads a map key and the `:` after it, if there is one at the current
+    /// position. Otherwise the position stays the same.
+    ///
+    /// Inside of a map a key extends up to `:`, `{` or `[`, like in
+    /// `Read::parse_str()`. Otherwise a value delimiter ends the lookup.
+    pub(crate) fn scan_key(&mut self, in_map: bool) -> Option<&'a [u8]> {
+        let slice = self.read.slice;
+        let start = self.read.index;
+        let mut i = start;
+        while let Some(&ch) = slice.get(i) {
+            match ch {
+                b':' => {
+                    self.read.index = i + 1;
+                    return Some(&slice[start..i]);
+                }
+                b'{' | b'[' if i != start => {
+                    self.read.index = i;
+                    return Some(&slice[start..i]);
+                }
+                b'{' | b'[' | b'}' | b']' => return None,
+                b'=' | b'~' | b'#' | b'$' | b'@' if !in_map => return None,
+                ch if ch < 0x21 && !in_map => return None,
+                _ => i += 1,
+            }
+        }
+        None
+    }
+}
@@ -2,35 +2,43 @@
 
 mod access;
 mod deserializer;
+#[cfg(feature = "alloc")]
+mod extract;
 mod iter;
 pub(crate) mod parser_number;
 mod read;
+mod scan;
 mod stream_deserializer;
 mod wrapper;
 
 // use self::wrapper::InitDeserializer;
+use crate::error::Result;
 #[cfg(feature = "float_roundtrip")]
 use crate::lexical;
-use crate::{
-    error::{Error, Result},
-    value::Number,
-};
+#[cfg(feature = "alloc")]
+use crate::{error::Error, value::Number};
 #[cfg(feature = "float_roundtrip")]
 use core::iter;
+#[cfg(feature = "alloc")]
 use core::{result, str::FromStr};
 use serde::de;
 
 #[cfg(feature = "arbitrary_precision")]
 use crate::number::NumberDeserializer;
 
+#[cfg(feature = "alloc")]
+pub use self::extract::extract;
 #[cfg(feature = "std")]
 pub use self::read::IoRead;
+#[doc(hidden)]
+pub use self::scan::set_simd_scan;
 pub use self::{
     deserializer::Deserializer,
     read::{Read, SliceRead, StrRead},
     wrapper::InitDeserializer,
 };
 
+#[cfg(feature = "alloc")]
 impl FromStr for Number {
     type Err = Error;
 
@@ -56,27 +64,16 @@ where
 
 /// Deserialize an instance of type `T` from an IO stream of EnCom.
 ///
-/// The content of the IO stream is deserialized directly from the stream
-/// without being buffered in memory by serde_encom.
-///
-/// When reading from a source against which short reads are not efficient, such
-/// as a [`File`], you will want to apply your own buffering because serde_encom
-/// will not buffer the input. See [`std::io::BufReader`].
+/// The content of the IO stream is read into memory up to its end and then
+/// deserialized as by [`from_slice`], since the length prefixed strings and
+/// bytes of EnCom are read as whole slices.
 ///
 /// It is expected that the input stream ends after the deserialized object.
 /// If the stream does not end, such as in the case of a persistent socket connection,
 /// this function will not return. It is possible instead to deserialize from a prefix of an input
 /// stream without looking for EOF by managing your own [`Deserializer`].
 ///
-/// Note that counter to intuition, this function is usually slower than
-/// reading a file completely into memory and then applying [`from_str`]
-/// or [`from_slice`] on it. See [issue #160].
-///
-/// [`File`]: https://doc.rust-lang.org/std/fs/struct.File.html
-/// [`std::io::BufReader`]: https://doc.rust-lang.org/std/io/struct.BufReader.html
-/// [`from_str`]: ./fn.from_str.html
 /// [`from_slice`]: ./fn.from_slice.html
-/// [issue #160]: https://github.com/serde-rs/json/issues/160
 ///
 /// # Example
 ///
@@ -159,12 +156,14 @@ where
 /// type.
 #[cfg(feature = "std")]
 #[cfg_attr(docsrs, doc(cfg(feature = "std")))]
-pub fn from_reader<R, T>(rdr: R) -> Result<T>
+pub fn from_reader<R, T>(mut rdr: R) -> Result<T>
 where
     R: crate::io::Read,
     T: de::DeserializeOwned,
 {
-    from_trait(read::IoRead::new(rdr))
+    let mut buf = Vec::new();
+    rdr.read_to_end(&mut buf).map_err(Error::io)?;
+    from_slice(&buf)
 }
 
 /// Deserialize an instance of type `T` from bytes of EnCom text.
@@ -5,6 +5,10 @@ pub(crate) enum ParserNumber {
     F64(f64),
     U64(u64),
     I64(i64),
+    /// An integer that doesn't fit in `u64`.
+    U128(u128),
+    /// An integer that doesn't fit in `i64`.
+    I128(i128),
     #[cfg(feature = "arbitrary_precision")]
     String(String),
 }
@@ -18,6 +22,8 @@ impl ParserNumber {
             ParserNumber::F64(x) => visitor.visit_f64(x),
             ParserNumber::U64(x) => visitor.visit_u64(x),
             ParserNumber::I64(x) => visitor.visit_i64(x),
+            ParserNumber::U128(x) => visitor.visit_u128(x),
+            ParserNumber::I128(x) => visitor.visit_i128(x),
             #[cfg(feature = "arbitrary_precision")]
             ParserNumber::String(x) => visitor.visit_map(NumberDeserializer { number: x.into() }),
         }
@@ -28,6 +34,9 @@ impl ParserNumber {
             ParserNumber::F64(x) => de::Error::invalid_type(Unexpected::Float(x), exp),
             ParserNumber::U64(x) => de::Error::invalid_type(Unexpected::Unsigned(x), exp),
             ParserNumber::I64(x) => de::Error::invalid_type(Unexpected::Signed(x), exp),
+            ParserNumber::U128(_) | ParserNumber::I128(_) => {
+                de::Error::invalid_type(Unexpected::Other("integer"), exp)
+            }
             #[cfg(feature = "arbitrary_precision")]
             ParserNumber::String(_) => de::Error::invalid_type(Unexpected::Other("number"), exp),
         }
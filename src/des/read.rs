@@ -8,6 +8,7 @@ use debug_unsafe::slice::SliceGetter;
 
 #[cfg(feature = "std")]
 use super::iter::LineColIterator;
+use super::scan;
 #[cfg(feature = "std")]
 use crate::io;
 #[cfg(feature = "raw_value")]
@@ -33,6 +34,16 @@ pub trait Read<'de>: private::Sealed {
     #[doc(hidden)]
     fn discard(&mut self);
 
+    /// Skips whitespace and returns the byte after it without consuming it,
+    /// or `None` at the end of the input.
+    #[doc(hidden)]
+    fn skip_whitespace(&mut self) -> Result<Option<u8>>;
+
+    /// Skips to the next `: { [ = ~ # $ @ . } ]` or whitespace and returns
+    /// it without consuming it, or `None` at the end of the input.
+    #[doc(hidden)]
+    fn skip_to_delimiter(&mut self) -> Result<Option<u8>>;
+
     /// Position of the most recent call to next().
     ///
     /// The most recent call was probably next() and not peek(), but this method
@@ -62,8 +73,17 @@ pub trait Read<'de>: private::Sealed {
 
     fn read_slice<'s>(&'s mut self, len: usize) -> Result<&'de [u8]>;
 
+    /// Skips `len` bytes of a string or bytes value.
+    #[doc(hidden)]
+    fn skip_bytes(&mut self, len: usize) -> Result<()>;
+
     fn parse_int_any_pos(&mut self) -> Result<u64>;
 
+    /// Whether a length prefixed string like `5=value` starts at the current
+    /// position, as opposed to an identifier like `Variant` or a number.
+    #[doc(hidden)]
+    fn is_str_ahead(&mut self) -> Result<bool>;
+
     // fn parse_int(&mut self) -> Result<ParserNumber>;
 
     // fn parse_int_any(&mut self) -> Result<ParserNumber>;
@@ -85,11 +105,6 @@ pub trait Read<'de>: private::Sealed {
     //#[doc(hidden)]
     //fn parse_str_raw<'s>(&'s mut self) -> Result<Reference<'de, 's, [u8]>>;
 
-    /// Assumes the previous byte was a quotation mark. Parses an EnCom-escaped
-    /// string until the next quotation mark but discards the data.
-    #[doc(hidden)]
-    fn ignore_str(&mut self) -> Result<()>;
-
     /// Assumes the previous byte was a hex escape sequnce ('\u') in a string.
     /// Parses next hexadecimal sequence.
     #[doc(hidden)]
@@ -175,9 +190,9 @@ where
 // This is more efficient than other iterators because peek() can be read-only
 // and we can compute line/col position only if an error happens.
 pub struct SliceRead<'a> {
-    slice: &'a [u8],
+    pub(crate) slice: &'a [u8],
     /// Index of the *next* byte that will be returned by next() or peek().
-    index: usize,
+    pub(crate) index: usize,
     #[cfg(feature = "raw_value")]
     raw_buffering_start_index: usize,
     save_start: usize,
@@ -208,6 +223,9 @@ where
     /// Create an EnCom input source to read from a std::io input stream.
     pub fn new(reader: R) -> Self {
         IoRead {
+            // Buffering here would read past the end of a value, which is
+            // left for the next reader of a stream.
+            #[allow(clippy::unbuffered_bytes)]
             iter: LineColIterator::new(reader.bytes()),
             ch: None,
             #[cfg(feature = "raw_value")]
@@ -317,6 +335,28 @@ where
         }
     }
 
+    fn skip_whitespace(&mut self) -> Result<Option<u8>> {
+        loop {
+            match self.peek()? {
+                Some(ch) if ch < 0x21 => self.discard(),
+                other => return Ok(other),
+            }
+        }
+    }
+
+    fn skip_to_delimiter(&mut self) -> Result<Option<u8>> {
+        loop {
+            match self.peek()? {
+                Some(
+                    b':' | b'{' | b'[' | b'=' | b'~' | b'#' | b'$' | b'@' | b'.' | b'}' | b']'
+                    | b' ' | b'\n' | b'\t' | b'\r',
+                )
+                | None => return self.peek(),
+                Some(_) => self.discard(),
+            }
+        }
+    }
+
     fn position(&self) -> Position {
         Position {
             line: self.iter.line(),
@@ -349,11 +389,30 @@ where
         unimplemented!()
     }
 
+    fn skip_bytes(&mut self, len: usize) -> Result<()> {
+        for _ in 0..len {
+            if self.next()?.is_none() {
+                return error(self, ErrorCode::EofWhileParsingString);
+            }
+        }
+        Ok(())
+    }
+
     #[inline]
     fn parse_int_any_pos(&mut self) -> Result<u64> {
         unimplemented!()
     }
 
+    /// Only one byte is peeked, which tells apart nothing but input that
+    /// doesn't start with a digit.
+    #[inline]
+    fn is_str_ahead(&mut self) -> Result<bool> {
+        match self.peek()? {
+            Some(b'0'..=b'9') => error(self, ErrorCode::LengthPrefixInReader),
+            _ => Ok(false),
+        }
+    }
+
     /* #[inline]
     fn parse_int(&mut self) -> Result<ParserNumber> {
         unimplemented!()
@@ -381,26 +440,6 @@ where
             .map(Reference::Copied)
     } */
 
-    fn ignore_str(&mut self) -> Result<()> {
-        loop {
-            let ch = next_or_eof(self)?;
-            if !ESCAPE[ch as usize] {
-                continue;
-            }
-            match ch {
-                b' ' | b'\n' | b'\t' | b'\r' => {
-                    return Ok(());
-                }
-                /* b'\\' => {
-                    ignore_escape(self)?;
-                } */
-                _ => {
-                    return error(self, ErrorCode::ControlCharacterWhileParsingString);
-                }
-            }
-        }
-    }
-
     fn decode_hex_escape(&mut self) -> Result<u16> {
         let mut n = 0;
         for _ in 0..4 {
@@ -580,6 +619,27 @@ impl<'de> Read<'de> for SliceRead<'de> {
         self.index += 1;
     }
 
+    #[inline]
+    fn skip_whitespace(&mut self) -> Result<Option<u8>> {
+        // Compact input has no whitespace between most values, so don't set
+        // up vectors for it.
+        match self.slice.get(self.index) {
+            Some(&ch) if ch < 0x21 => {
+                self.index += scan::whitespace_len(&self.slice[self.index..]);
+                self.peek()
+            }
+            ch => Ok(ch.copied()),
+        }
+    }
+
+    #[inline]
+    fn skip_to_delimiter(&mut self) -> Result<Option<u8>> {
+        if let Some(rest) = self.slice.get(self.index..) {
+            self.index += scan::delimiter_pos(rest);
+        }
+        self.peek()
+    }
+
     #[inline]
     fn position(&self) -> Position {
         self.position_of_index(self.index)
@@ -599,6 +659,10 @@ impl<'de> Read<'de> for SliceRead<'de> {
 
     #[inline]
     fn read_str<'s>(&'s mut self, len: usize) -> Result<&'de str> {
+        if len > self.slice.len() - self.index {
+            self.index = self.slice.len();
+            return error(self, ErrorCode::EofWhileParsingString);
+        }
         let start = self.index;
         self.index += len;
         as_str(self, &self.slice[start..self.index])
@@ -606,11 +670,25 @@ impl<'de> Read<'de> for SliceRead<'de> {
 
     #[inline]
     fn read_slice<'s>(&'s mut self, len: usize) -> Result<&'de [u8]> {
+        if len > self.slice.len() - self.index {
+            self.index = self.slice.len();
+            return error(self, ErrorCode::EofWhileParsingString);
+        }
         let start = self.index;
         self.index += len;
         Ok(&self.slice[start..self.index])
     }
 
+    #[inline]
+    fn skip_bytes(&mut self, len: usize) -> Result<()> {
+        if len > self.slice.len() - self.index {
+            self.index = self.slice.len();
+            return error(self, ErrorCode::EofWhileParsingString);
+        }
+        self.index += len;
+        Ok(())
+    }
+
     #[inline]
     fn parse_int_any_pos(&mut self) -> Result<u64> {
         let (res, i) =
@@ -619,6 +697,13 @@ impl<'de> Read<'de> for SliceRead<'de> {
         Ok(res)
     }
 
+    #[inline]
+    fn is_str_ahead(&mut self) -> Result<bool> {
+        let rest = &self.slice[self.index..];
+        let digits = rest.iter().take_while(|ch| ch.is_ascii_digit()).count();
+        Ok(digits != 0 && rest.get(digits) == Some(&b'='))
+    }
+
     /* #[inline]
     fn parse_int(&mut self) -> Result<ParserNumber> {
         let res = if *self.slice.first().ok_or(AtoiSimdError::Empty)? == b'-' {
@@ -664,28 +749,6 @@ impl<'de> Read<'de> for SliceRead<'de> {
         self.parse_str_bytes(false, |_, bytes| Ok(bytes))
     } */
 
-    fn ignore_str(&mut self) -> Result<()> {
-        // loop {
-        while self.index < self.slice.len() && !ESCAPE[self.slice[self.index] as usize] {
-            self.index += 1;
-        }
-        if self.index == self.slice.len() {
-            return error(self, ErrorCode::EofWhileParsingString);
-        }
-        match self.slice[self.index] {
-            b' ' | b'\n' | b'\t' | b'\r' => {
-                self.index += 1;
-                Ok(())
-            }
-            /* b'\\' => {
-                self.index += 1;
-                ignore_escape(self)?;
-            } */
-            _ => error(self, ErrorCode::ControlCharacterWhileParsingString),
-        }
-        // }
-    }
-
     fn decode_hex_escape(&mut self) -> Result<u16> {
         if self.index + 4 > self.slice.len() {
             self.index = self.slice.len();
@@ -787,6 +850,16 @@ impl<'de> Read<'de> for StrRead<'de> {
         self.delegate.discard();
     }
 
+    #[inline]
+    fn skip_whitespace(&mut self) -> Result<Option<u8>> {
+        self.delegate.skip_whitespace()
+    }
+
+    #[inline]
+    fn skip_to_delimiter(&mut self) -> Result<Option<u8>> {
+        self.delegate.skip_to_delimiter()
+    }
+
     #[inline]
     fn position(&self) -> Position {
         self.delegate.position()
@@ -812,11 +885,21 @@ impl<'de> Read<'de> for StrRead<'de> {
         self.delegate.read_slice(len)
     }
 
+    #[inline]
+    fn skip_bytes(&mut self, len: usize) -> Result<()> {
+        self.delegate.skip_bytes(len)
+    }
+
     #[inline]
     fn parse_int_any_pos(&mut self) -> Result<u64> {
         self.delegate.parse_int_any_pos()
     }
 
+    #[inline]
+    fn is_str_ahead(&mut self) -> Result<bool> {
+        self.delegate.is_str_ahead()
+    }
+
     /* #[inline]
     fn parse_int(&mut self) -> Result<ParserNumber> {
         self.delegate.parse_int()
@@ -846,11 +929,6 @@ impl<'de> Read<'de> for StrRead<'de> {
         self.delegate.parse_str_raw()
     } */
 
-    #[inline]
-    fn ignore_str(&mut self) -> Result<()> {
-        self.delegate.ignore_str()
-    }
-
     #[inline]
     fn decode_hex_escape(&mut self) -> Result<u16> {
         self.delegate.decode_hex_escape()
@@ -926,6 +1004,16 @@ where
         R::discard(self);
     }
 
+    #[inline]
+    fn skip_whitespace(&mut self) -> Result<Option<u8>> {
+        R::skip_whitespace(self)
+    }
+
+    #[inline]
+    fn skip_to_delimiter(&mut self) -> Result<Option<u8>> {
+        R::skip_to_delimiter(self)
+    }
+
     #[inline]
     fn position(&self) -> Position {
         R::position(self)
@@ -951,11 +1039,21 @@ where
         R::read_slice(self, len)
     }
 
+    #[inline]
+    fn skip_bytes(&mut self, len: usize) -> Result<()> {
+        R::skip_bytes(self, len)
+    }
+
     #[inline]
     fn parse_int_any_pos(&mut self) -> Result<u64> {
         R::parse_int_any_pos(self)
     }
 
+    #[inline]
+    fn is_str_ahead(&mut self) -> Result<bool> {
+        R::is_str_ahead(self)
+    }
+
     /* #[inline]
     fn parse_int(&mut self) -> Result<ParserNumber> {
         R::parse_int(self)
@@ -981,11 +1079,6 @@ where
         R::parse_str_raw(self)
     } */
 
-    #[inline]
-    fn ignore_str(&mut self) -> Result<()> {
-        R::ignore_str(self)
-    }
-
     #[inline]
     fn decode_hex_escape(&mut self) -> Result<u16> {
         R::decode_hex_escape(self)
@@ -1105,9 +1198,9 @@ fn as_str<'de, 's, R: Read<'de>>(read: &R, slice: &'s [u8]) -> Result<&'s str> {
     str::from_utf8(slice).or_else(|_| error(read, ErrorCode::InvalidUnicodeCodePoint))
 }
 
-/// Parses an EnCom escape sequence and appends it into the scratch space. Assumes
+/* /// Parses an EnCom escape sequence and appends it into the scratch space. Assumes
 /// the previous byte read was a backslash.
-/* fn parse_escape<'de, R: Read<'de>>(
+fn parse_escape<'de, R: Read<'de>>(
     read: &mut R,
     validate: bool,
     scratch: &mut Vec<u8>,
@@ -1207,9 +1300,9 @@ fn as_str<'de, 's, R: Read<'de>>(read: &R, slice: &'s [u8]) -> Result<&'s str> {
     Ok(())
 } */
 
-/// Parses an EnCom escape sequence and discards the value. Assumes the previous
+/* /// Parses an EnCom escape sequence and discards the value. Assumes the previous
 /// byte read was a backslash.
-/* fn ignore_escape<'de, R>(read: &mut R) -> Result<()>
+fn ignore_escape<'de, R>(read: &mut R) -> Result<()>
 where
     R: ?Sized + Read<'de>,
 {
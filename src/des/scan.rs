@@ -0,0 +1,345 @@
+//! Scanning over the runs of bytes that the deserializer skips without
+//! looking at each of them: whitespace, and the text of a value up to the
+//! delimiter that tells its type.
+//!
+//! Whole vectors are compared at once with AVX2 or SSE2 on x86_64 and NEON
+//! on aarch64, and the bytes that don't fill a vector are compared one by
+//! one. AVX2 is used if the target is compiled with it, or with the `std`
+//! feature if the CPU running the code has it; otherwise x86_64 falls back
+//! to SSE2.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+/// Bytes that end the text of a value whose type isn't known yet: a map
+/// key, the length prefix of a string, bytes or a date-time, the dot of a
+/// float, or the end of a number or a boolean.
+const DELIMITERS: &[u8] = b":{[=~#$@.}] \n\t\r";
+
+static IS_DELIMITER: [bool; 256] = {
+    let mut table = [false; 256];
+    let mut i = 0;
+    while i < DELIMITERS.len() {
+        table[DELIMITERS[i] as usize] = true;
+        i += 1;
+    }
+    table
+};
+
+/// Classes of the delimiters by their high nibble, one bit per high nibble
+/// that some delimiter has. A byte is a delimiter if the classes of its low
+/// and high nibble share a bit.
+#[cfg(any(
+    all(target_arch = "x86_64", any(target_feature = "avx2", feature = "std")),
+    all(target_arch = "aarch64", target_feature = "neon"),
+))]
+const HIGH_NIBBLE: [u8; 16] = [
+    0x01, 0, 0x02, 0x04, 0x08, 0x10, 0, 0x20, 0, 0, 0, 0, 0, 0, 0, 0,
+];
+
+/// Classes of the delimiters by their low nibble, the bits of the high
+/// nibbles that a delimiter has together with it.
+#[cfg(any(
+    all(target_arch = "x86_64", any(target_feature = "avx2", feature = "std")),
+    all(target_arch = "aarch64", target_feature = "neon"),
+))]
+const LOW_NIBBLE: [u8; 16] = [
+    0x0a, 0, 0, 0x02, 0x02, 0, 0, 0, 0, 0x01, 0x05, 0x30, 0, 0x35, 0x22, 0,
+];
+
+/// Whether the vector comparisons are used. Turning them off leaves the
+/// loops over single bytes, which benchmarks compare them with.
+static SIMD: AtomicBool = AtomicBool::new(true);
+
+/// Turns the vector comparisons of the slice readers on or off for the
+/// whole process. Only meant for benchmarks.
+#[doc(hidden)]
+pub fn set_simd_scan(enabled: bool) {
+    SIMD.store(enabled, Ordering::Relaxed);
+}
+
+/// Whitespace is any control byte or a space.
+#[inline]
+fn is_whitespace(ch: u8) -> bool {
+    ch < 0x21
+}
+
+/// The number of whitespace bytes at the start of `bytes`.
+#[inline]
+pub(crate) fn whitespace_len(bytes: &[u8]) -> usize {
+    let mut i = if SIMD.load(Ordering::Relaxed) {
+        simd::whitespace_len(bytes)
+    } else {
+        0
+    };
+    while i < bytes.len() && is_whitespace(bytes[i]) {
+        i += 1;
+    }
+    i
+}
+
+/// The index of the first delimiter in `bytes`, or its length if there is
+/// none.
+#[inline]
+pub(crate) fn delimiter_pos(bytes: &[u8]) -> usize {
+    // Most tokens are a short key or number, for which a vector costs more
+    // than looking at their bytes.
+    for (i, &ch) in bytes.iter().take(8).enumerate() {
+        if IS_DELIMITER[ch as usize] {
+            return i;
+        }
+    }
+    let mut i = if SIMD.load(Ordering::Relaxed) {
+        simd::delimiter_pos(bytes)
+    } else {
+        0
+    };
+    while i < bytes.len() && !IS_DELIMITER[bytes[i] as usize] {
+        i += 1;
+    }
+    i
+}
+
+// Each `simd` module returns an index before which no byte is a match, and
+// which is either that of a match or leaves less than a vector of bytes.
+
+/// Callers must make sure that the CPU has AVX2.
+#[cfg(all(target_arch = "x86_64", any(target_feature = "avx2", feature = "std")))]
+mod avx2 {
+    use super::{HIGH_NIBBLE, LOW_NIBBLE};
+    use core::arch::x86_64::*;
+
+    const LANES: usize = 32;
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn whitespace_len(bytes: &[u8]) -> usize {
+        let mut i = 0;
+        while i + LANES <= bytes.len() {
+            // SAFETY: the 32 bytes at `i` are in `bytes`, and the CPU
+            // has AVX2.
+            let mask = unsafe {
+                let v = _mm256_loadu_si256(bytes.as_ptr().add(i).cast());
+                let space = _mm256_cmpeq_epi8(_mm256_min_epu8(v, _mm256_set1_epi8(0x20)), v);
+                !(_mm256_movemask_epi8(space) as u32)
+            };
+            if mask != 0 {
+                return i + mask.trailing_zeros() as usize;
+            }
+            i += LANES;
+        }
+        i
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn delimiter_pos(bytes: &[u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: the CPU has AVX2, and the tables are 16 bytes each.
+        let (low_table, high_table, nibble) = unsafe {
+            let low = _mm_loadu_si128(LOW_NIBBLE.as_ptr().cast());
+            let high = _mm_loadu_si128(HIGH_NIBBLE.as_ptr().cast());
+            (
+                _mm256_broadcastsi128_si256(low),
+                _mm256_broadcastsi128_si256(high),
+                _mm256_set1_epi8(0x0f),
+            )
+        };
+        while i + LANES <= bytes.len() {
+            // SAFETY: the 32 bytes at `i` are in `bytes`, and the CPU
+            // has AVX2.
+            let mask = unsafe {
+                let v = _mm256_loadu_si256(bytes.as_ptr().add(i).cast());
+                let low = _mm256_and_si256(v, nibble);
+                let high = _mm256_and_si256(_mm256_srli_epi16(v, 4), nibble);
+                let class = _mm256_and_si256(
+                    _mm256_shuffle_epi8(low_table, low),
+                    _mm256_shuffle_epi8(high_table, high),
+                );
+                let other = _mm256_cmpeq_epi8(class, _mm256_setzero_si256());
+                !(_mm256_movemask_epi8(other) as u32)
+            };
+            if mask != 0 {
+                return i + mask.trailing_zeros() as usize;
+            }
+            i += LANES;
+        }
+        i
+    }
+}
+
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "sse2",
+    not(target_feature = "avx2"),
+))]
+mod sse2 {
+    use super::DELIMITERS;
+    use core::arch::x86_64::*;
+
+    const LANES: usize = 16;
+
+    #[inline]
+    pub(super) fn whitespace_len(bytes: &[u8]) -> usize {
+        let mut i = 0;
+        while i + LANES <= bytes.len() {
+            // SAFETY: the 16 bytes at `i` are in `bytes`, and SSE2 is
+            // enabled.
+            let mask = unsafe {
+                let v = _mm_loadu_si128(bytes.as_ptr().add(i).cast());
+                let space = _mm_cmpeq_epi8(_mm_min_epu8(v, _mm_set1_epi8(0x20)), v);
+                !(_mm_movemask_epi8(space) as u32) & 0xffff
+            };
+            if mask != 0 {
+                return i + mask.trailing_zeros() as usize;
+            }
+            i += LANES;
+        }
+        i
+    }
+
+    /// SSE2 has no byte shuffle to look the nibbles up with, so each
+    /// delimiter is compared on its own.
+    #[inline]
+    pub(super) fn delimiter_pos(bytes: &[u8]) -> usize {
+        let mut i = 0;
+        while i + LANES <= bytes.len() {
+            // SAFETY: the 16 bytes at `i` are in `bytes`, and SSE2 is
+            // enabled.
+            let mask = unsafe {
+                let v = _mm_loadu_si128(bytes.as_ptr().add(i).cast());
+                let mut found = _mm_setzero_si128();
+                for &delimiter in DELIMITERS {
+                    let eq = _mm_cmpeq_epi8(v, _mm_set1_epi8(delimiter as i8));
+                    found = _mm_or_si128(found, eq);
+                }
+                _mm_movemask_epi8(found) as u32
+            };
+            if mask != 0 {
+                return i + mask.trailing_zeros() as usize;
+            }
+            i += LANES;
+        }
+        i
+    }
+}
+
+#[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+mod simd {
+    #[inline]
+    pub(super) fn whitespace_len(bytes: &[u8]) -> usize {
+        // SAFETY: the target is compiled with AVX2.
+        unsafe { super::avx2::whitespace_len(bytes) }
+    }
+
+    #[inline]
+    pub(super) fn delimiter_pos(bytes: &[u8]) -> usize {
+        // SAFETY: the target is compiled with AVX2.
+        unsafe { super::avx2::delimiter_pos(bytes) }
+    }
+}
+
+/// Without AVX2 at compile time, whether the CPU has it is checked when the
+/// code runs, which the standard library caches after the first time.
+#[cfg(all(
+    target_arch = "x86_64",
+    target_feature = "sse2",
+    not(target_feature = "avx2"),
+))]
+mod simd {
+    #[inline]
+    pub(super) fn whitespace_len(bytes: &[u8]) -> usize {
+        #[cfg(feature = "std")]
+        if std::is_x86_feature_detected!("avx2") {
+            // SAFETY: the CPU has AVX2.
+            return unsafe { super::avx2::whitespace_len(bytes) };
+        }
+        super::sse2::whitespace_len(bytes)
+    }
+
+    #[inline]
+    pub(super) fn delimiter_pos(bytes: &[u8]) -> usize {
+        #[cfg(feature = "std")]
+        if std::is_x86_feature_detected!("avx2") {
+            // SAFETY: the CPU has AVX2.
+            return unsafe { super::avx2::delimiter_pos(bytes) };
+        }
+        super::sse2::delimiter_pos(bytes)
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod simd {
+    use super::{HIGH_NIBBLE, LOW_NIBBLE};
+    use core::arch::aarch64::*;
+
+    const LANES: usize = 16;
+
+    /// Four bits for each byte of `mask`, all set for a match.
+    #[inline]
+    unsafe fn nibble_mask(mask: uint8x16_t) -> u64 {
+        let narrowed = vshrn_n_u16(vreinterpretq_u16_u8(mask), 4);
+        vget_lane_u64(vreinterpret_u64_u8(narrowed), 0)
+    }
+
+    #[inline]
+    pub(super) fn whitespace_len(bytes: &[u8]) -> usize {
+        let mut i = 0;
+        while i + LANES <= bytes.len() {
+            // SAFETY: the 16 bytes at `i` are in `bytes`, and NEON is
+            // enabled.
+            let mask = unsafe {
+                let v = vld1q_u8(bytes.as_ptr().add(i));
+                nibble_mask(vcgtq_u8(v, vdupq_n_u8(0x20)))
+            };
+            if mask != 0 {
+                return i + mask.trailing_zeros() as usize / 4;
+            }
+            i += LANES;
+        }
+        i
+    }
+
+    #[inline]
+    pub(super) fn delimiter_pos(bytes: &[u8]) -> usize {
+        let mut i = 0;
+        // SAFETY: NEON is enabled, and the tables are 16 bytes each.
+        let (low_table, high_table) = unsafe {
+            (
+                vld1q_u8(LOW_NIBBLE.as_ptr()),
+                vld1q_u8(HIGH_NIBBLE.as_ptr()),
+            )
+        };
+        while i + LANES <= bytes.len() {
+            // SAFETY: the 16 bytes at `i` are in `bytes`, and NEON is
+            // enabled.
+            let mask = unsafe {
+                let v = vld1q_u8(bytes.as_ptr().add(i));
+                let low = vandq_u8(v, vdupq_n_u8(0x0f));
+                let high = vshrq_n_u8(v, 4);
+                let class = vandq_u8(vqtbl1q_u8(low_table, low), vqtbl1q_u8(high_table, high));
+                nibble_mask(vtstq_u8(class, class))
+            };
+            if mask != 0 {
+                return i + mask.trailing_zeros() as usize / 4;
+            }
+            i += LANES;
+        }
+        i
+    }
+}
+
+#[cfg(not(any(
+    all(target_arch = "x86_64", target_feature = "sse2"),
+    all(target_arch = "aarch64", target_feature = "neon"),
+)))]
+mod simd {
+    #[inline]
+    pub(super) fn whitespace_len(_bytes: &[u8]) -> usize {
+        0
+    }
+
+    #[inline]
+    pub(super) fn delimiter_pos(_bytes: &[u8]) -> usize {
+        0
+    }
+}
@@ -18,7 +18,7 @@ use serde::de;
 /// use serde_encom::{Deserializer, Value};
 ///
 /// fn main() {
-///     let data = "{\"k\": 3}1\"cool\"\"stuff\" 3{}  [0, 1, 2]";
+///     let data = "{k:3} 1 4=cool 5=stuff  [0 1 2]";
 ///
 ///     let stream = Deserializer::from_str(data).into_iter::<Value>();
 ///
@@ -65,7 +65,7 @@ where
     /// `old_data[stream.byte_offset()..]` to try again.
     ///
     /// ```
-    /// let data = b"0 1 ";
+    /// let data = b"[0] [1] [";
     ///
     /// let de = serde_encom::Deserializer::from_slice(data);
     /// let mut stream = de.into_iter::<Vec<i32>>();
@@ -2,9 +2,7 @@
 use crate::lexical;
 use crate::{
     des::{
-        access::{
-            InitMapAccess, InitSeqAccess, SavedInitMapAccess, SavedInitSeqAccess, VariantAccess,
-        },
+        access::{InitMapAccess, InitSeqAccess, SavedInitMapAccess, SavedInitSeqAccess},
         deserializer::{Deserializer, PreParser},
         read::Read,
     },
@@ -98,7 +96,18 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for InitDeserializer<'a, R> {
     to_inner_des_method!(deserialize_option);
     to_inner_des_method!(deserialize_unit);
     to_inner_des_method!(deserialize_identifier);
-    to_inner_des_method!(deserialize_ignored_any);
+
+    /// Skips the values of the whole input.
+    #[inline]
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        while self.des.parse_whitespace()?.is_some() {
+            self.des.ignore_value()?;
+        }
+        visitor.visit_unit()
+    }
 
     #[inline]
     fn deserialize_unit_struct<V>(self, name: &'static str, visitor: V) -> Result<V::Value>
@@ -181,7 +190,7 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for InitDeserializer<'a, R> {
             return Err(self.des.peek_error(ErrorCode::EofWhileParsingValue));
         }; */
 
-        let value = visitor.visit_map(InitMapAccess::new(self.des));
+        let value = visitor.visit_map(InitMapAccess::fields(self.des));
         match (value, self.des.end_map_init()) {
             (Ok(value), Ok(())) => Ok(value),
             (Err(err), _) | (_, Err(err)) => Err(self.des.fix_position(err)),
@@ -193,15 +202,17 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for InitDeserializer<'a, R> {
     #[inline]
     fn deserialize_enum<V>(
         self,
-        _name: &str,
-        _variants: &'static [&'static str],
+        name: &'static str,
+        variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
         match self.des.parse_whitespace()? {
-            Some(_) => visitor.visit_enum(VariantAccess::new(self.des)),
+            // Tuple and struct variants keep their braces at the top level.
+            Some(b'{') => self.des.deserialize_enum(name, variants, visitor),
+            Some(_) => self.des.visit_variant(visitor),
             None => Err(self.des.peek_error(ErrorCode::EofWhileParsingValue)),
         }
     }
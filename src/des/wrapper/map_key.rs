@@ -1,5 +1,8 @@
 use crate::{
-    des::read::{Read, Reference},
+    des::{
+        access::UnitVariantAccess,
+        read::{Read, Reference},
+    },
     error::{Error, ErrorCode, Result},
     Deserializer,
 };
@@ -9,6 +12,8 @@ use serde::{de, forward_to_deserialize_any};
 /// deserialize invalid EnCom successfully.
 pub(crate) struct MapKeyDeserializer<'a, R: 'a> {
     pub(crate) des: &'a mut Deserializer<R>,
+    /// The key names a struct field, which may be written by position.
+    pub(crate) fields: bool,
 }
 
 macro_rules! deserialize_numeric_key {
@@ -64,6 +69,31 @@ where
         }
     }
 
+    /// Struct fields written by position are read as `u64` identifiers, which
+    /// derived `Deserialize` impls resolve to the field at that position. Keys
+    /// of other maps are always read by name.
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        let by_index = self.fields;
+        let value = match self.des.read.parse_str()? {
+            Reference::Borrowed(s) => match field_position(by_index, s) {
+                Some(index) => visitor.visit_u64(index),
+                None => visitor.visit_borrowed_str(s),
+            },
+            Reference::Copied(s) => match field_position(by_index, s) {
+                Some(index) => visitor.visit_u64(index),
+                None => visitor.visit_str(s),
+            },
+        };
+
+        match value {
+            Ok(value) => Ok(value),
+            Err(err) => Err(self.des.fix_position(err)),
+        }
+    }
+
     deserialize_numeric_key!(deserialize_i8);
     deserialize_numeric_key!(deserialize_i16);
     deserialize_numeric_key!(deserialize_i32);
@@ -136,14 +166,16 @@ where
     #[inline]
     fn deserialize_enum<V>(
         self,
-        name: &'static str,
-        variants: &'static [&'static str],
+        _name: &'static str,
+        _variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        self.des.deserialize_enum(name, variants, visitor)
+        // Only unit variants can be keys, and the key is followed by the
+        // entry's value.
+        visitor.visit_enum(UnitVariantAccess::new(self.des))
     }
 
     #[inline]
@@ -164,6 +196,16 @@ where
 
     forward_to_deserialize_any! {
         char str string unit unit_struct seq tuple tuple_struct map
-        struct identifier ignored_any
+        struct ignored_any
+    }
+}
+
+/// Position of a struct field written by index, if `key` is one.
+#[inline]
+fn field_position(by_index: bool, key: &str) -> Option<u64> {
+    if by_index && !key.is_empty() && key.bytes().all(|b| b.is_ascii_digit()) {
+        key.parse().ok()
+    } else {
+        None
     }
 }
@@ -1,5 +1,5 @@
 use crate::{
-    des::read::Read,
+    des::{access::UnitVariantAccess, read::Read},
     error::{Error, ErrorCode, Result},
     Deserializer,
 };
@@ -136,14 +136,16 @@ where
     #[inline]
     fn deserialize_enum<V>(
         self,
-        name: &'static str,
-        variants: &'static [&'static str],
+        _name: &'static str,
+        _variants: &'static [&'static str],
         visitor: V,
     ) -> Result<V::Value>
     where
         V: de::Visitor<'de>,
     {
-        self.des.deserialize_enum(name, variants, visitor)
+        // Only unit variants can be keys, and the key is followed by the
+        // entry's value.
+        visitor.visit_enum(UnitVariantAccess::new(self.des))
     }
 
     #[inline]
@@ -7,9 +7,12 @@ use crate::{
     },
     error::{Error, ErrorCode, Result},
 };
-use ::std::hint::unreachable_unchecked;
+use core::hint::unreachable_unchecked;
 use serde::de;
 
+/// Digits in `u64::MAX`, from which a number may need a `u128`.
+const U64_DIGITS: usize = 20;
+
 pub(crate) struct SavedSeqDeserializer<'a, 's, R> {
     pub(crate) des: &'a mut Deserializer<R>,
     pub(crate) saved_type: &'s mut SavedType,
@@ -31,15 +34,20 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for SavedSeqDeserializer<'a, '
         let ret = if *self.saved_type == SavedType::Boolean {
             visitor.visit_bool(saved.first() == Some(&b't'))
         } else if *self.saved_type == SavedType::Number && saved.first() == Some(&b'-') {
-            visitor.visit_i64(atoi_simd::parse_neg::<_, false>(unsafe {
-                saved.get_unchecked(1..)
-            })?)
-        } else if saved.is_empty() {
-            match self.des.read.next()? {
-                Some(b't') => visitor.visit_bool(true),
-                Some(b'f') => visitor.visit_bool(false),
-                _ => Err(self.des.peek_error(ErrorCode::ExpectedSomeIdent)),
+            let digits = unsafe { saved.get_unchecked(1..) };
+            match atoi_simd::parse_neg::<i64, false>(digits) {
+                Ok(int) => visitor.visit_i64(int),
+                Err(_) => visitor.visit_i128(atoi_simd::parse_neg::<_, false>(digits)?),
+            }
+        } else if *self.saved_type == SavedType::Number && saved.len() >= U64_DIGITS {
+            let int = atoi_simd::parse_pos::<u128, false>(saved)?;
+            match u64::try_from(int) {
+                Ok(int) => visitor.visit_u64(int),
+                Err(_) => visitor.visit_u128(int),
             }
+        } else if saved.is_empty() {
+            // Only the first element is saved; the rest are read as usual.
+            self.des.deserialize_any(visitor)
         } else {
             let negative = saved.first() == Some(&b'-');
             let saved = if negative {
@@ -55,6 +63,7 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for SavedSeqDeserializer<'a, '
                 SavedType::Bytes => self
                     .des
                     .deserialize_bytes_by_len(visitor, parsed_int as usize),
+                SavedType::DateTime => self.des.visit_datetime_by_len(visitor, parsed_int as usize),
                 SavedType::Number => visitor.visit_u64(parsed_int),
                 SavedType::FloatNumber => {
                     visitor.visit_f64(self.des.parse_decimal(!negative, parsed_int, 0)?)
@@ -89,7 +98,19 @@ impl<'de, 'a, R: Read<'de>> de::Deserializer<'de> for SavedSeqDeserializer<'a, '
     to_inner_des_method!(deserialize_option);
     to_inner_des_method!(deserialize_unit);
     to_inner_des_method!(deserialize_identifier);
-    to_inner_des_method!(deserialize_ignored_any);
+
+    #[inline]
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value>
+    where
+        V: de::Visitor<'de>,
+    {
+        if self.des.read.saved_is_empty() {
+            return self.des.deserialize_ignored_any(visitor);
+        }
+        // The first element was read ahead, and is skipped as it is visited.
+        de::Deserializer::deserialize_any(self, de::IgnoredAny)?;
+        visitor.visit_unit()
+    }
     to_inner_des_method!(deserialize_seq);
 
     #[inline]
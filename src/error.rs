@@ -1,15 +1,17 @@
 //! When serializing or deserializing EnCom goes wrong.
 
 use crate::io;
+#[cfg(feature = "alloc")]
 use alloc::{
     boxed::Box,
     string::{String, ToString},
 };
 use atoi_simd::AtoiSimdError;
+#[cfg(feature = "alloc")]
+use core::str::FromStr;
 use core::{
     fmt::{self, Debug, Display},
     result,
-    str::FromStr,
 };
 use serde::{de, ser};
 #[cfg(feature = "std")]
@@ -23,7 +25,12 @@ pub struct Error {
     /// This `Box` allows us to keep the size of `Error` as small as possible. A
     /// larger `Error` type was substantially slower due to all the functions
     /// that pass around `Result<T, Error>`.
+    #[cfg(feature = "alloc")]
     err: Box<ErrorImpl>,
+    /// Without a heap the error is kept inline. It is only a code and a
+    /// position then, since custom messages are dropped.
+    #[cfg(not(feature = "alloc"))]
+    err: ErrorImpl,
 }
 
 /// Alias for a `Result` with the error type `serde_encom::Error`.
@@ -57,8 +64,11 @@ impl Error {
     /// - `ErrorCategory::Eof` - unexpected end of the input data
     pub fn classify(&self) -> ErrorCategory {
         match self.err.code {
+            #[cfg(feature = "alloc")]
             ErrorCode::Message(_) => ErrorCategory::Data,
-            ErrorCode::Io(_) => ErrorCategory::Io,
+            #[cfg(not(feature = "alloc"))]
+            ErrorCode::Custom => ErrorCategory::Data,
+            ErrorCode::Io(_) | ErrorCode::BufferFull => ErrorCategory::Io,
             ErrorCode::EofWhileParsingList
             | ErrorCode::EofWhileParsingObject
             | ErrorCode::EofWhileParsingString
@@ -81,7 +91,16 @@ impl Error {
             | ErrorCode::TrailingCharacters
             // | ErrorCode::UnexpectedEndOfHexEscape
             | ErrorCode::UnexpectedEndOfString
+            | ErrorCode::InvalidEncodedBytes
+            | ErrorCode::InvalidDateTime
             | ErrorCode::RecursionLimitExceeded => ErrorCategory::Syntax,
+            ErrorCode::CapacityExceeded
+            | ErrorCode::RawBytesNotUtf8
+            | ErrorCode::DateTimeOutOfRange => ErrorCategory::Data,
+            #[cfg(not(feature = "alloc"))]
+            ErrorCode::EncodedBytesWithoutAlloc => ErrorCategory::Data,
+            #[cfg(feature = "std")]
+            ErrorCode::LengthPrefixInReader => ErrorCategory::Data,
         }
     }
 
@@ -91,6 +110,19 @@ impl Error {
         self.classify() == ErrorCategory::Io
     }
 
+    /// Returns true if this error was caused by the output not fitting in the
+    /// buffer given to [`to_slice`](crate::to_slice).
+    pub fn is_buffer_full(&self) -> bool {
+        matches!(self.err.code, ErrorCode::BufferFull)
+    }
+
+    /// Returns true if this error was caused by a string or bytes that are
+    /// longer than the capacity they are read into, as with the adapters of
+    /// the `heapless` module.
+    pub fn is_capacity_exceeded(&self) -> bool {
+        matches!(self.err.code, ErrorCode::CapacityExceeded)
+    }
+
     /// Returns true if this error was caused by input that was not
     /// syntactically valid EnCom.
     pub fn is_syntax(&self) -> bool {
@@ -138,7 +170,7 @@ impl Error {
     /// }
     ///
     /// fn main() {
-    ///     let reader = ReaderThatWillTimeOut(br#" {"k": "#);
+    ///     let reader = ReaderThatWillTimeOut(b" k:");
     ///
     ///     let _: Value = match serde_encom::from_reader(reader) {
     ///         Ok(value) => value,
@@ -223,7 +255,7 @@ impl From<Error> for io::Error {
             err
         } else {
             match j.classify() {
-                ErrorCategory::Io => unreachable!(),
+                ErrorCategory::Io => io::Error::new(ErrorKind::WriteZero, j),
                 ErrorCategory::Syntax | ErrorCategory::Data => {
                     io::Error::new(ErrorKind::InvalidData, j)
                 }
@@ -241,11 +273,20 @@ struct ErrorImpl {
 
 pub(crate) enum ErrorCode {
     /// Catchall for syntax error messages
+    #[cfg(feature = "alloc")]
     Message(Box<str>),
 
+    /// An error from `de::Error::custom` or `ser::Error::custom`, whose
+    /// message can't be kept without a heap.
+    #[cfg(not(feature = "alloc"))]
+    Custom,
+
     /// Some IO error occurred while serializing or deserializing.
     Io(io::Error),
 
+    /// The output doesn't fit in the buffer it is serialized into.
+    BufferFull,
+
     /// EOF while parsing a list.
     EofWhileParsingList,
 
@@ -314,29 +355,74 @@ pub(crate) enum ErrorCode {
 
     /// Encountered nesting of EnCom maps and arrays more than 128 layers deep.
     RecursionLimitExceeded,
+
+    /// Hex or base64 bytes with a character or length that is not valid.
+    InvalidEncodedBytes,
+
+    /// A date-time that is not a valid RFC 3339 date-time.
+    InvalidDateTime,
+
+    /// A date-time outside of the years 0000 to 9999, or with an offset
+    /// that isn't whole minutes.
+    DateTimeOutOfRange,
+
+    /// Hex or base64 bytes need a heap to be decoded into.
+    #[cfg(not(feature = "alloc"))]
+    EncodedBytesWithoutAlloc,
+
+    /// Raw bytes that are not valid UTF-8 were serialized into a string.
+    RawBytesNotUtf8,
+
+    /// The length prefix of a string or bytes is bigger than the capacity
+    /// they are read into.
+    CapacityExceeded,
+
+    /// Digits where a variant or a key may be written as a length prefixed
+    /// string, which an `io::Read` can't look far enough ahead to tell.
+    #[cfg(feature = "std")]
+    LengthPrefixInReader,
 }
 
 impl Error {
     #[cold]
-    pub(crate) fn syntax(code: ErrorCode, line: usize, column: usize) -> Self {
+    fn new(code: ErrorCode, line: usize, column: usize) -> Self {
+        let err = ErrorImpl { code, line, column };
         Error {
-            err: Box::new(ErrorImpl { code, line, column }),
+            #[cfg(feature = "alloc")]
+            err: Box::new(err),
+            #[cfg(not(feature = "alloc"))]
+            err,
         }
     }
 
+    #[cold]
+    pub(crate) fn syntax(code: ErrorCode, line: usize, column: usize) -> Self {
+        Error::new(code, line, column)
+    }
+
     // Not public API. Should be pub(crate).
     //
     // Update `eager_json` crate when this function changes.
     #[doc(hidden)]
     #[cold]
     pub fn io(error: io::Error) -> Self {
-        Error {
-            err: Box::new(ErrorImpl {
-                code: ErrorCode::Io(error),
-                line: 0,
-                column: 0,
-            }),
-        }
+        Error::new(ErrorCode::Io(error), 0, 0)
+    }
+
+    /// Whether this is an integer that didn't fit in 64 bits, which
+    /// `deserialize_any` reads again as one of 128 bits.
+    pub(crate) fn is_number_out_of_range(&self) -> bool {
+        matches!(self.err.code, ErrorCode::NumberOutOfRange)
+    }
+
+    #[cold]
+    pub(crate) fn buffer_full() -> Self {
+        Error::new(ErrorCode::BufferFull, 0, 0)
+    }
+
+    #[cold]
+    pub(crate) fn raw_bytes_not_utf8() -> Self {
+        Error::new(ErrorCode::RawBytesNotUtf8, 0, 0)
     }
 
     #[cold]
@@ -355,8 +441,12 @@ impl Error {
 impl Display for ErrorCode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            #[cfg(feature = "alloc")]
             ErrorCode::Message(msg) => f.write_str(msg),
+            #[cfg(not(feature = "alloc"))]
+            ErrorCode::Custom => f.write_str("custom error"),
             ErrorCode::Io(err) => Display::fmt(err, f),
+            ErrorCode::BufferFull => f.write_str("buffer is full"),
             ErrorCode::EofWhileParsingList => f.write_str("EOF while parsing a list"),
             ErrorCode::EofWhileParsingObject => f.write_str("EOF while parsing an object"),
             ErrorCode::EofWhileParsingString => f.write_str("EOF while parsing a string"),
@@ -388,6 +478,21 @@ impl Display for ErrorCode {
             // ErrorCode::UnexpectedEndOfHexEscape => f.write_str("unexpected end of hex escape"),
             ErrorCode::UnexpectedEndOfString => f.write_str("unexpected end of string"),
             ErrorCode::RecursionLimitExceeded => f.write_str("recursion limit exceeded"),
+            ErrorCode::CapacityExceeded => f.write_str("length exceeds capacity"),
+            ErrorCode::InvalidEncodedBytes => f.write_str("invalid hex or base64 bytes"),
+            ErrorCode::InvalidDateTime => f.write_str("invalid RFC 3339 date-time"),
+            ErrorCode::DateTimeOutOfRange => f.write_str("date-time out of range"),
+            #[cfg(not(feature = "alloc"))]
+            ErrorCode::EncodedBytesWithoutAlloc => {
+                f.write_str("hex or base64 bytes can't be decoded without `alloc`")
+            }
+            ErrorCode::RawBytesNotUtf8 => {
+                f.write_str("raw bytes that are not valid UTF-8 can't be written to a string")
+            }
+            #[cfg(feature = "std")]
+            ErrorCode::LengthPrefixInReader => {
+                f.write_str("a length prefixed identifier can't be read from an io::Read")
+            }
         }
     }
 }
@@ -407,13 +512,7 @@ impl From<AtoiSimdError<'_>> for ErrorCode {
 
 impl From<AtoiSimdError<'_>> for Error {
     fn from(e: AtoiSimdError<'_>) -> Self {
-        Self {
-            err: Box::new(ErrorImpl {
-                code: e.into(),
-                line: 0,
-                column: 0,
-            }),
-        }
+        Error::new(e.into(), 0, 0)
     }
 }
 
@@ -429,7 +528,7 @@ impl serde::de::StdError for Error {
 
 impl Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        Display::fmt(&*self.err, f)
+        Display::fmt(&self.err, f)
     }
 }
 
@@ -450,6 +549,7 @@ impl Display for ErrorImpl {
 // Remove two layers of verbosity from the debug representation. Humans often
 // end up seeing this representation because it is what unwrap() shows.
 impl Debug for Error {
+    #[cfg(feature = "alloc")]
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(
             f,
@@ -459,12 +559,24 @@ impl Debug for Error {
             self.err.column
         )
     }
+
+    #[cfg(not(feature = "alloc"))]
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "Error(\"{}\", line: {}, column: {})",
+            self.err.code, self.err.line, self.err.column
+        )
+    }
 }
 
 impl de::Error for Error {
     #[cold]
     fn custom<T: Display>(msg: T) -> Error {
-        make_error(msg.to_string())
+        #[cfg(feature = "alloc")]
+        return make_error(msg.to_string());
+        #[cfg(not(feature = "alloc"))]
+        return Error::new(ErrorCode::Custom, 0, 0);
     }
 
     #[cold]
@@ -480,23 +592,19 @@ impl de::Error for Error {
 impl ser::Error for Error {
     #[cold]
     fn custom<T: Display>(msg: T) -> Error {
-        make_error(msg.to_string())
+        de::Error::custom(msg)
     }
 }
 
 // Parse our own error message that looks like "{} at line {} column {}" to work
 // around erased-serde round-tripping the error through de::Error::custom.
+#[cfg(feature = "alloc")]
 fn make_error(mut msg: String) -> Error {
     let (line, column) = parse_line_col(&mut msg).unwrap_or((0, 0));
-    Error {
-        err: Box::new(ErrorImpl {
-            code: ErrorCode::Message(msg.into_boxed_str()),
-            line,
-            column,
-        }),
-    }
+    Error::new(ErrorCode::Message(msg.into_boxed_str()), line, column)
 }
 
+#[cfg(feature = "alloc")]
 fn parse_line_col(msg: &mut String) -> Option<(usize, usize)> {
     let start_of_suffix = msg.rfind(" at line ")?;
 
@@ -536,6 +644,7 @@ fn parse_line_col(msg: &mut String) -> Option<(usize, usize)> {
     Some((line, column))
 }
 
+#[cfg(feature = "alloc")]
 fn starts_with_digit(slice: &str) -> bool {
     match slice.as_bytes().first() {
         None => false,
@@ -1 +0,0 @@
-"serde_encom requires that either `std` (default) or `alloc` feature is enabled"
\ No newline at end of file
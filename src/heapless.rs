@@ -0,0 +1,142 @@
+//! Fixed-capacity strings and bytes from the [`heapless`](::heapless) crate.
+//!
+//! With the `heapless` feature, `heapless::String<N>`, `heapless::Vec<T, N>`
+//! and the other heapless collections implement `Serialize` and
+//! `Deserialize`, and fail instead of truncating when the input doesn't fit.
+//!
+//! The [`string`] and [`bytes`] adapters go a step further for EnCom: the
+//! length prefix of a `N=` string or `N~` bytes is checked against the
+//! capacity before the value is read, so an over-long value fails with
+//! [`Error::is_capacity_exceeded`](crate::Error::is_capacity_exceeded) at
+//! its prefix.
+//!
+//! # Example
+//!
+//! ```
+//! use heapless::{String, Vec};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Debug, Serialize, Deserialize)]
+//! struct Sensor {
+//!     #[serde(with = "serde_encom::heapless::string")]
+//!     name: String<8>,
+//!     #[serde(with = "serde_encom::heapless::bytes")]
+//!     key: Vec<u8, 4>,
+//!     readings: Vec<i16, 4>,
+//! }
+//!
+//! let data = b"name:6=boiler key:2~\x01\x02 readings[3 -1]";
+//! let sensor: Sensor = serde_encom::from_slice(data).unwrap();
+//! assert_eq!(sensor.name, "boiler");
+//! assert_eq!(sensor.key, [1, 2]);
+//! assert_eq!(sensor.readings, [3, -1]);
+//!
+//! let data = b"name:10=lighthouse key:0~ readings[]";
+//! let err = serde_encom::from_slice::<Sensor>(data).unwrap_err();
+//! assert!(err.is_capacity_exceeded());
+//! ```
+
+use core::fmt;
+use heapless::{String, Vec};
+use serde::de::{self, Deserializer, Visitor};
+use serde::ser::Serializer;
+
+/// Name of the tuple struct whose length is the capacity of a string or
+/// bytes. Only the EnCom deserializers know it, so the adapters don't work
+/// with other formats.
+pub(crate) const TOKEN: &str = "$serde_encom::private::Capacity";
+
+/// Serialize and deserialize a `heapless::String<N>` as an EnCom string,
+/// checking its length prefix against `N`.
+///
+/// Use it with `#[serde(with = "serde_encom::heapless::string")]`.
+pub mod string {
+    use super::*;
+
+    /// Serializes `value` as a string.
+    pub fn serialize<S, const N: usize>(value: &String<N>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(value)
+    }
+
+    /// Deserializes a string of at most `N` bytes.
+    pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<String<N>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple_struct(TOKEN, N, StringVisitor)
+    }
+
+    struct StringVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for StringVisitor<N> {
+        type Value = String<N>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "a string no more than {} bytes long", N)
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            let mut s = String::new();
+            s.push_str(v)
+                .map_err(|_| E::invalid_length(v.len(), &self))?;
+            Ok(s)
+        }
+    }
+}
+
+/// Serialize and deserialize a `heapless::Vec<u8, N>` as EnCom bytes,
+/// checking their length prefix against `N`.
+///
+/// Use it with `#[serde(with = "serde_encom::heapless::bytes")]`.
+pub mod bytes {
+    use super::*;
+
+    /// Serializes `value` as bytes.
+    pub fn serialize<S, const N: usize>(
+        value: &Vec<u8, N>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_bytes(value)
+    }
+
+    /// Deserializes bytes of at most `N` bytes.
+    pub fn deserialize<'de, D, const N: usize>(deserializer: D) -> Result<Vec<u8, N>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_tuple_struct(TOKEN, N, BytesVisitor)
+    }
+
+    struct BytesVisitor<const N: usize>;
+
+    impl<'de, const N: usize> Visitor<'de> for BytesVisitor<N> {
+        type Value = Vec<u8, N>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            write!(formatter, "no more than {} bytes", N)
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Vec::from_slice(v).map_err(|_| E::invalid_length(v.len(), &self))
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            self.visit_bytes(v.as_bytes())
+        }
+    }
+}
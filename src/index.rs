@@ -0,0 +1,256 @@
+//! Byte offsets of the values of an EnCom document, for random access.
+//!
+//! Building an [`Index`] walks the document once, skipping strings and bytes
+//! by their length prefix, and records where each map entry and array
+//! element is. Lookups then go straight to the recorded bytes.
+
+use crate::{
+    des::{from_slice, Deserializer, SliceRead},
+    error::{ErrorCode, Result},
+    value::push_token,
+};
+use alloc::{collections::BTreeMap, string::String};
+use core::{ops::Range, str};
+use serde::de;
+
+/// Byte spans of the values of an EnCom document, addressed by EnCom Pointer.
+///
+/// The span of a map entry covers its value, without the key. Spans can be
+/// persisted with [`spans`](Index::spans) and attached to the same document
+/// again with [`from_spans`](Index::from_spans).
+///
+/// # Example
+///
+/// ```
+/// use serde_encom::index::Index;
+///
+/// let data = b"records[{id:1 name:5=first} {id:2 name:6=second}]";
+/// let index = Index::build(data).unwrap();
+///
+/// assert_eq!(index.get("/records/1/name"), Some(&b"6=second"[..]));
+///
+/// let name: Option<&str> = index.deserialize_at("/records/1/name").unwrap();
+/// assert_eq!(name, Some("second"));
+/// ```
+pub struct Index<'a> {
+    input: &'a [u8],
+    spans: BTreeMap<String, Range<usize>>,
+    max_depth: Option<usize>,
+}
+
+impl<'a> Index<'a> {
+    /// Indexes every value of the document.
+    pub fn build(input: &'a [u8]) -> Result<Self> {
+        Self::build_inner(input, None)
+    }
+
+    /// Indexes the values of the document down to `depth` levels of nesting.
+    ///
+    /// The top level entries are at depth 1. Lookups of deeper values start
+    /// at their closest indexed ancestor.
+    pub fn build_to_depth(input: &'a [u8], depth: usize) -> Result<Self> {
+        Self::build_inner(input, Some(depth))
+    }
+
+    fn build_inner(input: &'a [u8], max_depth: Option<usize>) -> Result<Self> {
+        let mut builder = Builder {
+            de: Deserializer::new(SliceRead::new(input)),
+            spans: BTreeMap::new(),
+            path: String::new(),
+            max_depth: max_depth.unwrap_or(usize::MAX),
+        };
+        if builder.max_depth > 0 {
+            builder.container(None, 0)?;
+            builder.de.end()?;
+        }
+        builder.spans.insert(String::new(), 0..input.len());
+
+        Ok(Index {
+            input,
+            spans: builder.spans,
+            max_depth,
+        })
+    }
+
+    /// Attaches previously built spans to the document they were built from.
+    pub fn from_spans(
+        input: &'a [u8],
+        spans: BTreeMap<String, Range<usize>>,
+        max_depth: Option<usize>,
+    ) -> Self {
+        Index {
+            input,
+            spans,
+            max_depth,
+        }
+    }
+
+    /// The recorded spans, by EnCom Pointer.
+    pub fn spans(&self) -> &BTreeMap<String, Range<usize>> {
+        &self.spans
+    }
+
+    /// The depth the document was indexed to, or `None` if it was indexed
+    /// completely.
+    pub fn max_depth(&self) -> Option<usize> {
+        self.max_depth
+    }
+
+    /// Returns the number of recorded spans, including the whole document.
+    pub fn len(&self) -> usize {
+        self.spans.len()
+    }
+
+    /// Returns true if no spans are recorded.
+    pub fn is_empty(&self) -> bool {
+        self.spans.is_empty()
+    }
+
+    /// Returns the EnCom text of the value addressed by `pointer`, or `None`
+    /// if there is no such value.
+    pub fn get(&self, pointer: &str) -> Option<&'a [u8]> {
+        self.span(pointer).and_then(|span| self.input.get(span))
+    }
+
+    /// Deserializes the value addressed by `pointer`, borrowing from the
+    /// document where `T` allows it. Returns `None` if there is no such value.
+    pub fn deserialize_at<T>(&self, pointer: &str) -> Result<Option<T>>
+    where
+        T: de::Deserialize<'a>,
+    {
+        if pointer.is_empty() {
+            return from_slice(self.input).map(Some);
+        }
+        let span = match self.span(pointer) {
+            Some(span) if span.start <= span.end && span.end <= self.input.len() => span,
+            _ => return Ok(None),
+        };
+
+        let mut de = Deserializer::new(SliceRead::new(&self.input[..span.end]));
+        de.read.index = span.start;
+        let value = de::Deserialize::deserialize(&mut de)?;
+        de.end()?;
+        Ok(Some(value))
+    }
+
+    fn span(&self, pointer: &str) -> Option<Range<usize>> {
+        if let Some(span) = self.spans.get(pointer) {
+            return Some(span.clone());
+        }
+
+        // Values deeper than `max_depth` are looked up from their ancestor.
+        let cut = pointer.match_indices('/').nth(self.max_depth?)?.0;
+        let ancestor = self.spans.get(&pointer[..cut])?;
+        let mut de = Deserializer::new(SliceRead::new(self.input.get(..ancestor.end)?));
+        de.read.index = ancestor.start;
+        if !de.seek_pointer(&pointer[cut..], cut == 0).ok()? {
+            return None;
+        }
+        de.parse_whitespace().ok()?;
+        let start = de.read.index;
+        de.ignore_value().ok()?;
+        Some(start..de.read.index)
+    }
+}
+
+struct Builder<'a> {
+    de: Deserializer<SliceRead<'a>>,
+    spans: BTreeMap<String, Range<usize>>,
+    path: String,
+    max_depth: usize,
+}
+
+impl<'a> Builder<'a> {
+    /// Records the entries of a map or an array whose opening bracket is
+    /// already consumed, or of the top level.
+    fn container(&mut self, mut is_map: Option<bool>, depth: usize) -> Result<()> {
+        let mut i = 0usize;
+        loop {
+            match self.de.parse_whitespace()? {
+                None | Some(b'}' | b']') => return Ok(()),
+                Some(_) => {}
+            }
+
+            let len = self.path.len();
+            let key = match is_map {
+                Some(false) => None,
+                _ => self.de.scan_key(is_map.is_some()),
+            };
+            match key {
+                Some(key) if *is_map.get_or_insert(true) => self.push_key(key)?,
+                _ if *is_map.get_or_insert(false) => {
+                    // A value without a key has no pointer.
+                    self.de.ignore_value()?;
+                    continue;
+                }
+                _ => {
+                    self.path.push('/');
+                    self.path.push_str(itoa::Buffer::new().format(i));
+                }
+            }
+            self.value(depth + 1)?;
+            self.path.truncate(len);
+            i += 1;
+        }
+    }
+
+    fn value(&mut self, depth: usize) -> Result<()> {
+        let peek = self.de.parse_whitespace()?;
+        let start = self.de.read.index;
+        if depth >= self.max_depth {
+            self.de.ignore_value()?;
+        } else {
+            self.de.remaining_depth -= 1;
+            if self.de.remaining_depth == 0 {
+                return Err(self.de.peek_error(ErrorCode::RecursionLimitExceeded));
+            }
+            match peek {
+                Some(b'{') => {
+                    self.de.eat_char();
+                    self.container(None, depth)?;
+                    self.close()?;
+                }
+                Some(b'[') => {
+                    self.de.eat_char();
+                    self.container(Some(false), depth)?;
+                    self.close()?;
+                }
+                _ => match self.de.scan_key(false) {
+                    // A single entry like an enum variant `Variant:value`.
+                    Some(key) => {
+                        let len = self.path.len();
+                        self.push_key(key)?;
+                        self.value(depth + 1)?;
+                        self.path.truncate(len);
+                    }
+                    None => self.de.ignore_value()?,
+                },
+            }
+            self.de.remaining_depth += 1;
+        }
+        self.spans
+            .insert(self.path.clone(), start..self.de.read.index);
+        Ok(())
+    }
+
+    fn push_key(&mut self, key: &[u8]) -> Result<()> {
+        match str::from_utf8(key) {
+            Ok(key) => {
+                push_token(&mut self.path, key);
+                Ok(())
+            }
+            Err(_) => Err(self.de.error(ErrorCode::InvalidUnicodeCodePoint)),
+        }
+    }
+
+    fn close(&mut self) -> Result<()> {
+        match self.de.parse_whitespace()? {
+            Some(b'}' | b']') => {
+                self.de.eat_char();
+                Ok(())
+            }
+            Some(_) => Err(self.de.peek_error(ErrorCode::TrailingCharacters)),
+            None => Err(self.de.peek_error(ErrorCode::EofWhileParsingObject)),
+        }
+    }
+}
@@ -1,6 +1,7 @@
 //! Reimplements core logic and types from `std::io` in an `alloc`-friendly
 //! fashion.
 
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 use core::{
     fmt::{self, Display},
@@ -11,13 +12,13 @@ pub enum ErrorKind {
     Other,
 }
 
-// IO errors can never occur in no-std mode. All our no-std IO implementations
-// are infallible.
+// The only IO error in no-std mode is a writer that is full. All our other
+// no-std IO implementations are infallible.
 pub struct Error;
 
 impl Display for Error {
-    fn fmt(&self, _formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
-        unreachable!()
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("failed to write whole buffer")
     }
 }
 
@@ -25,6 +26,10 @@ impl Error {
     pub(crate) fn new(_kind: ErrorKind, _error: &'static str) -> Error {
         Error
     }
+
+    pub(crate) fn other(_error: &'static str) -> Error {
+        Error
+    }
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -32,12 +37,13 @@ pub type Result<T> = result::Result<T, Error>;
 pub trait Write {
     fn write(&mut self, buf: &[u8]) -> Result<usize>;
 
-    fn write_all(&mut self, buf: &[u8]) -> Result<()> {
-        // All our Write impls in no_std mode always write the whole buffer in
-        // one call infallibly.
-        let result = self.write(buf);
-        debug_assert!(result.is_ok());
-        debug_assert_eq!(result.unwrap_or(0), buf.len());
+    fn write_all(&mut self, mut buf: &[u8]) -> Result<()> {
+        while !buf.is_empty() {
+            match self.write(buf)? {
+                0 => return Err(Error),
+                n => buf = &buf[n..],
+            }
+        }
         Ok(())
     }
 
@@ -61,6 +67,7 @@ impl<W: Write> Write for &mut W {
     }
 }
 
+#[cfg(feature = "alloc")]
 impl Write for Vec<u8> {
     #[inline]
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
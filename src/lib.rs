@@ -13,14 +13,47 @@
     dead_code
 )]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
+#[cfg(feature = "actix")]
+#[cfg_attr(docsrs, doc(cfg(feature = "actix")))]
+pub mod actix;
+#[cfg(feature = "axum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+pub mod axum;
+mod bytes;
+mod datetime;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
 mod des;
 mod error;
-mod features_check;
+#[cfg(feature = "heapless")]
+#[cfg_attr(docsrs, doc(cfg(feature = "heapless")))]
+pub mod heapless;
+#[cfg(feature = "alloc")]
+pub mod index;
 mod io;
+#[cfg(feature = "alloc")]
 mod macros;
+mod options;
+#[cfg(feature = "rayon")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rayon")))]
+pub mod par;
+#[cfg(feature = "alloc")]
+pub mod schema;
 mod ser;
-mod value;
+#[cfg(feature = "alloc")]
+pub mod value;
+pub mod with;
 
-pub use crate::{des::*, error::*, ser::*, value::*};
+#[cfg(not(feature = "alloc"))]
+pub use crate::{bytes::BytesMode, datetime::DateTime, des::*, error::*, options::Options, ser::*};
+#[cfg(feature = "alloc")]
+pub use crate::{
+    bytes::BytesMode, datetime::DateTime, des::*, error::*, options::Options, ser::*, value::*,
+};
+
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub use crate::macros::__private;
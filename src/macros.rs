@@ -37,6 +37,20 @@
 /// });
 /// ```
 ///
+/// Byte string literals are [`Value::Bytes`](crate::Value::Bytes), while
+/// interpolated byte arrays and vectors are arrays of numbers like any other
+/// `Serialize` type.
+///
+/// ```
+/// # use serde_encom::{encom_from_json, Value};
+/// #
+/// let key = [1u8, 2];
+/// let value = encom_from_json!({ "sig": b"\x01\xff", "key": key });
+///
+/// assert_eq!(value["sig"], Value::Bytes(vec![1, 255]));
+/// assert_eq!(value["key"], encom_from_json!([1, 2]));
+/// ```
+///
 /// Trailing commas are allowed inside both arrays and objects.
 ///
 /// ```
@@ -109,6 +123,16 @@ macro_rules! encom_from_json_internal {
         encom_from_json_internal!(@array [$($elems,)* encom_from_json_internal!({$($map)*})] $($rest)*)
     };
 
+    // Next element is a single token, such as a literal, followed by comma.
+    (@array [$($elems:expr,)*] $next:tt , $($rest:tt)*) => {
+        encom_from_json_internal!(@array [$($elems,)* encom_from_json_internal!($next),] $($rest)*)
+    };
+
+    // Last element is a single token with no trailing comma.
+    (@array [$($elems:expr,)*] $last:tt) => {
+        encom_from_json_internal!(@array [$($elems,)* encom_from_json_internal!($last)])
+    };
+
     // Next element is an expression followed by comma.
     (@array [$($elems:expr,)*] $next:expr, $($rest:tt)*) => {
         encom_from_json_internal!(@array [$($elems,)* encom_from_json_internal!($next),] $($rest)*)
@@ -183,6 +207,16 @@ macro_rules! encom_from_json_internal {
         encom_from_json_internal!(@object $object [$($key)+] (encom_from_json_internal!({$($map)*})) $($rest)*);
     };
 
+    // Next value is a single token, such as a literal, followed by comma.
+    (@object $object:ident ($($key:tt)+) (: $value:tt , $($rest:tt)*) $copy:tt) => {
+        encom_from_json_internal!(@object $object [$($key)+] (encom_from_json_internal!($value)) , $($rest)*);
+    };
+
+    // Last value is a single token with no trailing comma.
+    (@object $object:ident ($($key:tt)+) (: $value:tt) $copy:tt) => {
+        encom_from_json_internal!(@object $object [$($key)+] (encom_from_json_internal!($value)));
+    };
+
     // Next value is an expression followed by comma.
     (@object $object:ident ($($key:tt)+) (: $value:expr , $($rest:tt)*) $copy:tt) => {
         encom_from_json_internal!(@object $object [$($key)+] (encom_from_json_internal!($value)) , $($rest)*);
@@ -272,6 +306,25 @@ macro_rules! encom_from_json_internal {
         })
     };
 
+    // A single token is passed on as a token rather than an expression, so
+    // that a byte string can still be told apart from a `[u8; N]` variable.
+    // Only a single token is matched against `literal`, which fails to
+    // compile instead of falling through for `-x`.
+    ($other:tt) => {
+        encom_from_json_internal!(@token $other)
+    };
+
+    // Byte strings are bytes, other literals are serialized.
+    (@token $other:literal) => {{
+        #[allow(unused_imports)]
+        use $crate::__private::{BytesLiteral as _, SerializeLiteral as _};
+        $crate::__private::Literal($other).into_value()
+    }};
+
+    (@token $other:expr) => {
+        $crate::to_value(&$other).unwrap()
+    };
+
     // Any Serialize type: numbers, strings, struct literals, variables etc.
     // Must be below every other rule.
     ($other:expr) => {
@@ -301,3 +354,34 @@ macro_rules! encom_from_json_unexpected {
 macro_rules! encom_from_json_expect_expr_comma {
     ($e:expr , $($tt:tt)*) => {};
 }
+
+// Helpers of `encom_from_json_internal!` to turn a literal into a `Value`.
+// `Literal(lit).into_value()` finds `BytesLiteral` for a byte string by value
+// before it autorefs to `SerializeLiteral`, which takes every other literal.
+#[doc(hidden)]
+pub mod __private {
+    use crate::Value;
+    use serde::Serialize;
+
+    pub struct Literal<T>(pub T);
+
+    pub trait BytesLiteral {
+        fn into_value(self) -> Value;
+    }
+
+    impl<const N: usize> BytesLiteral for Literal<&[u8; N]> {
+        fn into_value(self) -> Value {
+            Value::Bytes(self.0.to_vec())
+        }
+    }
+
+    pub trait SerializeLiteral {
+        fn into_value(self) -> Value;
+    }
+
+    impl<T: Serialize> SerializeLiteral for &Literal<T> {
+        fn into_value(self) -> Value {
+            crate::to_value(&self.0).unwrap()
+        }
+    }
+}
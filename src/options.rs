@@ -0,0 +1,320 @@
+#[cfg(feature = "alloc")]
+use crate::des::{Deserializer, InitDeserializer, Read, SliceRead, StrRead};
+#[cfg(feature = "alloc")]
+use crate::error::Error;
+use crate::{
+    bytes::BytesMode,
+    error::Result,
+    io,
+    ser::{Formatter, InitSerializer, Serializer, SliceWriter},
+};
+#[cfg(feature = "alloc")]
+use alloc::{string::String, vec::Vec};
+#[cfg(feature = "alloc")]
+use serde::de;
+use serde::ser::Serialize;
+
+/// Settings that change how EnCom is written and read.
+///
+/// The defaults produce the same output as [`to_string`](crate::to_string)
+/// and friends. Data written with some options has to be read with the same
+/// options.
+///
+/// # Examples
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use serde_encom::Options;
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// enum Event {
+///     Started,
+///     Progress(u64),
+///     Failed { code: i32 },
+/// }
+///
+/// let events = vec![Event::Started, Event::Progress(75), Event::Failed { code: -1 }];
+///
+/// let options = Options::new().variants_by_index(true);
+/// let s = options.to_string(&events).unwrap();
+/// assert_eq!(s, "0 1:75 {2{code:-1}}");
+///
+/// let back: Vec<Event> = options.from_str(&s).unwrap();
+/// assert_eq!(back, events);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Options {
+    variant_index: bool,
+    field_index: bool,
+    bytes: Option<BytesMode>,
+}
+
+impl Options {
+    /// Makes the default options.
+    #[inline]
+    pub fn new() -> Self {
+        Options::default()
+    }
+
+    /// Writes enum variants by their index in the enum declaration instead of
+    /// their name, e.g. `{0:7}` instead of `{U64:7}`, and a unit variant as a
+    /// plain number.
+    ///
+    /// This only applies to externally tagged enums, which is the serde
+    /// default. The tags of internally and adjacently tagged enums and enums
+    /// used as map keys keep their names. Reordering variants changes their
+    /// encoding.
+    #[inline]
+    pub fn variants_by_index(mut self, yes: bool) -> Self {
+        self.variant_index = yes;
+        self
+    }
+
+    /// Writes struct fields by their position in the struct declaration
+    /// instead of their name, e.g. `0:3 1:-4` instead of `x:3 y:-4`.
+    ///
+    /// Appending fields keeps old data readable, as long as the new fields
+    /// can be missing (`#[serde(default)]` or `Option`). Reordering or
+    /// removing fields changes the meaning of existing data.
+    ///
+    /// Fields skipped with `#[serde(skip_serializing_if = "...")]` still
+    /// take up their position. Fields that are skipped only when serializing
+    /// or only when deserializing shift the positions of the fields after
+    /// them, so don't use them in this mode. Flattened structs are written
+    /// as maps and keep their field names. Internally tagged and untagged
+    /// enums can't read fields by position.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde::{Deserialize, Serialize};
+    /// use serde_encom::Options;
+    ///
+    /// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    /// struct Point {
+    ///     x: i64,
+    ///     y: i64,
+    /// }
+    ///
+    /// let options = Options::new().fields_by_index(true);
+    /// let s = options.to_string(&Point { x: 3, y: -4 }).unwrap();
+    /// assert_eq!(s, "0:3 1:-4");
+    ///
+    /// let back: Point = options.from_str(&s).unwrap();
+    /// assert_eq!(back, Point { x: 3, y: -4 });
+    /// ```
+    #[inline]
+    pub fn fields_by_index(mut self, yes: bool) -> Self {
+        self.field_index = yes;
+        self
+    }
+
+    /// Writes bytes in the given mode.
+    ///
+    /// By default, bytes are raw, except in strings, which use base64. Raw
+    /// bytes make [`to_string`](Options::to_string) fail if they are not
+    /// valid UTF-8. Bytes of every mode are read back the same way, so this
+    /// option is not needed for reading.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_encom::{BytesMode, Options, Value};
+    ///
+    /// let value: Value = serde_encom::from_slice(b"key:2~\x01\xff").unwrap();
+    ///
+    /// let s = Options::new().bytes(BytesMode::Hex).to_string(&value).unwrap();
+    /// assert_eq!(s, "key:4#01ff");
+    ///
+    /// assert_eq!(serde_encom::to_string(&value).unwrap(), "key:3$Af8");
+    /// assert!(Options::new().bytes(BytesMode::Raw).to_string(&value).is_err());
+    ///
+    /// let back: Value = serde_encom::from_str(&s).unwrap();
+    /// assert_eq!(back, value);
+    /// ```
+    #[inline]
+    pub fn bytes(mut self, mode: BytesMode) -> Self {
+        self.bytes = Some(mode);
+        self
+    }
+
+    fn configure<W, F>(&self, ser: &mut Serializer<W, F>, bytes: BytesMode)
+    where
+        W: io::Write,
+        F: Formatter,
+    {
+        ser.variant_index = self.variant_index;
+        ser.field_index = self.field_index;
+        ser.bytes = self.bytes.unwrap_or(bytes);
+    }
+
+    /// Serialize the given data structure as EnCom into the IO stream.
+    ///
+    /// # Errors
+    ///
+    /// Serialization can fail if `T`'s implementation of `Serialize` decides
+    /// to fail, or if `T` contains a map with non-string keys.
+    pub fn to_writer<W, T>(&self, writer: W, value: &T) -> Result<()>
+    where
+        W: io::Write,
+        T: ?Sized + Serialize,
+    {
+        let mut ser = Serializer::new(writer);
+        self.configure(&mut ser, BytesMode::Raw);
+        value.serialize(InitSerializer { ser: &mut ser })
+    }
+
+    /// Serialize the given data structure as pretty-printed EnCom into the IO
+    /// stream.
+    ///
+    /// # Errors
+    ///
+    /// Serialization can fail if `T`'s implementation of `Serialize` decides
+    /// to fail, or if `T` contains a map with non-string keys.
+    pub fn to_writer_pretty<W, T>(&self, writer: W, value: &T) -> Result<()>
+    where
+        W: io::Write,
+        T: ?Sized + Serialize,
+    {
+        let mut ser = Serializer::pretty(writer);
+        self.configure(&mut ser, BytesMode::Raw);
+        value.serialize(InitSerializer { ser: &mut ser })
+    }
+
+    /// Serialize the given data structure as EnCom into a byte slice, and
+    /// return the number of bytes written.
+    ///
+    /// # Errors
+    ///
+    /// The same as [`to_slice`](crate::to_slice).
+    pub fn to_slice<T>(&self, buf: &mut [u8], value: &T) -> Result<usize>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut writer = SliceWriter::new(buf);
+        let result = self.to_writer(&mut writer, value);
+        writer.finish(result)
+    }
+
+    /// Serialize the given data structure as an EnCom byte vector.
+    ///
+    /// # Errors
+    ///
+    /// Serialization can fail if `T`'s implementation of `Serialize` decides
+    /// to fail, or if `T` contains a map with non-string keys.
+    #[cfg(feature = "alloc")]
+    pub fn to_vec<T>(&self, value: &T) -> Result<Vec<u8>>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut writer = Vec::with_capacity(128);
+        self.to_writer(&mut writer, value)?;
+        Ok(writer)
+    }
+
+    /// Serialize the given data structure as a String of EnCom.
+    ///
+    /// Bytes are written as base64, unless another [`bytes`](Options::bytes)
+    /// mode is set.
+    ///
+    /// # Errors
+    ///
+    /// Serialization can fail if `T`'s implementation of `Serialize` decides
+    /// to fail, if `T` contains a map with non-string keys, or if raw bytes
+    /// are not valid UTF-8.
+    #[cfg(feature = "alloc")]
+    pub fn to_string<T>(&self, value: &T) -> Result<String>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut vec = Vec::with_capacity(128);
+        let mut ser = Serializer::new(&mut vec);
+        self.configure(&mut ser, BytesMode::Base64);
+        value.serialize(InitSerializer { ser: &mut ser })?;
+        self.string_from_vec(vec)
+    }
+
+    /// Serialize the given data structure as a pretty-printed String of
+    /// EnCom.
+    ///
+    /// Bytes are written as base64, unless another [`bytes`](Options::bytes)
+    /// mode is set.
+    ///
+    /// # Errors
+    ///
+    /// Serialization can fail if `T`'s implementation of `Serialize` decides
+    /// to fail, if `T` contains a map with non-string keys, or if raw bytes
+    /// are not valid UTF-8.
+    #[cfg(feature = "alloc")]
+    pub fn to_string_pretty<T>(&self, value: &T) -> Result<String>
+    where
+        T: ?Sized + Serialize,
+    {
+        let mut vec = Vec::with_capacity(128);
+        let mut ser = Serializer::pretty(&mut vec);
+        self.configure(&mut ser, BytesMode::Base64);
+        value.serialize(InitSerializer { ser: &mut ser })?;
+        self.string_from_vec(vec)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn string_from_vec(&self, vec: Vec<u8>) -> Result<String> {
+        if self.bytes == Some(BytesMode::Raw) {
+            return String::from_utf8(vec).map_err(|_| Error::raw_bytes_not_utf8());
+        }
+        let string = unsafe {
+            // We do not emit invalid UTF-8, and bytes are encoded.
+            String::from_utf8_unchecked(vec)
+        };
+        Ok(string)
+    }
+
+    /// Deserialize an instance of type `T` from bytes of EnCom text.
+    ///
+    /// # Errors
+    ///
+    /// This conversion can fail if the structure of the input does not match
+    /// the structure expected by `T`, or if it was written with different
+    /// options.
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::wrong_self_convention)]
+    pub fn from_slice<'a, T>(&self, v: &'a [u8]) -> Result<T>
+    where
+        T: de::Deserialize<'a>,
+    {
+        self.from_trait(SliceRead::new(v))
+    }
+
+    /// Deserialize an instance of type `T` from a string of EnCom text.
+    ///
+    /// # Errors
+    ///
+    /// This conversion can fail if the structure of the input does not match
+    /// the structure expected by `T`, or if it was written with different
+    /// options.
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::should_implement_trait, clippy::wrong_self_convention)]
+    pub fn from_str<'a, T>(&self, s: &'a str) -> Result<T>
+    where
+        T: de::Deserialize<'a>,
+    {
+        self.from_trait(StrRead::new(s))
+    }
+
+    #[cfg(feature = "alloc")]
+    #[allow(clippy::wrong_self_convention)]
+    fn from_trait<'de, R, T>(&self, read: R) -> Result<T>
+    where
+        R: Read<'de>,
+        T: de::Deserialize<'de>,
+    {
+        let mut de = Deserializer::new(read);
+        de.variants_by_index(self.variant_index);
+        de.fields_by_index(self.field_index);
+        let value = de::Deserialize::deserialize(InitDeserializer { des: &mut de })?;
+
+        // Make sure the whole stream has been consumed.
+        de.end()?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,213 @@
+//! Parallel deserialization of large top-level arrays.
+//!
+//! A document that is a long top-level sequence, `{..} {..} {..}`, can be
+//! read into a `Vec<T>` one element after another by [`from_slice`], or with
+//! [`from_slice_vec`] here, which finds where each element starts and ends
+//! first and then deserializes them on the [rayon](::rayon) thread pool.
+//! Finding the elements only looks at their delimiters and jumps over
+//! strings and bytes by their length prefix, so it takes a fraction of the
+//! time of deserializing them.
+//!
+//! The elements keep their order, and an error is the same one, at the same
+//! line and column, that [`from_slice`] returns for the input.
+//!
+//! [`from_slice`]: crate::from_slice
+
+use crate::des::{Deserializer, SliceRead};
+use crate::error::{ErrorCode, Result};
+use core::ops::Range;
+use rayon::prelude::*;
+use serde::de::{self, Deserialize as _};
+use std::vec::Vec;
+
+/// Deserialize the elements of a top-level EnCom array in parallel.
+///
+/// This returns the same as `serde_encom::from_slice::<Vec<T>>(v)`, and is
+/// faster for inputs with many elements when there are cores to spare.
+///
+/// The array can also be written in brackets, `[{..} {..}]`. Its elements
+/// are then read as if they were written without them, unless they aren't
+/// `T`s: `[1 2]` of `Vec<Vec<u8>>` is a single element, as for `from_slice`.
+///
+/// # Example
+///
+/// ```
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize, Debug, PartialEq)]
+/// struct Record<'a> {
+///     id: u64,
+///     name: &'a str,
+/// }
+///
+/// let data = b"{id:1 name:5=first} {id:2 name:6=second}";
+/// let records: Vec<Record> = serde_encom::par::from_slice_vec(data).unwrap();
+/// assert_eq!(records[1], Record { id: 2, name: "second" });
+///
+/// let data = b"[{id:1 name:5=first} {id:2 name:6=second}]";
+/// let bracketed: Vec<Record> = serde_encom::par::from_slice_vec(data).unwrap();
+/// assert_eq!(bracketed, records);
+///
+/// let data = b"{id:1 name:5=first}\n{id:x name:6=second}";
+/// let err = serde_encom::par::from_slice_vec::<Record>(data).unwrap_err();
+/// assert_eq!((err.line(), err.column()), (2, 5));
+/// ```
+///
+/// # Errors
+///
+/// This conversion can fail if the input is not an array of elements of
+/// EnCom that `T` expects, in the same way as [`from_slice`]. If several
+/// elements fail, the error is that of the first of them.
+///
+/// [`from_slice`]: crate::from_slice
+pub fn from_slice_vec<'a, T>(v: &'a [u8]) -> Result<Vec<T>>
+where
+    T: de::Deserialize<'a> + Send,
+{
+    match bracketed(v) {
+        Brackets::None => elements_in(v, 0..v.len()),
+        Brackets::Around(inner) => match elements_in(v, inner) {
+            // `[1 2]` of `Vec<Vec<u8>>` is a single element.
+            Err(err) => elements_in(v, 0..v.len()).map_err(|_| err),
+            Ok(values) => Ok(values),
+        },
+        // Of an array that fails in brackets, or an element that does, the
+        // error is that of the one that fails later.
+        Brackets::Invalid => elements_in(v, 0..v.len()).map_err(|err| {
+            let mut de = Deserializer::from_slice(v);
+            match Vec::<T>::deserialize(&mut de) {
+                Err(inner) => {
+                    let inner = de.fix_position(inner);
+                    if (inner.line(), inner.column()) > (err.line(), err.column()) {
+                        inner
+                    } else {
+                        err
+                    }
+                }
+                Ok(_) => err,
+            }
+        }),
+    }
+}
+
+/// Whether the top-level array is written in brackets.
+enum Brackets {
+    None,
+    /// The bytes between the brackets.
+    Around(Range<usize>),
+    /// `v` starts with a bracket that isn't closed, or an array in it that
+    /// isn't valid EnCom.
+    Invalid,
+}
+
+fn bracketed(v: &[u8]) -> Brackets {
+    let mut de = Deserializer::from_slice(v);
+    let open = match de.parse_whitespace() {
+        Ok(Some(b'[')) => de.read.index,
+        _ => return Brackets::None,
+    };
+    if de.ignore_value().is_err() {
+        return Brackets::Invalid;
+    }
+    let close = de.read.index - 1;
+    match de.parse_whitespace() {
+        Ok(None) => Brackets::Around(open + 1..close),
+        _ => Brackets::None,
+    }
+}
+
+/// Deserializes the elements in `range` of `v` in parallel.
+fn elements_in<'a, T>(v: &'a [u8], range: Range<usize>) -> Result<Vec<T>>
+where
+    T: de::Deserialize<'a> + Send,
+{
+    let end = range.end;
+    let (elements, scan_error) = find_elements(v, range);
+
+    let parsed: Vec<Result<Option<T>>> = elements
+        .par_iter()
+        .map(|element| deserialize_element(v, element))
+        .collect();
+
+    let mut values = Vec::with_capacity(elements.len());
+    for (value, element) in parsed.into_iter().zip(&elements) {
+        match value? {
+            Some(value) => values.push(value),
+            // The element didn't end where the scan found its end, so the
+            // rest is read in order.
+            None => return deserialize_rest(v, element.start..end, values),
+        }
+    }
+
+    match scan_error {
+        // Deserializing the element that the scan failed on gives the error
+        // that `from_slice` would, whether or not it is the scan's.
+        Some(start) => deserialize_rest(v, start..end, values),
+        None => Ok(values),
+    }
+}
+
+/// The byte ranges of the elements in `range` of `v`, and the start of the
+/// element after them if it couldn't be skipped.
+fn find_elements(v: &[u8], range: Range<usize>) -> (Vec<Range<usize>>, Option<usize>) {
+    let mut de = deserializer_at(v, range.start);
+    let mut elements = Vec::new();
+    loop {
+        match de.parse_whitespace() {
+            Ok(Some(_)) if de.read.index == range.end => return (elements, None),
+            Ok(Some(b']')) | Err(_) => break,
+            Ok(Some(_)) => {}
+            Ok(None) => return (elements, None),
+        }
+        let start = de.read.index;
+        if de.ignore_value().is_err() {
+            return (elements, Some(start));
+        }
+        elements.push(start..de.read.index);
+    }
+    let start = de.read.index;
+    (elements, Some(start))
+}
+
+/// Deserializes the element at `element`, or returns `None` if `T` reads
+/// past its end or stops before it.
+fn deserialize_element<'a, T>(v: &'a [u8], element: &Range<usize>) -> Result<Option<T>>
+where
+    T: de::Deserialize<'a>,
+{
+    let mut de = deserializer_at(v, element.start);
+    let value = T::deserialize(&mut de).map_err(|err| de.fix_position(err))?;
+    Ok(if de.read.index == element.end {
+        Some(value)
+    } else {
+        None
+    })
+}
+
+/// Deserializes the elements in `range` of `v` one by one, as `from_slice`
+/// does, after `values`.
+fn deserialize_rest<'a, T>(v: &'a [u8], range: Range<usize>, mut values: Vec<T>) -> Result<Vec<T>>
+where
+    T: de::Deserialize<'a>,
+{
+    let mut de = deserializer_at(v, range.start);
+    loop {
+        match de.parse_whitespace()? {
+            Some(_) if de.read.index == range.end => return Ok(values),
+            Some(b']') => return Err(de.peek_error(ErrorCode::TrailingComma)),
+            Some(_) => {
+                let value = T::deserialize(&mut de).map_err(|err| de.fix_position(err))?;
+                values.push(value);
+            }
+            None => return Ok(values),
+        }
+    }
+}
+
+/// A deserializer of the whole of `v` that starts at `start`, so that its
+/// errors have positions in `v`.
+fn deserializer_at(v: &[u8], start: usize) -> Deserializer<SliceRead<'_>> {
+    let mut read = SliceRead::new(v);
+    read.index = start;
+    Deserializer::new(read)
+}
@@ -0,0 +1,516 @@
+//! Conversion of JSON Schemas, such as those generated by `schemars`, into
+//! EnCom schemas.
+//!
+//! JSON Schema describes JSON documents, so a few things differ for EnCom:
+//! byte strings have no JSON type and are marked with the [`BYTES_KEYWORD`]
+//! extension, and string lengths count characters in JSON Schema but bytes
+//! here. A top-level map is written without braces in EnCom, which is how a
+//! root `object` schema is read by [`Schema::validate_slice`].
+
+use super::{ArraySchema, Bounds, Integer, ObjectSchema, Schema};
+use crate::error::Error;
+use crate::value::{push_token, Value};
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use schemars::{json_schema, JsonSchema, SchemaGenerator};
+use serde::de::Error as _;
+use serde_json::{Map, Value as Json};
+
+/// The keyword that marks a JSON Schema as EnCom bytes, with the value
+/// `"bytes"`.
+///
+/// JSON has no byte strings, so a `serde_bytes` field is described by
+/// `schemars` as an array of numbers. Annotate it with
+/// `#[schemars(with = "serde_encom::schema::Bytes")]` to mark it instead.
+pub const BYTES_KEYWORD: &str = "x-encom-type";
+
+/// Stand-in type that describes a `serde_bytes` field.
+///
+/// Its JSON Schema is still an array of numbers for JSON consumers, but with
+/// the [`BYTES_KEYWORD`] extension it converts to EnCom bytes.
+///
+/// # Examples
+///
+/// ```
+/// use schemars::JsonSchema;
+/// use serde::{Deserialize, Serialize};
+/// use serde_encom::schema::Schema;
+///
+/// #[derive(Serialize, Deserialize, JsonSchema)]
+/// struct Blob {
+///     #[serde(with = "serde_bytes")]
+///     #[schemars(with = "serde_encom::schema::Bytes")]
+///     data: Vec<u8>,
+/// }
+///
+/// let schema = Schema::for_type::<Blob>().unwrap();
+/// let data = &schema.object.as_ref().unwrap().fields["data"];
+/// assert_eq!(data.types().collect::<Vec<_>>(), ["bytes"]);
+///
+/// let blob = Blob { data: vec![1, 2, 3] };
+/// let encom = serde_encom::to_vec(&blob).unwrap();
+/// assert!(schema.validate_slice(&encom).is_ok());
+/// ```
+pub struct Bytes;
+
+impl JsonSchema for Bytes {
+    fn inline_schema() -> bool {
+        true
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        "Bytes".into()
+    }
+
+    fn json_schema(_: &mut SchemaGenerator) -> schemars::Schema {
+        json_schema!({
+            "type": "array",
+            "items": {
+                "type": "integer",
+                "minimum": 0,
+                "maximum": 255,
+            },
+            BYTES_KEYWORD: "bytes",
+        })
+    }
+}
+
+impl Schema {
+    /// Builds the schema of the EnCom documents that `T` serializes to, from
+    /// its `schemars` JSON Schema.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use schemars::JsonSchema;
+    /// use serde::Serialize;
+    /// use serde_encom::schema::Schema;
+    ///
+    /// #[derive(Serialize, JsonSchema)]
+    /// struct User {
+    ///     id: u8,
+    ///     name: String,
+    ///     nick: Option<String>,
+    /// }
+    ///
+    /// let schema = Schema::for_type::<User>().unwrap();
+    /// assert!(schema.validate_slice(b"id:7 name:3=Ann nick:n").is_ok());
+    ///
+    /// let errors = schema.validate_slice(b"id:300").unwrap_err();
+    /// assert_eq!(errors[0].to_string(), "300 is greater than the maximum 255 at `/id`");
+    /// assert_eq!(errors[1].to_string(), "missing required key at `/name`");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails if the generated schema uses something that can't be converted,
+    /// see [`from_json_schema`](Schema::from_json_schema).
+    pub fn for_type<T: JsonSchema>() -> Result<Schema, Error> {
+        Schema::from_json_schema(&schemars::schema_for!(T))
+    }
+
+    /// Converts a JSON Schema into an EnCom schema.
+    ///
+    /// The types, numeric ranges, lengths, `enum` and `const` values, array
+    /// `items` and `prefixItems`, and map `properties` and `required` keys
+    /// are converted. A union (`anyOf`, `oneOf` or a list of types) becomes
+    /// a single schema that allows everything its branches allow, and
+    /// `allOf` merges the maps of its branches. Local `$ref`s are followed,
+    /// except back into a schema that is being converted: a recursive
+    /// position allows any value. Annotations and other keywords are
+    /// ignored.
+    ///
+    /// # Errors
+    ///
+    /// Fails on a `false` schema, an unknown type name, and a `$ref` that
+    /// isn't a pointer into the same document.
+    pub fn from_json_schema(schema: &schemars::Schema) -> Result<Schema, Error> {
+        let root = schema.as_value();
+        let mut converter = Converter {
+            root,
+            refs: Vec::new(),
+            path: String::new(),
+        };
+        converter.convert(root).map_err(Error::custom)
+    }
+}
+
+struct Converter<'a> {
+    root: &'a Json,
+    /// `$ref`s being converted, to cut recursion.
+    refs: Vec<String>,
+    /// Location in the JSON Schema document, as a JSON Pointer.
+    path: String,
+}
+
+impl<'a> Converter<'a> {
+    fn convert(&mut self, schema: &Json) -> Result<Schema, String> {
+        let map = match schema {
+            Json::Bool(true) => return Ok(Schema::default()),
+            Json::Object(map) => map,
+            _ => return Err(format!("expected a schema at `{}`", self.path)),
+        };
+
+        if let Some(reference) = map.get("$ref") {
+            return self.reference(reference);
+        }
+        if let Some(Json::Array(branches)) = map.get("allOf") {
+            let merged = self.merge_all(map, branches)?;
+            return self.convert_map(&merged);
+        }
+        for keyword in ["anyOf", "oneOf"] {
+            if let Some(Json::Array(branches)) = map.get(keyword) {
+                return self.union(keyword, branches);
+            }
+        }
+        self.convert_map(map)
+    }
+
+    fn child(&mut self, keys: &[&str], schema: &Json) -> Result<Schema, String> {
+        let len = self.path.len();
+        for key in keys {
+            push_token(&mut self.path, key);
+        }
+        let schema = self.convert(schema)?;
+        self.path.truncate(len);
+        Ok(schema)
+    }
+
+    fn resolve<'r>(&self, reference: &'r Json) -> Result<(&'r str, &'a Json), String> {
+        reference
+            .as_str()
+            .and_then(|reference| {
+                let pointer = reference.strip_prefix('#')?;
+                Some((reference, self.root.pointer(pointer)?))
+            })
+            .ok_or_else(|| format!("unresolvable `$ref` at `{}`", self.path))
+    }
+
+    fn reference(&mut self, reference: &Json) -> Result<Schema, String> {
+        let (name, target) = self.resolve(reference)?;
+        if self.refs.iter().any(|r| r == name) {
+            return Ok(Schema::default());
+        }
+        self.refs.push(name.to_string());
+        let path = core::mem::replace(&mut self.path, name[1..].to_string());
+        let schema = self.convert(target);
+        self.path = path;
+        self.refs.pop();
+        schema
+    }
+
+    fn union(&mut self, keyword: &str, branches: &[Json]) -> Result<Schema, String> {
+        let mut union: Option<Schema> = None;
+        for (i, branch) in branches.iter().enumerate() {
+            let schema = self.child(&[keyword, itoa::Buffer::new().format(i)], branch)?;
+            union = Some(match union {
+                Some(union) => widen(union, schema),
+                None => schema,
+            });
+        }
+        Ok(union.unwrap_or_default())
+    }
+
+    /// Merges the maps of the `allOf` branches into the schema that has them.
+    fn merge_all(
+        &mut self,
+        map: &Map<String, Json>,
+        branches: &[Json],
+    ) -> Result<Map<String, Json>, String> {
+        let mut merged = map.clone();
+        merged.remove("allOf");
+        for branch in branches {
+            let mut branch = branch;
+            while let Some(reference) = branch.get("$ref") {
+                branch = self.resolve(reference)?.1;
+            }
+            let Some(branch) = branch.as_object() else {
+                continue;
+            };
+            for (key, value) in branch {
+                match (key.as_str(), merged.get_mut(key)) {
+                    ("properties", Some(Json::Object(properties))) => {
+                        if let Json::Object(more) = value {
+                            properties.extend(more.clone());
+                        }
+                    }
+                    ("required", Some(Json::Array(required))) => {
+                        if let Json::Array(more) = value {
+                            required.extend(more.iter().cloned());
+                        }
+                    }
+                    _ => {
+                        merged.insert(key.clone(), value.clone());
+                    }
+                }
+            }
+        }
+        Ok(merged)
+    }
+
+    fn convert_map(&mut self, map: &Map<String, Json>) -> Result<Schema, String> {
+        let mut schema = Schema::default();
+
+        if let Some(value) = map.get("const") {
+            schema.values = Some(Vec::from([Value::from(value)]));
+        }
+        if let Some(Json::Array(values)) = map.get("enum") {
+            schema.values = Some(values.iter().map(Value::from).collect());
+        }
+
+        if map.get(BYTES_KEYWORD).and_then(Json::as_str) == Some("bytes") {
+            schema.bytes = Some(lengths(map, "minItems", "maxItems", 1));
+            return Ok(schema);
+        }
+
+        let types: Vec<&str> = match map.get("type") {
+            Some(Json::String(name)) => Vec::from([name.as_str()]),
+            Some(Json::Array(names)) => names.iter().filter_map(Json::as_str).collect(),
+            Some(_) => return Err(format!("expected type names at `{}/type`", self.path)),
+            None => implied_types(map),
+        };
+
+        for name in types {
+            match name {
+                "null" => schema.null = true,
+                "boolean" => schema.bool = true,
+                "integer" => schema.int = Some(int_bounds(map)),
+                "number" => {
+                    schema.int = Some(int_bounds(map));
+                    schema.float = Some(float_bounds(map));
+                }
+                // A character is at most 4 bytes long.
+                "string" => schema.string = Some(lengths(map, "minLength", "maxLength", 4)),
+                "array" => schema.array = Some(self.array(map)?),
+                "object" => schema.object = Some(self.object(map)?),
+                _ => return Err(format!("unknown type `{}` at `{}/type`", name, self.path)),
+            }
+        }
+
+        // Values without a type still need their types allowed in a union.
+        if let Some(values) = &schema.values {
+            if schema.types().next().is_none() {
+                let mut observed = Schema::default();
+                for value in values {
+                    observed.observe(value);
+                }
+                observed.count = 0;
+                observed.values = schema.values;
+                schema = observed;
+            }
+        }
+
+        Ok(schema)
+    }
+
+    fn array(&mut self, map: &Map<String, Json>) -> Result<ArraySchema, String> {
+        let len = lengths(map, "minItems", "maxItems", 1);
+        let mut items: Option<Schema> = None;
+        let mut prefix_len = 0;
+        if let Some(Json::Array(prefix)) = map.get("prefixItems") {
+            prefix_len = prefix.len();
+            for (i, item) in prefix.iter().enumerate() {
+                let item = self.child(&["prefixItems", itoa::Buffer::new().format(i)], item)?;
+                items = Some(match items {
+                    Some(items) => widen(items, item),
+                    None => item,
+                });
+            }
+        }
+        match map.get("items") {
+            // Only the `prefixItems` are allowed.
+            Some(Json::Bool(false)) => {}
+            Some(item) => {
+                let item = self.child(&["items"], item)?;
+                items = Some(match items {
+                    Some(items) => widen(items, item),
+                    None => item,
+                });
+            }
+            // Items after the `prefixItems` can be anything.
+            None if items.is_some() && len.max > prefix_len => items = Some(Schema::default()),
+            None => {}
+        }
+        Ok(ArraySchema {
+            len,
+            items: Box::new(items.unwrap_or_default()),
+        })
+    }
+
+    fn object(&mut self, map: &Map<String, Json>) -> Result<ObjectSchema, String> {
+        let mut object = ObjectSchema::default();
+        if let Some(Json::Object(properties)) = map.get("properties") {
+            for (key, property) in properties {
+                let field = self.child(&["properties", key], property)?;
+                object.fields.insert(key.clone(), field);
+            }
+        }
+        if let Some(Json::Array(required)) = map.get("required") {
+            for key in required.iter().filter_map(Json::as_str) {
+                object.fields.entry(key.to_string()).or_default();
+                object.required.insert(key.to_string());
+            }
+        }
+        Ok(object)
+    }
+}
+
+/// The types a schema without `type` applies its keywords to.
+fn implied_types(map: &Map<String, Json>) -> Vec<&'static str> {
+    let mut types = Vec::new();
+    let has = |keys: &[&str]| keys.iter().any(|key| map.contains_key(*key));
+    if has(&["minimum", "maximum", "exclusiveMinimum", "exclusiveMaximum"]) {
+        types.push("number");
+    }
+    if has(&["minLength", "maxLength"]) {
+        types.push("string");
+    }
+    if has(&["items", "prefixItems", "minItems", "maxItems"]) {
+        types.push("array");
+    }
+    if has(&["properties", "required"]) {
+        types.push("object");
+    }
+    types
+}
+
+fn int_bounds(map: &Map<String, Json>) -> Bounds<Integer> {
+    let (format_min, format_max) = match map.get("format").and_then(Json::as_str) {
+        Some("int8") => (i8::MIN.into(), i8::MAX.into()),
+        Some("int16") => (i16::MIN.into(), i16::MAX.into()),
+        Some("int32") => (i32::MIN.into(), i32::MAX.into()),
+        Some("int64") => (i64::MIN.into(), i64::MAX.into()),
+        Some("uint8") => (0.into(), u8::MAX.into()),
+        Some("uint16") => (0.into(), u16::MAX.into()),
+        Some("uint32") => (0.into(), u32::MAX.into()),
+        Some("uint64") => (0.into(), u64::MAX.into()),
+        _ => (Integer::MIN, Integer::MAX),
+    };
+    let bound = |key: &str, round: fn(f64) -> f64| {
+        let n = map.get(key)?.as_number()?;
+        n.as_i64()
+            .map(i128::from)
+            .or_else(|| n.as_u64().map(i128::from))
+            .or_else(|| n.as_f64().map(|f| round(f) as i128))
+    };
+    let min = match (
+        bound("minimum", f64::ceil),
+        bound("exclusiveMinimum", f64::floor),
+    ) {
+        (Some(min), Some(exclusive)) => Some(min.max(exclusive + 1)),
+        (Some(min), None) => Some(min),
+        (None, Some(exclusive)) => Some(exclusive + 1),
+        (None, None) => None,
+    };
+    let max = match (
+        bound("maximum", f64::floor),
+        bound("exclusiveMaximum", f64::ceil),
+    ) {
+        (Some(max), Some(exclusive)) => Some(max.min(exclusive - 1)),
+        (Some(max), None) => Some(max),
+        (None, Some(exclusive)) => Some(exclusive - 1),
+        (None, None) => None,
+    };
+    Bounds {
+        min: min.map_or(Integer::MIN, Integer::from).max(format_min),
+        max: max.map_or(Integer::MAX, Integer::from).min(format_max),
+    }
+}
+
+/// Exclusive bounds are taken as inclusive.
+fn float_bounds(map: &Map<String, Json>) -> Bounds<f64> {
+    let bound = |keys: [&str; 2], pick: fn(f64, f64) -> f64| {
+        keys.iter()
+            .filter_map(|key| map.get(*key)?.as_f64())
+            .reduce(pick)
+    };
+    Bounds {
+        min: bound(["minimum", "exclusiveMinimum"], f64::max).unwrap_or(f64::NEG_INFINITY),
+        max: bound(["maximum", "exclusiveMaximum"], f64::min).unwrap_or(f64::INFINITY),
+    }
+}
+
+/// Reads a length range, scaling the maximum by the size of a unit.
+fn lengths(map: &Map<String, Json>, min: &str, max: &str, unit: usize) -> Bounds<usize> {
+    let len = |key: &str| {
+        let len = map.get(key)?.as_u64()?;
+        Some(usize::try_from(len).unwrap_or(usize::MAX))
+    };
+    Bounds {
+        min: len(min).unwrap_or(0),
+        max: len(max).map_or(usize::MAX, |max| max.saturating_mul(unit)),
+    }
+}
+
+/// A schema that allows everything either schema allows.
+fn widen(a: Schema, b: Schema) -> Schema {
+    let any = |schema: &Schema| schema.values.is_none() && schema.types().next().is_none();
+    if any(&a) || any(&b) {
+        return Schema::default();
+    }
+    Schema {
+        count: a.count + b.count,
+        null: a.null || b.null,
+        bool: a.bool || b.bool,
+        int: either(a.int, b.int, |a, b| Bounds {
+            min: a.min.min(b.min),
+            max: a.max.max(b.max),
+        }),
+        float: either(a.float, b.float, |a, b| Bounds {
+            min: a.min.min(b.min),
+            max: a.max.max(b.max),
+        }),
+        string: either(a.string, b.string, widen_len),
+        bytes: either(a.bytes, b.bytes, widen_len),
+        datetime: either(a.datetime, b.datetime, |a, b| Bounds {
+            min: a.min.min(b.min),
+            max: a.max.max(b.max),
+        }),
+        array: either(a.array, b.array, |a, b| ArraySchema {
+            len: widen_len(a.len, b.len),
+            items: Box::new(widen(*a.items, *b.items)),
+        }),
+        object: either(a.object, b.object, |mut a, b| {
+            a.required.retain(|key| b.required.contains(key));
+            for (key, field) in b.fields {
+                let field = match a.fields.remove(&key) {
+                    Some(other) => widen(other, field),
+                    None => field,
+                };
+                a.fields.insert(key, field);
+            }
+            a.count += b.count;
+            a
+        }),
+        values: match (a.values, b.values) {
+            (Some(mut a), Some(b)) => {
+                for value in b {
+                    if !a.contains(&value) {
+                        a.push(value);
+                    }
+                }
+                Some(a)
+            }
+            _ => None,
+        },
+    }
+}
+
+fn widen_len(a: Bounds<usize>, b: Bounds<usize>) -> Bounds<usize> {
+    Bounds {
+        min: a.min.min(b.min),
+        max: a.max.max(b.max),
+    }
+}
+
+fn either<T>(a: Option<T>, b: Option<T>, both: impl FnOnce(T, T) -> T) -> Option<T> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(both(a, b)),
+        (a, b) => a.or(b),
+    }
+}
@@ -0,0 +1,398 @@
+//! Schemas inferred from sample documents.
+//!
+//! [`infer`] walks a set of [`Value`]s and records, for every position in
+//! them, which types were seen there and in what ranges. The resulting
+//! [`Schema`] is a union of everything that was observed: a field that was
+//! a string in one sample and a number in another has both types.
+//!
+//! A schema renders as a human-readable EnCom document through its
+//! `Display` impl, and as Rust source code through [`Schema::to_rust`].
+//!
+//! A schema can also be declared, by deserializing it from the same EnCom
+//! format, and used to [validate](Schema::validate) documents before
+//! deserializing them. The ranges then are limits instead of observations.
+//! With the `schemars` feature, a schema can be declared by a Rust type
+//! through [`Schema::for_type`], or converted from any JSON Schema.
+
+#[cfg(feature = "schemars")]
+mod json_schema;
+mod parse;
+mod render;
+mod validate;
+
+#[cfg(feature = "schemars")]
+#[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+pub use self::json_schema::{Bytes, BYTES_KEYWORD};
+pub use self::validate::ValidationError;
+use crate::datetime::DateTime;
+use crate::value::{Number, Value};
+use alloc::{
+    boxed::Box,
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+use core::fmt::{self, Display};
+
+/// Infers the schema of a set of sample values.
+///
+/// # Examples
+///
+/// ```
+/// use serde_encom::schema;
+/// use serde_encom::Value;
+///
+/// let samples: Vec<Value> = vec![
+///     serde_encom::from_str("id:1 name:3=Ann").unwrap(),
+///     serde_encom::from_str("id:2 name:3=Bob nick:2=bo").unwrap(),
+/// ];
+///
+/// let schema = schema::infer(&samples);
+/// let object = schema.object.as_ref().unwrap();
+/// assert_eq!(object.count, 2);
+///
+/// let id = &object.fields["id"];
+/// assert_eq!(id.int.map(|b| (b.min, b.max)), Some((1.into(), 2.into())));
+/// assert!(!object.is_optional("id"));
+/// assert!(object.is_optional("nick"));
+/// ```
+pub fn infer<'a, I>(values: I) -> Schema
+where
+    I: IntoIterator<Item = &'a Value>,
+{
+    let mut schema = Schema::default();
+    for value in values {
+        schema.observe(value);
+    }
+    schema
+}
+
+/// The observed shape of the values at one position of a document.
+///
+/// Every type that was seen has its own entry, so a schema is a union of
+/// types. A type that was never seen is `None` (or `false`).
+///
+/// A schema serializes, prints with `Display` and deserializes as an EnCom
+/// document. Every position has its `count` of observed values and its
+/// `type`, a string or an array of strings for a union of `null`, `bool`,
+/// `int`, `float`, `string`, `bytes`, `datetime`, `array` and `object`. A
+/// declared schema can also use `number` for `int` and `float`. Ranges are
+/// given per type: `int{min max}`, `float{min max}`, `datetime{min max}`
+/// with date-time bounds, and `string{min_len max_len}`,
+/// `bytes{...}` and `array{...}` for lengths, every bound being optional.
+/// Arrays describe their elements in `items`, maps their values in `fields`.
+/// A field that isn't required is marked `optional:t`. `enum[...]` lists
+/// the only allowed values. Without a `type`, the types are those that have
+/// ranges or `fields`, and a position without any allows every value.
+///
+/// # Examples
+///
+/// ```
+/// use serde_encom::{schema, Value};
+///
+/// let samples: Vec<Value> = vec![
+///     serde_encom::from_str("id:1 tags[1=a]").unwrap(),
+///     serde_encom::from_str("id:7").unwrap(),
+/// ];
+///
+/// let schema = schema::infer(&samples);
+/// assert_eq!(
+///     schema.to_string(),
+///     "\
+/// count:2
+/// type:6=object
+/// fields{
+///   id{
+///     count:2
+///     type:3=int
+///     int{
+///       min:1
+///       max:7
+///     }
+///   }
+///   tags{
+///     count:1
+///     optional:t
+///     type:5=array
+///     array{
+///       min_len:1
+///       max_len:1
+///       items{
+///         count:1
+///         type:6=string
+///         string{
+///           min_len:1
+///           max_len:1
+///         }
+///       }
+///     }
+///   }
+/// }"
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Schema {
+    /// Number of values observed.
+    pub count: u64,
+    /// Whether `null` was seen.
+    pub null: bool,
+    /// Whether `true` or `false` was seen.
+    pub bool: bool,
+    /// Range of the integers seen.
+    pub int: Option<Bounds<Integer>>,
+    /// Range of the floats seen.
+    pub float: Option<Bounds<f64>>,
+    /// Range of the lengths, in bytes, of the strings seen.
+    pub string: Option<Bounds<usize>>,
+    /// Range of the lengths of the byte strings seen.
+    pub bytes: Option<Bounds<usize>>,
+    /// Range of the date-times seen.
+    pub datetime: Option<Bounds<DateTime>>,
+    /// Shape of the arrays seen.
+    pub array: Option<ArraySchema>,
+    /// Shape of the maps seen.
+    pub object: Option<ObjectSchema>,
+    /// The only allowed values, if declared. Inferred schemas never have
+    /// them.
+    pub values: Option<Vec<Value>>,
+}
+
+/// An inclusive range of observed values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Bounds<T> {
+    /// The smallest value seen.
+    pub min: T,
+    /// The largest value seen.
+    pub max: T,
+}
+
+/// An integer bound, which can be any `i128` or `u128`.
+///
+/// Integers compare by value, whichever type they were made from.
+///
+/// ```
+/// use serde_encom::schema::Integer;
+///
+/// assert!(Integer::from(-1) < Integer::from(u128::MAX));
+/// assert_eq!(Integer::from(7u8), Integer::from(7i128));
+/// assert_eq!(Integer::MAX.as_i128(), None);
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Integer(IntegerRepr);
+
+/// Negative integers are always `Neg`, which makes the derived order the
+/// order of the values.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+enum IntegerRepr {
+    Neg(i128),
+    Pos(u128),
+}
+
+impl Integer {
+    /// The smallest integer, `i128::MIN`.
+    pub const MIN: Integer = Integer(IntegerRepr::Neg(i128::MIN));
+    /// The largest integer, `u128::MAX`.
+    pub const MAX: Integer = Integer(IntegerRepr::Pos(u128::MAX));
+
+    /// The integer as an `i128`, if it fits.
+    pub fn as_i128(self) -> Option<i128> {
+        match self.0 {
+            IntegerRepr::Neg(i) => Some(i),
+            IntegerRepr::Pos(u) => i128::try_from(u).ok(),
+        }
+    }
+
+    /// The integer as a `u128`, if it isn't negative.
+    pub fn as_u128(self) -> Option<u128> {
+        match self.0 {
+            IntegerRepr::Neg(_) => None,
+            IntegerRepr::Pos(u) => Some(u),
+        }
+    }
+
+    /// The integer as an `f64`, which may be rounded.
+    pub fn as_f64(self) -> f64 {
+        match self.0 {
+            IntegerRepr::Neg(i) => i as f64,
+            IntegerRepr::Pos(u) => u as f64,
+        }
+    }
+
+    fn of(n: &Number) -> Option<Integer> {
+        n.as_i128()
+            .map(Integer::from)
+            .or_else(|| n.as_u128().map(Integer::from))
+    }
+}
+
+impl From<i128> for Integer {
+    fn from(i: i128) -> Self {
+        match u128::try_from(i) {
+            Ok(u) => Integer(IntegerRepr::Pos(u)),
+            Err(_) => Integer(IntegerRepr::Neg(i)),
+        }
+    }
+}
+
+impl From<u128> for Integer {
+    fn from(u: u128) -> Self {
+        Integer(IntegerRepr::Pos(u))
+    }
+}
+
+macro_rules! from_integer {
+    ($wide:ident: $($ty:ident)*) => {
+        $(
+            impl From<$ty> for Integer {
+                fn from(n: $ty) -> Self {
+                    Integer::from(n as $wide)
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(i128: i8 i16 i32 i64 isize);
+from_integer!(u128: u8 u16 u32 u64 usize);
+
+impl fmt::Display for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            IntegerRepr::Neg(i) => Display::fmt(&i, f),
+            IntegerRepr::Pos(u) => Display::fmt(&u, f),
+        }
+    }
+}
+
+impl fmt::Debug for Integer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+/// The observed shape of arrays.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArraySchema {
+    /// Range of the array lengths.
+    pub len: Bounds<usize>,
+    /// Shape of the elements of all arrays together.
+    pub items: Box<Schema>,
+}
+
+/// The observed shape of maps.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ObjectSchema {
+    /// Number of maps observed.
+    pub count: u64,
+    /// Shape of the values of every key seen, in key order. The `count` of a
+    /// field is the number of maps it was present in.
+    pub fields: BTreeMap<String, Schema>,
+    /// Keys present in every map.
+    pub required: BTreeSet<String>,
+}
+
+impl Schema {
+    /// Adds a sample value to the schema.
+    pub fn observe(&mut self, value: &Value) {
+        self.count += 1;
+        match value {
+            Value::Null => self.null = true,
+            Value::Bool(_) => self.bool = true,
+            Value::Number(n) => self.observe_number(n),
+            Value::String(s) => widen(&mut self.string, s.len()),
+            Value::Bytes(b) => widen(&mut self.bytes, b.len()),
+            Value::DateTime(dt) => widen(&mut self.datetime, *dt),
+            Value::Array(list) => {
+                let array = self.array.get_or_insert_with(|| ArraySchema {
+                    len: Bounds {
+                        min: list.len(),
+                        max: list.len(),
+                    },
+                    items: Box::default(),
+                });
+                array.len.widen(list.len());
+                for item in list {
+                    array.items.observe(item);
+                }
+            }
+            Value::Object(map) => {
+                let object = self.object.get_or_insert_with(ObjectSchema::default);
+                object.count += 1;
+                if object.count == 1 {
+                    object.required = map.keys().cloned().collect();
+                } else {
+                    object.required.retain(|key| map.contains_key(key));
+                }
+                for (key, value) in map {
+                    match object.fields.get_mut(key) {
+                        Some(field) => field.observe(value),
+                        None => {
+                            let mut field = Schema::default();
+                            field.observe(value);
+                            object.fields.insert(key.clone(), field);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn observe_number(&mut self, n: &Number) {
+        if let Some(i) = Integer::of(n) {
+            widen(&mut self.int, i);
+        } else if let Some(f) = n.as_f64() {
+            match &mut self.float {
+                Some(bounds) => {
+                    bounds.min = bounds.min.min(f);
+                    bounds.max = bounds.max.max(f);
+                }
+                None => self.float = Some(Bounds { min: f, max: f }),
+            }
+        }
+    }
+
+    /// Names of the types seen, in a fixed order: `null`, `bool`, `int`,
+    /// `float`, `string`, `bytes`, `datetime`, `array` and `object`.
+    pub fn types(&self) -> impl Iterator<Item = &'static str> + '_ {
+        [
+            ("null", self.null),
+            ("bool", self.bool),
+            ("int", self.int.is_some()),
+            ("float", self.float.is_some()),
+            ("string", self.string.is_some()),
+            ("bytes", self.bytes.is_some()),
+            ("datetime", self.datetime.is_some()),
+            ("array", self.array.is_some()),
+            ("object", self.object.is_some()),
+        ]
+        .into_iter()
+        .filter(|&(_, seen)| seen)
+        .map(|(name, _)| name)
+    }
+}
+
+impl ObjectSchema {
+    /// Whether the field `key` was missing from some of the maps.
+    pub fn is_optional(&self, key: &str) -> bool {
+        !self.required.contains(key)
+    }
+}
+
+impl<T: Ord + Copy> Bounds<T> {
+    fn widen(&mut self, value: T) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+    }
+}
+
+fn widen<T: Ord + Copy>(bounds: &mut Option<Bounds<T>>, value: T) {
+    match bounds {
+        Some(bounds) => bounds.widen(value),
+        None => {
+            *bounds = Some(Bounds {
+                min: value,
+                max: value,
+            })
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use super::{ArraySchema, Bounds, Integer, ObjectSchema, Schema};
+use crate::datetime::DateTime;
+use crate::value::{push_token, Map, Value};
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::str::FromStr;
+use serde::de::{Deserialize, Deserializer, Error as _};
+
+impl<'de> Deserialize<'de> for Schema {
+    fn deserialize<D>(deserializer: D) -> Result<Schema, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let mut path = String::new();
+        let (schema, _) = node(&value, &mut path).map_err(D::Error::custom)?;
+        Ok(schema)
+    }
+}
+
+impl FromStr for Schema {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> Result<Schema, crate::Error> {
+        crate::from_str(s)
+    }
+}
+
+impl<T> Bounds<T> {
+    const fn new(min: T, max: T) -> Self {
+        Bounds { min, max }
+    }
+}
+
+const ANY_INT: Bounds<Integer> = Bounds::new(Integer::MIN, Integer::MAX);
+const ANY_FLOAT: Bounds<f64> = Bounds::new(f64::NEG_INFINITY, f64::INFINITY);
+const ANY_LEN: Bounds<usize> = Bounds::new(0, usize::MAX);
+const ANY_DATETIME: Bounds<DateTime> = Bounds::new(DateTime::MIN, DateTime::MAX);
+
+/// Parses the schema at `path`, and whether it is marked optional.
+fn node(value: &Value, path: &mut String) -> Result<(Schema, bool), String> {
+    let map = as_map(value, path)?;
+    let mut schema = Schema::default();
+    let mut optional = false;
+    let mut types = None;
+
+    for (key, value) in map {
+        let len = path.len();
+        push_token(path, key);
+        match key.as_str() {
+            "count" => schema.count = value.as_u64().ok_or_else(|| expected("a count", path))?,
+            "optional" => {
+                optional = value
+                    .as_bool()
+                    .ok_or_else(|| expected("`t` or `f`", path))?
+            }
+            "type" => types = Some(type_names(value, path)?),
+            "enum" => {
+                let values = value.as_array().ok_or_else(|| expected("an array", path))?;
+                schema.values = Some(values.clone());
+            }
+            "int" => schema.int = Some(int_bounds(value, path)?),
+            "float" => schema.float = Some(float_bounds(value, path)?),
+            "string" => schema.string = Some(len_bounds(value, path)?),
+            "bytes" => schema.bytes = Some(len_bounds(value, path)?),
+            "datetime" => schema.datetime = Some(datetime_bounds(value, path)?),
+            "array" => schema.array = Some(array(value, path)?),
+            "fields" => schema.object = Some(object(value, path)?),
+            _ => return Err(format!("unknown schema key at `{}`", path)),
+        }
+        path.truncate(len);
+    }
+
+    if let Some(types) = types {
+        let declared: Vec<&str> = schema.types().collect();
+        for name in declared {
+            let number = matches!(name, "int" | "float") && types.contains(&"number");
+            if !number && !types.contains(&name) {
+                return Err(format!("`{}` is not a listed type at `{}`", name, path));
+            }
+        }
+        for name in &types {
+            match *name {
+                "null" => schema.null = true,
+                "bool" => schema.bool = true,
+                "int" => {
+                    schema.int.get_or_insert(ANY_INT);
+                }
+                "float" => {
+                    schema.float.get_or_insert(ANY_FLOAT);
+                }
+                "number" => {
+                    schema.int.get_or_insert(ANY_INT);
+                    schema.float.get_or_insert(ANY_FLOAT);
+                }
+                "string" => {
+                    schema.string.get_or_insert(ANY_LEN);
+                }
+                "bytes" => {
+                    schema.bytes.get_or_insert(ANY_LEN);
+                }
+                "datetime" => {
+                    schema.datetime.get_or_insert(ANY_DATETIME);
+                }
+                "array" => {
+                    schema.array.get_or_insert_with(|| ArraySchema {
+                        len: ANY_LEN,
+                        items: Box::default(),
+                    });
+                }
+                "object" => {
+                    schema.object.get_or_insert_with(ObjectSchema::default);
+                }
+                _ => return Err(format!("unknown type `{}` at `{}/type`", name, path)),
+            }
+        }
+    }
+
+    // Only the number of values is printed; for a map that is the number of
+    // maps, unless it was a union with other types.
+    let count = schema.count;
+    if let Some(object) = &mut schema.object {
+        object.count = count;
+    }
+
+    Ok((schema, optional))
+}
+
+fn as_map<'a>(value: &'a Value, path: &str) -> Result<&'a Map<String, Value>, String> {
+    value.as_object().ok_or_else(|| expected("a map", path))
+}
+
+fn expected(what: &str, path: &str) -> String {
+    format!("expected {} at `{}`", what, path)
+}
+
+fn type_names<'a>(value: &'a Value, path: &str) -> Result<Vec<&'a str>, String> {
+    match value {
+        Value::String(name) => Ok(Vec::from([name.as_str()])),
+        Value::Array(names) => names
+            .iter()
+            .map(|name| name.as_str().ok_or_else(|| expected("type names", path)))
+            .collect(),
+        _ => Err(expected("a type name or an array of them", path)),
+    }
+}
+
+/// Reads the entries of a range map, rejecting unknown keys.
+fn bound_entries<'a>(
+    map: &'a Map<String, Value>,
+    keys: &[&str],
+    path: &str,
+) -> Result<Vec<Option<&'a Value>>, String> {
+    if let Some(key) = map.keys().find(|key| !keys.contains(&key.as_str())) {
+        let mut path = path.to_string();
+        push_token(&mut path, key);
+        return Err(format!("unknown schema key at `{}`", path));
+    }
+    Ok(keys.iter().map(|&key| map.get(key)).collect())
+}
+
+fn int_bounds(value: &Value, path: &str) -> Result<Bounds<Integer>, String> {
+    let entries = bound_entries(as_map(value, path)?, &["min", "max"], path)?;
+    let int = |value: Option<&Value>, default| match value {
+        None => Ok(default),
+        Some(value) => value
+            .as_number()
+            .and_then(Integer::of)
+            .ok_or_else(|| expected("integer bounds", path)),
+    };
+    Ok(Bounds {
+        min: int(entries[0], Integer::MIN)?,
+        max: int(entries[1], Integer::MAX)?,
+    })
+}
+
+fn float_bounds(value: &Value, path: &str) -> Result<Bounds<f64>, String> {
+    let entries = bound_entries(as_map(value, path)?, &["min", "max"], path)?;
+    let float = |value: Option<&Value>, default| match value {
+        None => Ok(default),
+        Some(value) => value
+            .as_f64()
+            .ok_or_else(|| expected("number bounds", path)),
+    };
+    Ok(Bounds {
+        min: float(entries[0], f64::NEG_INFINITY)?,
+        max: float(entries[1], f64::INFINITY)?,
+    })
+}
+
+fn datetime_bounds(value: &Value, path: &str) -> Result<Bounds<DateTime>, String> {
+    let entries = bound_entries(as_map(value, path)?, &["min", "max"], path)?;
+    let datetime = |value: Option<&Value>, default| match value {
+        None => Ok(default),
+        Some(value) => value
+            .as_datetime()
+            .ok_or_else(|| expected("date-time bounds", path)),
+    };
+    Ok(Bounds {
+        min: datetime(entries[0], DateTime::MIN)?,
+        max: datetime(entries[1], DateTime::MAX)?,
+    })
+}
+
+fn len_bounds(value: &Value, path: &str) -> Result<Bounds<usize>, String> {
+    let entries = bound_entries(as_map(value, path)?, &["min_len", "max_len"], path)?;
+    lengths(&entries, path)
+}
+
+fn lengths(entries: &[Option<&Value>], path: &str) -> Result<Bounds<usize>, String> {
+    let len = |value: Option<&Value>, default| match value {
+        None => Ok(default),
+        Some(value) => value
+            .as_u64()
+            .and_then(|len| usize::try_from(len).ok())
+            .ok_or_else(|| expected("length bounds", path)),
+    };
+    Ok(Bounds {
+        min: len(entries[0], 0)?,
+        max: len(entries[1], usize::MAX)?,
+    })
+}
+
+fn array(value: &Value, path: &mut String) -> Result<ArraySchema, String> {
+    let entries = bound_entries(as_map(value, path)?, &["min_len", "max_len", "items"], path)?;
+    let len = lengths(&entries, path)?;
+    let items = match entries[2] {
+        Some(items) => {
+            let len = path.len();
+            path.push_str("/items");
+            let (items, _) = node(items, path)?;
+            path.truncate(len);
+            items
+        }
+        None => Schema::default(),
+    };
+    Ok(ArraySchema {
+        len,
+        items: Box::new(items),
+    })
+}
+
+fn object(value: &Value, path: &mut String) -> Result<ObjectSchema, String> {
+    let mut object = ObjectSchema::default();
+    for (key, value) in as_map(value, path)? {
+        let len = path.len();
+        push_token(path, key);
+        let (field, optional) = node(value, path)?;
+        path.truncate(len);
+        if !optional {
+            object.required.insert(key.clone());
+        }
+        object.fields.insert(key.clone(), field);
+    }
+    Ok(object)
+}
@@ -0,0 +1,441 @@
+use super::{Bounds, Integer, IntegerRepr, ObjectSchema, Schema};
+use crate::datetime::DateTime;
+use alloc::{
+    borrow::ToOwned,
+    collections::BTreeSet,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display, Write as _};
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+impl Schema {
+    /// Generates Rust source code for types that deserialize the documents
+    /// the schema was inferred from.
+    ///
+    /// A map becomes a struct named `name`, and every map nested in it
+    /// becomes a struct named after its field. Other root values become a
+    /// `type` alias. Fields missing from some of the samples or that were
+    /// `null` become `Option`s. Positions where values of several types were
+    /// seen, or none at all, become `serde_encom::Value`. Byte strings become
+    /// `serde_bytes::ByteBuf`, and date-times `serde_encom::DateTime`.
+    ///
+    /// The code is a starting point: integer types are the widest that fit
+    /// the observed range, and struct names may need a better choice.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_encom::{schema, Value};
+    ///
+    /// let samples: Vec<Value> = vec![
+    ///     serde_encom::from_str("id:1 userName:3=Ann pos{x:1.5 y:-2}").unwrap(),
+    ///     serde_encom::from_str("id:2 userName:3=Bob pos{x:0 y:3} nick:n").unwrap(),
+    /// ];
+    ///
+    /// let schema = schema::infer(&samples);
+    /// assert_eq!(
+    ///     schema.to_rust("User"),
+    ///     "\
+    /// use serde::{Deserialize, Serialize};
+    ///
+    /// #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    /// pub struct User {
+    ///     pub id: u64,
+    ///     pub nick: Option<serde_encom::Value>,
+    ///     pub pos: Pos,
+    ///     #[serde(rename = \"userName\")]
+    ///     pub user_name: String,
+    /// }
+    ///
+    /// #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    /// pub struct Pos {
+    ///     pub x: f64,
+    ///     pub y: i64,
+    /// }
+    /// "
+    /// );
+    /// ```
+    pub fn to_rust(&self, name: &str) -> String {
+        let mut gen = RustGen {
+            names: BTreeSet::new(),
+            pending: Vec::new(),
+        };
+        let mut out = String::from("use serde::{Deserialize, Serialize};\n");
+
+        let root = pascal_case(name);
+        match &self.object {
+            Some(object) if self.types().all(|t| t == "object") => {
+                gen.names.insert(root.clone());
+                gen.pending.push((root, object));
+            }
+            _ => {
+                gen.names.insert(root.clone());
+                let ty = gen.rust_type(self, &root);
+                let _ = write!(out, "\npub type {} = {};\n", root, ty);
+            }
+        }
+
+        let mut next = 0;
+        while next < gen.pending.len() {
+            let (name, object) = gen.pending[next].clone();
+            next += 1;
+            gen.write_struct(&mut out, &name, object);
+        }
+        out
+    }
+}
+
+impl Display for Schema {
+    /// Prints the schema as a pretty EnCom document.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let s = crate::to_string_pretty(self).map_err(|_| fmt::Error)?;
+        f.write_str(&s)
+    }
+}
+
+impl Serialize for Schema {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        Node {
+            schema: self,
+            optional: false,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// A schema and whether it is the schema of an optional field.
+struct Node<'a> {
+    schema: &'a Schema,
+    optional: bool,
+}
+
+impl Serialize for Node<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let schema = self.schema;
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("count", &schema.count)?;
+        if self.optional {
+            map.serialize_entry("optional", &true)?;
+        }
+
+        let types: Vec<&str> = schema.types().collect();
+        match types.as_slice() {
+            [] => {}
+            [single] => map.serialize_entry("type", single)?,
+            _ => map.serialize_entry("type", &types)?,
+        }
+
+        if let Some(values) = &schema.values {
+            map.serialize_entry("enum", values)?;
+        }
+        if let Some(int) = schema.int {
+            let min = (int.min != Integer::MIN).then_some(Int(int.min));
+            let max = (int.max != Integer::MAX).then_some(Int(int.max));
+            MinMax::new(("min", "max"), min, max).write_to(&mut map, "int")?;
+        }
+        if let Some(float) = schema.float {
+            let min = float.min.is_finite().then_some(float.min);
+            let max = float.max.is_finite().then_some(float.max);
+            MinMax::new(("min", "max"), min, max).write_to(&mut map, "float")?;
+        }
+        if let Some(len) = schema.string {
+            MinMax::lengths(len).write_to(&mut map, "string")?;
+        }
+        if let Some(len) = schema.bytes {
+            MinMax::lengths(len).write_to(&mut map, "bytes")?;
+        }
+        if let Some(datetime) = schema.datetime {
+            let min = (datetime.min != DateTime::MIN).then_some(datetime.min);
+            let max = (datetime.max != DateTime::MAX).then_some(datetime.max);
+            MinMax::new(("min", "max"), min, max).write_to(&mut map, "datetime")?;
+        }
+        if let Some(array) = &schema.array {
+            let mut lengths = MinMax::lengths(array.len);
+            if array.items.count > 0 || array.items.types().next().is_some() {
+                lengths.items = Some(&array.items);
+            }
+            lengths.write_to(&mut map, "array")?;
+        }
+        if let Some(object) = &schema.object {
+            if !object.fields.is_empty() {
+                map.serialize_entry("fields", &Fields(object))?;
+            }
+        }
+        map.end()
+    }
+}
+
+/// The range of a type, and the elements of an array. Unbounded ends are
+/// left out.
+struct MinMax<'a, T> {
+    keys: (&'static str, &'static str),
+    min: Option<T>,
+    max: Option<T>,
+    items: Option<&'a Schema>,
+}
+
+impl<'a, T: Serialize> MinMax<'a, T> {
+    fn new(keys: (&'static str, &'static str), min: Option<T>, max: Option<T>) -> Self {
+        MinMax {
+            keys,
+            min,
+            max,
+            items: None,
+        }
+    }
+
+    /// Writes the entry, unless it would be an empty map, which EnCom can't
+    /// tell apart from a missing value.
+    fn write_to<M: SerializeMap>(&self, map: &mut M, key: &str) -> Result<(), M::Error> {
+        if self.min.is_some() || self.max.is_some() || self.items.is_some() {
+            map.serialize_entry(key, self)?;
+        }
+        Ok(())
+    }
+}
+
+impl<'a> MinMax<'a, usize> {
+    fn lengths(len: Bounds<usize>) -> Self {
+        let min = (len.min != 0).then_some(len.min);
+        let max = (len.max != usize::MAX).then_some(len.max);
+        MinMax::new(("min_len", "max_len"), min, max)
+    }
+}
+
+impl<T: Serialize> Serialize for MinMax<'_, T> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(None)?;
+        if let Some(min) = &self.min {
+            map.serialize_entry(self.keys.0, min)?;
+        }
+        if let Some(max) = &self.max {
+            map.serialize_entry(self.keys.1, max)?;
+        }
+        if let Some(items) = self.items {
+            map.serialize_entry("items", items)?;
+        }
+        map.end()
+    }
+}
+
+struct Fields<'a>(&'a ObjectSchema);
+
+impl Serialize for Fields<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let object = self.0;
+        let mut map = serializer.serialize_map(Some(object.fields.len()))?;
+        for (key, field) in &object.fields {
+            let node = Node {
+                schema: field,
+                optional: !object.required.contains(key),
+            };
+            map.serialize_entry(key, &node)?;
+        }
+        map.end()
+    }
+}
+
+/// An observed integer, written as the narrowest integer type that holds
+/// it.
+#[derive(Clone, Copy)]
+struct Int(Integer);
+
+impl Serialize for Int {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 .0 {
+            IntegerRepr::Neg(i) => match i64::try_from(i) {
+                Ok(i) => serializer.serialize_i64(i),
+                Err(_) => serializer.serialize_i128(i),
+            },
+            IntegerRepr::Pos(u) => match u64::try_from(u) {
+                Ok(u) => serializer.serialize_u64(u),
+                Err(_) => serializer.serialize_u128(u),
+            },
+        }
+    }
+}
+
+struct RustGen<'a> {
+    /// Struct names in use.
+    names: BTreeSet<String>,
+    /// Structs to write, in the order their names were given.
+    pending: Vec<(String, &'a ObjectSchema)>,
+}
+
+impl<'a> RustGen<'a> {
+    fn write_struct(&mut self, out: &mut String, name: &str, object: &'a ObjectSchema) {
+        let mut body = String::new();
+        let mut idents = BTreeSet::new();
+        for (key, field) in &object.fields {
+            let ident = unique_ident(&mut idents, field_ident(key));
+            let mut ty = self.rust_type(field, key);
+            if !object.required.contains(key) && !ty.starts_with("Option<") {
+                ty = format!("Option<{}>", ty);
+            }
+            if ident.trim_start_matches("r#") != key {
+                let _ = writeln!(body, "    #[serde(rename = {:?})]", key);
+            }
+            let _ = writeln!(body, "    pub {}: {},", ident, ty);
+        }
+
+        let _ = write!(
+            out,
+            "\n#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]\npub struct {} {{\n{}}}\n",
+            name, body
+        );
+    }
+
+    fn rust_type(&mut self, schema: &'a Schema, hint: &str) -> String {
+        let mut types = schema.types().filter(|&t| t != "null");
+        let ty = match (types.next(), types.next(), types.next()) {
+            (None, ..) => return "serde_encom::Value".to_owned(),
+            (Some("int"), None, _) => int_type(schema.int.unwrap()).to_owned(),
+            (Some("int"), Some("float"), None) | (Some("float"), None, _) => "f64".to_owned(),
+            (Some("bool"), None, _) => "bool".to_owned(),
+            (Some("string"), None, _) => "String".to_owned(),
+            (Some("bytes"), None, _) => "serde_bytes::ByteBuf".to_owned(),
+            (Some("datetime"), None, _) => "serde_encom::DateTime".to_owned(),
+            (Some("array"), None, _) => {
+                let items = &schema.array.as_ref().unwrap().items;
+                format!("Vec<{}>", self.rust_type(items, &singular(hint)))
+            }
+            (Some("object"), None, _) => {
+                let object = schema.object.as_ref().unwrap();
+                let name = self.struct_name(hint);
+                self.pending.push((name.clone(), object));
+                name
+            }
+            _ => "serde_encom::Value".to_owned(),
+        };
+
+        if schema.null {
+            format!("Option<{}>", ty)
+        } else {
+            ty
+        }
+    }
+
+    fn struct_name(&mut self, hint: &str) -> String {
+        let base = pascal_case(hint);
+        let mut name = base.clone();
+        let mut n = 2;
+        while self.names.contains(&name) {
+            name = format!("{}{}", base, n);
+            n += 1;
+        }
+        self.names.insert(name.clone());
+        name
+    }
+}
+
+fn int_type(int: Bounds<Integer>) -> &'static str {
+    if int.min >= Integer::from(0) {
+        if int.max <= Integer::from(u64::MAX) {
+            "u64"
+        } else {
+            "u128"
+        }
+    } else if int.min >= Integer::from(i64::MIN) && int.max <= Integer::from(i64::MAX) {
+        "i64"
+    } else {
+        "i128"
+    }
+}
+
+/// Splits a key into lowercase words, at non-alphanumeric characters and
+/// at lowercase to uppercase changes.
+fn words(key: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut prev_lower = false;
+    for c in key.chars() {
+        if !c.is_alphanumeric() {
+            if !word.is_empty() {
+                words.push(core::mem::take(&mut word));
+            }
+            prev_lower = false;
+            continue;
+        }
+        if c.is_uppercase() && prev_lower && !word.is_empty() {
+            words.push(core::mem::take(&mut word));
+        }
+        prev_lower = c.is_lowercase() || c.is_numeric();
+        word.extend(c.to_lowercase());
+    }
+    if !word.is_empty() {
+        words.push(word);
+    }
+    words
+}
+
+fn pascal_case(key: &str) -> String {
+    let mut out = String::new();
+    for word in words(key) {
+        let mut chars = word.chars();
+        if let Some(first) = chars.next() {
+            out.extend(first.to_uppercase());
+            out.push_str(chars.as_str());
+        }
+    }
+    match out.chars().next() {
+        None => "Unnamed".to_owned(),
+        Some(c) if c.is_numeric() => format!("T{}", out),
+        Some(_) => out,
+    }
+}
+
+fn field_ident(key: &str) -> String {
+    let ident = words(key).join("_");
+    match ident.chars().next() {
+        None => "field".to_owned(),
+        Some(c) if c.is_numeric() => format!("field_{}", ident),
+        Some(_) if matches!(ident.as_str(), "self" | "super" | "crate") => format!("{}_", ident),
+        Some(_) if KEYWORDS.contains(&ident.as_str()) => format!("r#{}", ident),
+        Some(_) => ident,
+    }
+}
+
+/// Adds a number to `ident` if another field of the struct has it, since
+/// different keys like `a-b` and `a_b` can give the same identifier.
+fn unique_ident(idents: &mut BTreeSet<String>, ident: String) -> String {
+    let mut unique = ident.clone();
+    let mut n = 2;
+    while idents.contains(&unique) {
+        unique = format!("{}_{}", ident.trim_start_matches("r#"), n);
+        n += 1;
+    }
+    idents.insert(unique.clone());
+    unique
+}
+
+fn singular(hint: &str) -> String {
+    match hint.strip_suffix('s') {
+        Some(stem) if !stem.is_empty() && !stem.ends_with('s') => stem.to_string(),
+        _ => format!("{}_item", hint),
+    }
+}
+
+const KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "do", "dyn",
+    "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let",
+    "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return",
+    "static", "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use",
+    "virtual", "where", "while", "yield",
+];
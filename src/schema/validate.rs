@@ -0,0 +1,735 @@
+use super::{Integer, Schema};
+use crate::{
+    datetime::{DateTime, DateTimeVisitor, TOKEN},
+    des::{Deserializer, InitDeserializer, SliceRead},
+    value::{push_token, Value, ValueVisitor},
+};
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display};
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+
+/// A place where a document doesn't match a [`Schema`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    pointer: String,
+    message: String,
+}
+
+impl ValidationError {
+    /// The EnCom Pointer of the value, or of the missing key, that doesn't
+    /// match. It is empty for the root.
+    pub fn pointer(&self) -> &str {
+        &self.pointer
+    }
+
+    /// What is wrong with the value.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at `{}`", self.message, self.pointer)
+    }
+}
+
+impl serde::de::StdError for ValidationError {}
+
+impl Schema {
+    /// Checks a value against the schema.
+    ///
+    /// Every mismatch is reported, in document order, with the mismatches of
+    /// a map's values before its missing keys. A value whose type doesn't
+    /// match isn't checked any further. Keys that have no schema are allowed
+    /// with any value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_encom::schema::Schema;
+    /// use serde_encom::Value;
+    ///
+    /// let schema: Schema = "\
+    ///     fields{
+    ///       id{type:3=int int{min:1}}
+    ///       kind{enum[4=user 5=admin]}
+    ///       key{optional:t type:5=bytes bytes{max_len:4}}
+    ///     }"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// let value: Value = serde_encom::from_str("id:7 kind:4=user key:2~ab").unwrap();
+    /// assert!(schema.validate(&value).is_ok());
+    ///
+    /// let value: Value = serde_encom::from_str("id:0 key:3=abc").unwrap();
+    /// let errors = schema.validate(&value).unwrap_err();
+    /// let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    /// assert_eq!(
+    ///     errors,
+    ///     [
+    ///         "0 is less than the minimum 1 at `/id`",
+    ///         "expected bytes, found string at `/key`",
+    ///         "missing required key at `/kind`",
+    ///     ]
+    /// );
+    /// ```
+    pub fn validate(&self, value: &Value) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        let mut path = String::new();
+        validate_value(self, value, &mut path, &mut errors);
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Checks an EnCom document against the schema as it is read, without
+    /// building a `Value`.
+    ///
+    /// The errors are the same as those of [`validate`](Schema::validate).
+    /// Values checked against `enum` are the exception: they are built to be
+    /// compared. If the document isn't valid EnCom, reading stops and the
+    /// syntax error is reported last, at the pointer where it happened.
+    ///
+    /// At the top level a document is a map or a sequence. Unless the schema
+    /// allows arrays, a sequence of a single value is checked as that value,
+    /// which is how a unit variant or a number is written on its own.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_encom::schema::Schema;
+    ///
+    /// let schema: Schema = "type:5=array array{max_len:3 items{int{max:9}}}"
+    ///     .parse()
+    ///     .unwrap();
+    ///
+    /// assert!(schema.validate_slice(b"1 2 3").is_ok());
+    ///
+    /// let errors = schema.validate_slice(b"1 20 3 4").unwrap_err();
+    /// assert_eq!(errors[0].pointer(), "/1");
+    /// assert_eq!(errors[0].message(), "20 is greater than the maximum 9");
+    /// assert_eq!(errors[1].pointer(), "");
+    /// assert_eq!(errors[1].message(), "length 4 is greater than the maximum 3");
+    /// ```
+    pub fn validate_slice(&self, v: &[u8]) -> Result<(), Vec<ValidationError>> {
+        let mut errors = Vec::new();
+        let mut path = String::new();
+
+        let mut des = Deserializer::new(SliceRead::new(v));
+        let root = Root(Check {
+            schema: self,
+            path: &mut path,
+            errors: &mut errors,
+            hint: Hint::Any,
+        });
+        let result = root
+            .deserialize(InitDeserializer { des: &mut des })
+            .and_then(|_| des.end());
+        if let Err(err) = result {
+            errors.push(ValidationError {
+                pointer: path,
+                message: err.to_string(),
+            });
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// The type of a value, and what is checked against ranges.
+#[derive(Clone, Copy)]
+enum Kind {
+    Null,
+    Bool,
+    Int(Integer),
+    Float(f64),
+    String(usize),
+    Bytes(usize),
+    DateTime(DateTime),
+    Array(usize),
+    Object,
+}
+
+impl Kind {
+    fn of(value: &Value) -> Kind {
+        match value {
+            Value::Null => Kind::Null,
+            Value::Bool(_) => Kind::Bool,
+            Value::Number(n) => match Integer::of(n) {
+                Some(i) => Kind::Int(i),
+                None => Kind::Float(n.as_f64().unwrap_or(f64::NAN)),
+            },
+            Value::String(s) => Kind::String(s.len()),
+            Value::Bytes(b) => Kind::Bytes(b.len()),
+            Value::DateTime(dt) => Kind::DateTime(*dt),
+            Value::Array(list) => Kind::Array(list.len()),
+            Value::Object(_) => Kind::Object,
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Kind::Null => "null",
+            Kind::Bool => "bool",
+            Kind::Int(_) => "int",
+            Kind::Float(_) => "float",
+            Kind::String(_) => "string",
+            Kind::Bytes(_) => "bytes",
+            Kind::DateTime(_) => "datetime",
+            Kind::Array(_) => "array",
+            Kind::Object => "object",
+        }
+    }
+}
+
+impl Schema {
+    /// Checks the type of a value. The lengths of containers aren't known
+    /// yet when reading, so they are checked by `check_range`.
+    fn check_type(&self, kind: Kind) -> Result<(), String> {
+        if self.types().next().is_none() {
+            return Ok(());
+        }
+        let matches = match kind {
+            Kind::Null => self.null,
+            Kind::Bool => self.bool,
+            Kind::Int(_) => self.int.is_some() || self.float.is_some(),
+            Kind::Float(_) => self.float.is_some(),
+            Kind::String(_) => self.string.is_some(),
+            Kind::Bytes(_) => self.bytes.is_some(),
+            Kind::DateTime(_) => self.datetime.is_some(),
+            Kind::Array(_) => self.array.is_some(),
+            Kind::Object => self.object.is_some(),
+        };
+        if matches {
+            return Ok(());
+        }
+        let mut expected = String::new();
+        for (i, name) in self.types().enumerate() {
+            if i > 0 {
+                expected.push_str(" or ");
+            }
+            expected.push_str(name);
+        }
+        Err(format!("expected {}, found {}", expected, kind.name()))
+    }
+
+    fn check_range(&self, kind: Kind) -> Result<(), String> {
+        match kind {
+            Kind::Int(i) => match (self.int, self.float) {
+                (Some(int), _) => in_range(i, int.min, int.max, ""),
+                (None, Some(float)) => in_range(i.as_f64(), float.min, float.max, ""),
+                (None, None) => Ok(()),
+            },
+            Kind::Float(f) => match self.float {
+                Some(float) => in_range(f, float.min, float.max, ""),
+                None => Ok(()),
+            },
+            Kind::String(len) => match self.string {
+                Some(bounds) => in_range(len, bounds.min, bounds.max, "length "),
+                None => Ok(()),
+            },
+            Kind::Bytes(len) => match self.bytes {
+                Some(bounds) => in_range(len, bounds.min, bounds.max, "length "),
+                None => Ok(()),
+            },
+            Kind::DateTime(dt) => match self.datetime {
+                Some(bounds) => in_range(dt, bounds.min, bounds.max, ""),
+                None => Ok(()),
+            },
+            Kind::Array(len) => match &self.array {
+                Some(array) => in_range(len, array.len.min, array.len.max, "length "),
+                None => Ok(()),
+            },
+            Kind::Null | Kind::Bool | Kind::Object => Ok(()),
+        }
+    }
+
+    fn check_value(&self, value: &Value) -> Result<(), String> {
+        match &self.values {
+            Some(values) if !values.contains(value) => {
+                Err("value is not one of the allowed values".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+fn in_range<T: PartialOrd + Display>(value: T, min: T, max: T, what: &str) -> Result<(), String> {
+    if value < min {
+        Err(format!(
+            "{}{} is less than the minimum {}",
+            what, value, min
+        ))
+    } else if value > max {
+        Err(format!(
+            "{}{} is greater than the maximum {}",
+            what, value, max
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn report(errors: &mut Vec<ValidationError>, path: &str, result: Result<(), String>) -> bool {
+    match result {
+        Ok(()) => true,
+        Err(message) => {
+            errors.push(ValidationError {
+                pointer: path.to_string(),
+                message,
+            });
+            false
+        }
+    }
+}
+
+fn report_missing<'a>(
+    errors: &mut Vec<ValidationError>,
+    path: &mut String,
+    missing: impl Iterator<Item = &'a String>,
+) {
+    for key in missing {
+        let len = path.len();
+        push_token(path, key);
+        report(errors, path, Err("missing required key".to_string()));
+        path.truncate(len);
+    }
+}
+
+fn validate_value(
+    schema: &Schema,
+    value: &Value,
+    path: &mut String,
+    errors: &mut Vec<ValidationError>,
+) {
+    let kind = Kind::of(value);
+    if !report(errors, path, schema.check_value(value))
+        || !report(errors, path, schema.check_type(kind))
+    {
+        return;
+    }
+
+    match value {
+        Value::Array(list) => {
+            if let Some(array) = &schema.array {
+                for (i, item) in list.iter().enumerate() {
+                    let len = path.len();
+                    path.push('/');
+                    path.push_str(itoa::Buffer::new().format(i));
+                    validate_value(&array.items, item, path, errors);
+                    path.truncate(len);
+                }
+            }
+        }
+        Value::Object(map) => {
+            if let Some(object) = &schema.object {
+                for (key, value) in map {
+                    if let Some(field) = object.fields.get(key) {
+                        let len = path.len();
+                        push_token(path, key);
+                        validate_value(field, value, path, errors);
+                        path.truncate(len);
+                    }
+                }
+                let missing = object.required.iter().filter(|key| !map.contains_key(*key));
+                report_missing(errors, path, missing);
+            }
+        }
+        _ => {}
+    }
+
+    report(errors, path, schema.check_range(kind));
+}
+
+/// A schema that allows every value, for the parts of a document that
+/// still have to be read but aren't checked.
+const ANY: Schema = Schema {
+    count: 0,
+    null: false,
+    bool: false,
+    int: None,
+    float: None,
+    string: None,
+    bytes: None,
+    datetime: None,
+    array: None,
+    object: None,
+    values: None,
+};
+
+const NO_FIELDS: &BTreeMap<String, Schema> = &BTreeMap::new();
+
+/// How to read the elements of an array after the first one, which is how
+/// `ValueVisitor` reads them.
+#[derive(Clone, Copy)]
+enum Hint {
+    Any,
+    Number,
+    Str,
+    Bytes,
+}
+
+/// Checks the value it deserializes against `schema`, and gives the hint
+/// for the array elements after it.
+struct Check<'s, 'p> {
+    schema: &'s Schema,
+    path: &'p mut String,
+    errors: &'p mut Vec<ValidationError>,
+    hint: Hint,
+}
+
+impl Check<'_, '_> {
+    fn scalar(self, kind: Kind, hint: Hint) -> Hint {
+        if report(self.errors, self.path, self.schema.check_type(kind)) {
+            report(self.errors, self.path, self.schema.check_range(kind));
+        }
+        hint
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for Check<'_, '_> {
+    type Value = Hint;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Hint, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        if self.schema.values.is_some() {
+            let value = match self.hint {
+                Hint::Any => deserializer.deserialize_any(ValueVisitor)?,
+                Hint::Number => deserializer.deserialize_u64(ValueVisitor)?,
+                Hint::Str => deserializer.deserialize_str(ValueVisitor)?,
+                Hint::Bytes => deserializer.deserialize_bytes(ValueVisitor)?,
+            };
+            validate_value(self.schema, &value, self.path, self.errors);
+            return Ok(match value {
+                Value::Number(_) => Hint::Number,
+                Value::String(_) => Hint::Str,
+                Value::Bytes(_) => Hint::Bytes,
+                _ => Hint::Any,
+            });
+        }
+
+        match self.hint {
+            Hint::Any => deserializer.deserialize_any(self),
+            Hint::Number => deserializer.deserialize_u64(self),
+            Hint::Str => deserializer.deserialize_str(self),
+            Hint::Bytes => deserializer.deserialize_bytes(self),
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for Check<'_, '_> {
+    type Value = Hint;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any valid EnCom value")
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<Hint, E> {
+        Ok(self.scalar(Kind::Bool, Hint::Any))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Hint, E> {
+        Ok(self.scalar(Kind::Int(Integer::from(value)), Hint::Number))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Hint, E> {
+        Ok(self.scalar(Kind::Int(Integer::from(value)), Hint::Number))
+    }
+
+    fn visit_i128<E>(self, value: i128) -> Result<Hint, E> {
+        Ok(self.scalar(Kind::Int(Integer::from(value)), Hint::Number))
+    }
+
+    fn visit_u128<E>(self, value: u128) -> Result<Hint, E> {
+        Ok(self.scalar(Kind::Int(Integer::from(value)), Hint::Number))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Hint, E> {
+        Ok(self.scalar(Kind::Float(value), Hint::Number))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Hint, E> {
+        Ok(self.scalar(Kind::String(value.len()), Hint::Str))
+    }
+
+    fn visit_bytes<E>(self, value: &[u8]) -> Result<Hint, E> {
+        Ok(self.scalar(Kind::Bytes(value.len()), Hint::Bytes))
+    }
+
+    fn visit_none<E>(self) -> Result<Hint, E> {
+        Ok(self.scalar(Kind::Null, Hint::Any))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Hint, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_unit<E>(self) -> Result<Hint, E> {
+        Ok(self.scalar(Kind::Null, Hint::Any))
+    }
+
+    fn visit_seq<A>(self, mut access: A) -> Result<Hint, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let Check {
+            schema,
+            path,
+            errors,
+            ..
+        } = self;
+
+        let checked = report(errors, path, schema.check_type(Kind::Array(0)));
+        let items = match &schema.array {
+            Some(array) if checked => &*array.items,
+            _ => &ANY,
+        };
+
+        let mut hint = Hint::Any;
+        let mut len = 0;
+        loop {
+            let at = path.len();
+            path.push('/');
+            path.push_str(itoa::Buffer::new().format(len));
+            let check = Check {
+                schema: items,
+                path: &mut *path,
+                errors: &mut *errors,
+                hint,
+            };
+            let next = access.next_element_seed(check)?;
+            path.truncate(at);
+            match next {
+                Some(next) if len == 0 => hint = next,
+                Some(_) => {}
+                None => break,
+            }
+            len += 1;
+        }
+
+        if checked {
+            report(errors, path, schema.check_range(Kind::Array(len)));
+        }
+        Ok(Hint::Any)
+    }
+
+    fn visit_map<A>(self, mut access: A) -> Result<Hint, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let Check {
+            schema,
+            path,
+            errors,
+            ..
+        } = self;
+
+        // A date-time is read as a map with a single `TOKEN` key, so the map
+        // is only checked as an object once its first key is known.
+        let mut checked = None;
+        let mut missing: BTreeSet<&String> = match &schema.object {
+            Some(object) => object.required.iter().collect(),
+            None => BTreeSet::new(),
+        };
+
+        loop {
+            let at = path.len();
+            let key = Key {
+                path: &mut *path,
+                missing: &mut missing,
+                fields: match (&schema.object, checked) {
+                    (Some(object), None | Some(true)) => &object.fields,
+                    _ => NO_FIELDS,
+                },
+                first: checked.is_none(),
+            };
+            let field = match access.next_key_seed(key)? {
+                Some(Field::DateTime) => {
+                    let datetime = access.next_value_seed(DateTimeVisitor)?;
+                    let check = Check {
+                        schema,
+                        path,
+                        errors,
+                        hint: Hint::Any,
+                    };
+                    return Ok(check.scalar(Kind::DateTime(datetime), Hint::Any));
+                }
+                Some(Field::Known(field)) => Some(field),
+                Some(Field::Unknown) => None,
+                None => break,
+            };
+            if checked.is_none() {
+                checked = Some(report(errors, &path[..at], schema.check_type(Kind::Object)));
+            }
+            let field = match checked {
+                Some(true) => field,
+                _ => None,
+            };
+            access.next_value_seed(Check {
+                schema: field.unwrap_or(&ANY),
+                path: &mut *path,
+                errors: &mut *errors,
+                hint: Hint::Any,
+            })?;
+            path.truncate(at);
+        }
+
+        let checked = match checked {
+            Some(checked) => checked,
+            None => report(errors, path, schema.check_type(Kind::Object)),
+        };
+        if checked {
+            report_missing(errors, path, missing.into_iter());
+        }
+        Ok(Hint::Any)
+    }
+}
+
+/// Reads the top level of a document, which is always a map or a sequence.
+struct Root<'s, 'p>(Check<'s, 'p>);
+
+impl<'de> DeserializeSeed<'de> for Root<'_, '_> {
+    type Value = Hint;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Hint, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Root<'_, '_> {
+    type Value = Hint;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an EnCom document")
+    }
+
+    fn visit_map<A>(self, access: A) -> Result<Hint, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        self.0.visit_map(access)
+    }
+
+    fn visit_seq<A>(self, mut access: A) -> Result<Hint, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let Check {
+            schema,
+            path,
+            errors,
+            ..
+        } = self.0;
+        let any = schema.values.is_none() && schema.types().next().is_none();
+        if any || schema.array.is_some() {
+            return Check {
+                schema,
+                path,
+                errors,
+                hint: Hint::Any,
+            }
+            .visit_seq(access);
+        }
+
+        let check = Check {
+            schema,
+            path: &mut *path,
+            errors: &mut *errors,
+            hint: Hint::Any,
+        };
+        let hint = match access.next_element_seed(check)? {
+            Some(hint) => hint,
+            None => {
+                report(errors, path, Err("missing value".to_string()));
+                return Ok(Hint::Any);
+            }
+        };
+
+        let mut rest = false;
+        loop {
+            let check = Check {
+                schema: &ANY,
+                path: &mut *path,
+                errors: &mut *errors,
+                hint,
+            };
+            if access.next_element_seed(check)?.is_none() {
+                break;
+            }
+            rest = true;
+        }
+        if rest {
+            let message = "expected a single value, found a sequence";
+            report(errors, path, Err(message.to_string()));
+        }
+        Ok(Hint::Any)
+    }
+}
+
+/// Reads a map key, appends it to the path and finds its schema.
+struct Key<'s, 'p> {
+    path: &'p mut String,
+    missing: &'p mut BTreeSet<&'s String>,
+    fields: &'s BTreeMap<String, Schema>,
+    /// Whether this is the first key, which may make the map a date-time.
+    first: bool,
+}
+
+/// What a map key turned out to be.
+enum Field<'s> {
+    Known(&'s Schema),
+    Unknown,
+    DateTime,
+}
+
+impl<'de, 's> DeserializeSeed<'de> for Key<'s, '_> {
+    type Value = Field<'s>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_str(self)
+    }
+}
+
+impl<'de, 's> Visitor<'de> for Key<'s, '_> {
+    type Value = Field<'s>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a map key")
+    }
+
+    fn visit_str<E>(self, key: &str) -> Result<Self::Value, E> {
+        if self.first && key == TOKEN {
+            return Ok(Field::DateTime);
+        }
+        push_token(self.path, key);
+        let (key, field) = match self.fields.get_key_value(key) {
+            Some((key, field)) => (key, field),
+            None => return Ok(Field::Unknown),
+        };
+        self.missing.remove(key);
+        Ok(Field::Known(field))
+    }
+}
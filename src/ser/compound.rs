@@ -27,6 +27,8 @@ pub(crate) enum Compound<'a, W: 'a, F: 'a> {
     Map {
         ser: &'a mut Serializer<W, F>,
         state: State,
+        /// Position of the next struct field.
+        field: u32,
     },
     #[cfg(feature = "arbitrary_precision")]
     Number { ser: &'a mut Serializer<W, F> },
@@ -49,7 +51,7 @@ where
         T: ?Sized + Serialize,
     {
         match self {
-            Compound::Map { ser, state } => {
+            Compound::Map { ser, state, .. } => {
                 match *state {
                     State::First => *state = State::Rest,
                     State::Initial => *state = State::RestNoClose,
@@ -76,7 +78,7 @@ where
     #[inline]
     fn end(self) -> Result<()> {
         match self {
-            Compound::Map { ser, state } => match state {
+            Compound::Map { ser, state, .. } => match state {
                 State::Empty => Ok(()),
                 State::RestNoClose => Ok(()),
                 _ => ser.formatter.end_array(&mut ser.writer).map_err(Error::io),
@@ -155,7 +157,7 @@ where
     #[inline]
     fn end(self) -> Result<()> {
         match self {
-            Compound::Map { ser, state } => {
+            Compound::Map { ser, state, .. } => {
                 match state {
                     State::Empty => {}
                     State::RestNoClose => {}
@@ -190,7 +192,7 @@ where
         T: ?Sized + Serialize,
     {
         match self {
-            Compound::Map { ser, state } => {
+            Compound::Map { ser, state, .. } => {
                 match *state {
                     State::First => *state = State::Rest,
                     State::Initial => *state = State::RestNoClose,
@@ -239,7 +241,7 @@ where
     #[inline]
     fn end(self) -> Result<()> {
         match self {
-            Compound::Map { ser, state } => match state {
+            Compound::Map { ser, state, .. } => match state {
                 State::Empty => Ok(()),
                 State::RestNoClose => Ok(()),
                 _ => ser.formatter.end_object(&mut ser.writer).map_err(Error::io),
@@ -267,7 +269,15 @@ where
         T: ?Sized + Serialize,
     {
         match self {
-            Compound::Map { .. } => SerializeMap::serialize_entry(self, key, value),
+            Compound::Map { ser, field, .. } => {
+                if ser.field_index {
+                    let index = *field;
+                    *field += 1;
+                    SerializeMap::serialize_entry(self, &index, value)
+                } else {
+                    SerializeMap::serialize_entry(self, key, value)
+                }
+            }
             #[cfg(feature = "arbitrary_precision")]
             Compound::Number { ser, .. } => {
                 if key == crate::number::TOKEN {
@@ -287,6 +297,20 @@ where
         }
     }
 
+    #[inline]
+    fn skip_field(&mut self, _key: &'static str) -> Result<()> {
+        match self {
+            Compound::Map { field, .. } => {
+                *field += 1;
+                Ok(())
+            }
+            #[cfg(feature = "arbitrary_precision")]
+            Compound::Number { .. } => Ok(()),
+            #[cfg(feature = "raw_value")]
+            Compound::RawValue { .. } => Ok(()),
+        }
+    }
+
     #[inline]
     fn end(self) -> Result<()> {
         match self {
@@ -322,10 +346,15 @@ where
         }
     }
 
+    #[inline]
+    fn skip_field(&mut self, key: &'static str) -> Result<()> {
+        SerializeStruct::skip_field(self, key)
+    }
+
     #[inline]
     fn end(self) -> Result<()> {
         match self {
-            Compound::Map { ser, state } => {
+            Compound::Map { ser, state, .. } => {
                 match state {
                     State::Empty => {}
                     State::RestNoClose => {}
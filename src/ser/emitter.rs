@@ -0,0 +1,194 @@
+use crate::error::{Error, ErrorCode, Result};
+use core::fmt::Display;
+use serde::ser::{Impossible, Serialize, Serializer};
+
+/// Serializes the text behind a private newtype token, like that of a
+/// date-time, with `F`, and fails with `code` for anything else.
+pub(crate) struct TextEmitter<F> {
+    f: F,
+    code: ErrorCode,
+}
+
+impl<F> TextEmitter<F> {
+    pub(crate) fn new<T>(code: ErrorCode, f: F) -> Self
+    where
+        F: FnOnce(&str) -> Result<T>,
+    {
+        TextEmitter { f, code }
+    }
+
+    fn error(self) -> Error {
+        Error::syntax(self.code, 0, 0)
+    }
+}
+
+impl<F, T> Serializer for TextEmitter<F>
+where
+    F: FnOnce(&str) -> Result<T>,
+{
+    type Ok = T;
+    type Error = Error;
+
+    type SerializeSeq = Impossible<T, Error>;
+    type SerializeTuple = Impossible<T, Error>;
+    type SerializeTupleStruct = Impossible<T, Error>;
+    type SerializeTupleVariant = Impossible<T, Error>;
+    type SerializeMap = Impossible<T, Error>;
+    type SerializeStruct = Impossible<T, Error>;
+    type SerializeStructVariant = Impossible<T, Error>;
+
+    fn serialize_bool(self, _v: bool) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_i8(self, _v: i8) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_i16(self, _v: i16) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_i32(self, _v: i32) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_i64(self, _v: i64) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_u8(self, _v: u8) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_u16(self, _v: u16) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_u32(self, _v: u32) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_u64(self, _v: u64) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_f32(self, _v: f32) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_f64(self, _v: f64) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_char(self, _v: char) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_str(self, value: &str) -> Result<T> {
+        (self.f)(value)
+    }
+
+    fn serialize_bytes(self, _value: &[u8]) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_none(self) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_some<V>(self, _value: &V) -> Result<T>
+    where
+        V: ?Sized + Serialize,
+    {
+        Err(self.error())
+    }
+
+    fn serialize_unit(self) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+    ) -> Result<T> {
+        Err(self.error())
+    }
+
+    fn serialize_newtype_struct<V>(self, _name: &'static str, _value: &V) -> Result<T>
+    where
+        V: ?Sized + Serialize,
+    {
+        Err(self.error())
+    }
+
+    fn serialize_newtype_variant<V>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _value: &V,
+    ) -> Result<T>
+    where
+        V: ?Sized + Serialize,
+    {
+        Err(self.error())
+    }
+
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq> {
+        Err(self.error())
+    }
+
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple> {
+        Err(self.error())
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleStruct> {
+        Err(self.error())
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeTupleVariant> {
+        Err(self.error())
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap> {
+        Err(self.error())
+    }
+
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct> {
+        Err(self.error())
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        _len: usize,
+    ) -> Result<Self::SerializeStructVariant> {
+        Err(self.error())
+    }
+
+    fn collect_str<V>(self, _value: &V) -> Result<T>
+    where
+        V: ?Sized + Display,
+    {
+        Err(self.error())
+    }
+}
@@ -1,4 +1,4 @@
-use crate::io;
+use crate::{bytes::BytesMode, io};
 
 /// This trait abstracts away serializing the EnCom control characters, which allows the user to
 /// optionally pretty print the EnCom output.
@@ -171,13 +171,22 @@ pub(crate) trait Formatter {
         writer.write_all(b"=")
     }
 
-    /// Called before each series of `write_bytes`
+    /// Called before bytes, writes the marker of their `mode`
     #[inline]
-    fn begin_bytes<W>(&mut self, writer: &mut W) -> io::Result<()>
+    fn begin_bytes<W>(&mut self, writer: &mut W, mode: BytesMode) -> io::Result<()>
     where
         W: ?Sized + io::Write,
     {
-        writer.write_all(b"~")
+        writer.write_all(&[mode.marker()])
+    }
+
+    /// Called before a date-time, writes a `@`
+    #[inline]
+    fn begin_datetime<W>(&mut self, writer: &mut W) -> io::Result<()>
+    where
+        W: ?Sized + io::Write,
+    {
+        writer.write_all(b"@")
     }
 
     /*     /// Called after each series of `write_string_fragment` and
@@ -200,9 +209,9 @@ pub(crate) trait Formatter {
         writer.write_all(value.as_bytes())
     }
 
-    /// Writes a string fragment that doesn't need any escaping to the
+    /* /// Writes a string fragment that doesn't need any escaping to the
     /// specified writer.
-    /* #[inline]
+    #[inline]
     fn write_string<W>(&mut self, writer: &mut W, value: &str) -> io::Result<()>
     where
         W: ?Sized + io::Write,
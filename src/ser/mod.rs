@@ -1,12 +1,20 @@
 //! Serialize a Rust data structure into EnCom data.
 
-use self::wrapper::InitSerializer;
-use crate::{error::Result, io};
+pub(crate) use self::{
+    emitter::TextEmitter, formatter::Formatter, serializer::Serializer, wrapper::InitSerializer,
+};
+#[cfg(feature = "alloc")]
+use crate::options::Options;
+use crate::{
+    error::{Error, Result},
+    io,
+};
+#[cfg(feature = "alloc")]
 use alloc::{string::String, vec::Vec};
 use serde::ser::Serialize;
-use serializer::Serializer;
 
 mod compound;
+mod emitter;
 mod formatter;
 mod serializer;
 mod wrapper;
@@ -481,6 +489,112 @@ where
     value.serialize(InitSerializer { ser: &mut ser })
 }
 
+/// Serialize the given data structure as EnCom into a byte slice, and return
+/// the number of bytes written.
+///
+/// This needs neither `std` nor `alloc`.
+///
+/// # Examples
+///
+/// ```
+/// use serde::Serialize;
+///
+/// #[derive(Serialize)]
+/// struct Reading {
+///     id: u8,
+///     temp: f32,
+/// }
+///
+/// let reading = Reading { id: 3, temp: 21.5 };
+///
+/// let mut buf = [0; 32];
+/// let len = serde_encom::to_slice(&mut buf, &reading).unwrap();
+/// assert_eq!(&buf[..len], b"id:3 temp:21.5");
+///
+/// let mut buf = [0; 8];
+/// let err = serde_encom::to_slice(&mut buf, &reading).unwrap_err();
+/// assert!(err.is_buffer_full());
+/// ```
+///
+/// # Errors
+///
+/// Fails with an error for which [`is_buffer_full`](Error::is_buffer_full)
+/// is true if the output doesn't fit in `buf`, whose content is unspecified
+/// then. Serialization can also fail if `T`'s implementation of `Serialize`
+/// decides to fail, or if `T` contains a map with non-string keys.
+#[inline]
+pub fn to_slice<T>(buf: &mut [u8], value: &T) -> Result<usize>
+where
+    T: ?Sized + Serialize,
+{
+    let mut writer = SliceWriter::new(buf);
+    let result = value.serialize(InitSerializer {
+        ser: &mut Serializer::new(&mut writer),
+    });
+    writer.finish(result)
+}
+
+/// Serialize the given data structure as pretty-printed EnCom into a byte
+/// slice, and return the number of bytes written.
+///
+/// # Errors
+///
+/// The same as [`to_slice`].
+#[inline]
+pub fn to_slice_pretty<T>(buf: &mut [u8], value: &T) -> Result<usize>
+where
+    T: ?Sized + Serialize,
+{
+    let mut writer = SliceWriter::new(buf);
+    let result = value.serialize(InitSerializer {
+        ser: &mut Serializer::pretty(&mut writer),
+    });
+    writer.finish(result)
+}
+
+/// Writes into a slice until it is full.
+pub(crate) struct SliceWriter<'a> {
+    buf: &'a mut [u8],
+    len: usize,
+    full: bool,
+}
+
+impl<'a> SliceWriter<'a> {
+    pub(crate) fn new(buf: &'a mut [u8]) -> Self {
+        SliceWriter {
+            buf,
+            len: 0,
+            full: false,
+        }
+    }
+
+    /// Returns the number of bytes written, or the error that stopped the
+    /// writing, which is a full buffer if it didn't fit.
+    pub(crate) fn finish(self, result: Result<()>) -> Result<usize> {
+        match result {
+            Ok(()) => Ok(self.len),
+            Err(_) if self.full => Err(Error::buffer_full()),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+impl io::Write for SliceWriter<'_> {
+    #[inline]
+    fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+        let n = data.len().min(self.buf.len() - self.len);
+        self.buf[self.len..self.len + n].copy_from_slice(&data[..n]);
+        self.len += n;
+        self.full |= n < data.len();
+        Ok(n)
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
 /// Serialize the given data structure as an EnCom byte vector.
 ///
 /// # Errors
@@ -488,6 +602,8 @@ where
 /// Serialization can fail if `T`'s implementation of `Serialize` decides to
 /// fail, or if `T` contains a map with non-string keys.
 #[inline]
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub fn to_vec<T>(value: &T) -> Result<Vec<u8>>
 where
     T: ?Sized + Serialize,
@@ -504,6 +620,8 @@ where
 /// Serialization can fail if `T`'s implementation of `Serialize` decides to
 /// fail, or if `T` contains a map with non-string keys.
 #[inline]
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub fn to_vec_pretty<T>(value: &T) -> Result<Vec<u8>>
 where
     T: ?Sized + Serialize,
@@ -515,38 +633,36 @@ where
 
 /// Serialize the given data structure as a String of EnCom.
 ///
+/// Bytes are written as base64, see [`BytesMode`](crate::BytesMode).
+///
 /// # Errors
 ///
 /// Serialization can fail if `T`'s implementation of `Serialize` decides to
 /// fail, or if `T` contains a map with non-string keys.
 #[inline]
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub fn to_string<T>(value: &T) -> Result<String>
 where
     T: ?Sized + Serialize,
 {
-    let vec = to_vec(value)?;
-    let string = unsafe {
-        // We do not emit invalid UTF-8.
-        String::from_utf8_unchecked(vec)
-    };
-    Ok(string)
+    Options::new().to_string(value)
 }
 
 /// Serialize the given data structure as a pretty-printed String of EnCom.
 ///
+/// Bytes are written as base64, see [`BytesMode`](crate::BytesMode).
+///
 /// # Errors
 ///
 /// Serialization can fail if `T`'s implementation of `Serialize` decides to
 /// fail, or if `T` contains a map with non-string keys.
 #[inline]
+#[cfg(feature = "alloc")]
+#[cfg_attr(docsrs, doc(cfg(feature = "alloc")))]
 pub fn to_string_pretty<T>(value: &T) -> Result<String>
 where
     T: ?Sized + Serialize,
 {
-    let vec = to_vec_pretty(value)?;
-    let string = unsafe {
-        // We do not emit invalid UTF-8.
-        String::from_utf8_unchecked(vec)
-    };
-    Ok(string)
+    Options::new().to_string_pretty(value)
 }
@@ -1,12 +1,17 @@
 use super::{
     compound::{Compound, State},
     formatter::{CompactFormatter, Formatter, PrettyFormatter},
+    TextEmitter,
 };
 use crate::{
-    error::{Error, Result},
+    bytes::BytesMode,
+    error::{Error, ErrorCode, Result},
     io,
 };
+#[cfg(feature = "alloc")]
 use alloc::string::ToString;
+#[cfg(not(feature = "alloc"))]
+use core::fmt;
 use core::{fmt::Display, num::FpCategory};
 use serde::ser::{self, Serialize};
 
@@ -15,6 +20,12 @@ use serde::ser::{self, Serialize};
 pub(crate) struct Serializer<W, F = CompactFormatter> {
     pub(crate) writer: W,
     pub(crate) formatter: F,
+    /// Write enum variants by their index instead of their name.
+    pub(crate) variant_index: bool,
+    /// Write struct fields by their position instead of their name.
+    pub(crate) field_index: bool,
+    /// How to write bytes.
+    pub(crate) bytes: BytesMode,
 }
 
 impl<W> Serializer<W>
@@ -48,7 +59,13 @@ where
     /// specified.
     #[inline]
     pub fn with_formatter(writer: W, formatter: F) -> Self {
-        Serializer { writer, formatter }
+        Serializer {
+            writer,
+            formatter,
+            variant_index: false,
+            field_index: false,
+            bytes: BytesMode::Raw,
+        }
     }
 
     /* /// Unwrap the `Writer` from the `Serializer`.
@@ -60,6 +77,18 @@ where
 
 pub(crate) trait SerializerExtras: Sized {
     fn serialize_keystr(self, value: &str) -> Result<()>;
+
+    /// Writes the variant of an enum in key position, by name or by index.
+    fn serialize_variant_key(self, variant_index: u32, variant: &str) -> Result<()>;
+
+    /// Writes the `Display` output of a value as a string.
+    fn serialize_display<T: ?Sized + Display>(self, value: &T) -> Result<()>;
+
+    /// Writes the RFC 3339 text of a date-time.
+    fn serialize_datetime(self, text: &str) -> Result<()>;
+
+    /// Writes the text of a number as it is, like that of a decimal.
+    fn serialize_number_str(self, text: &str) -> Result<()>;
 }
 
 impl<W, F> SerializerExtras for &mut Serializer<W, F>
@@ -73,6 +102,104 @@ where
             .write_key(&mut self.writer, value)
             .map_err(Error::io)
     }
+
+    #[inline]
+    fn serialize_datetime(self, text: &str) -> Result<()> {
+        self.formatter
+            .write_u64(&mut self.writer, text.len() as u64)
+            .map_err(Error::io)?;
+        self.formatter
+            .begin_datetime(&mut self.writer)
+            .map_err(Error::io)?;
+        self.formatter
+            .write_bytes(&mut self.writer, text.as_bytes())
+            .map_err(Error::io)
+    }
+
+    #[inline]
+    fn serialize_number_str(self, text: &str) -> Result<()> {
+        self.formatter
+            .write_number_str(&mut self.writer, text)
+            .map_err(Error::io)
+    }
+
+    #[inline]
+    fn serialize_variant_key(self, variant_index: u32, variant: &str) -> Result<()> {
+        if self.variant_index {
+            self.formatter
+                .write_u32(&mut self.writer, variant_index)
+                .map_err(Error::io)
+        } else {
+            self.serialize_keystr(variant)
+        }
+    }
+
+    #[cfg(feature = "alloc")]
+    fn serialize_display<T: ?Sized + Display>(self, value: &T) -> Result<()> {
+        ser::Serializer::serialize_str(self, &value.to_string())
+    }
+
+    /// Without a heap the value is formatted twice: once to find the length
+    /// that prefixes the string, and once to write it.
+    #[cfg(not(feature = "alloc"))]
+    fn serialize_display<T: ?Sized + Display>(self, value: &T) -> Result<()> {
+        struct Len(usize);
+
+        impl fmt::Write for Len {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                self.0 += s.len();
+                Ok(())
+            }
+        }
+
+        let mut len = Len(0);
+        fmt::write(&mut len, format_args!("{}", value)).map_err(display_error)?;
+        self.formatter
+            .write_u64(&mut self.writer, len.0 as u64)
+            .map_err(Error::io)?;
+        self.formatter
+            .begin_string(&mut self.writer)
+            .map_err(Error::io)?;
+        write_display(self, value)
+    }
+}
+
+/// Writes the `Display` output of a value as it is formatted, without
+/// buffering it.
+#[cfg(not(feature = "alloc"))]
+fn write_display<W, F, T>(ser: &mut Serializer<W, F>, value: &T) -> Result<()>
+where
+    W: io::Write,
+    F: Formatter,
+    T: ?Sized + Display,
+{
+    struct Adapter<'a, W, F> {
+        ser: &'a mut Serializer<W, F>,
+        error: Option<io::Error>,
+    }
+
+    impl<W: io::Write, F: Formatter> fmt::Write for Adapter<'_, W, F> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            let ser = &mut *self.ser;
+            ser.formatter
+                .write_bytes(&mut ser.writer, s.as_bytes())
+                .map_err(|err| {
+                    self.error = Some(err);
+                    fmt::Error
+                })
+        }
+    }
+
+    let mut adapter = Adapter { ser, error: None };
+    match fmt::write(&mut adapter, format_args!("{}", value)) {
+        Ok(()) => Ok(()),
+        Err(err) => Err(adapter.error.map_or_else(|| display_error(err), Error::io)),
+    }
+}
+
+#[cfg(not(feature = "alloc"))]
+fn display_error(_: fmt::Error) -> Error {
+    ser::Error::custom("a Display implementation returned an error")
 }
 
 impl<'a, W, F> ser::Serializer for &'a mut Serializer<W, F>
@@ -224,15 +351,18 @@ where
             seq.serialize_element(byte)?;
         }
         seq.end() */
+        let mode = self.bytes;
         self.formatter
-            .write_u64(&mut self.writer, value.len() as u64)
+            .write_u64(&mut self.writer, mode.encoded_len(value.len()) as u64)
             .map_err(Error::io)?;
         self.formatter
-            .begin_bytes(&mut self.writer)
+            .begin_bytes(&mut self.writer, mode)
             .map_err(Error::io)?;
-        self.formatter
-            .write_bytes(&mut self.writer, value)
-            .map_err(Error::io)
+        match mode {
+            BytesMode::Raw => self.formatter.write_bytes(&mut self.writer, value),
+            mode => mode.encode(&mut self.writer, value),
+        }
+        .map_err(Error::io)
     }
 
     #[inline]
@@ -251,18 +381,33 @@ where
     fn serialize_unit_variant(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         variant: &'static str,
     ) -> Result<()> {
-        self.serialize_str(variant)
+        if self.variant_index {
+            self.serialize_u32(variant_index)
+        } else {
+            self.serialize_str(variant)
+        }
     }
 
     /// Serialize newtypes without an object wrapper.
     #[inline]
-    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
     {
+        if name == crate::datetime::TOKEN {
+            return value.serialize(TextEmitter::new(ErrorCode::InvalidDateTime, |text| {
+                self.serialize_datetime(text)
+            }));
+        }
+        #[cfg(feature = "rust_decimal")]
+        if name == crate::decimal::TOKEN {
+            return value.serialize(TextEmitter::new(ErrorCode::InvalidNumber, |text| {
+                self.serialize_number_str(text)
+            }));
+        }
         value.serialize(self)
     }
 
@@ -270,7 +415,7 @@ where
     fn serialize_newtype_variant<T>(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         variant: &'static str,
         value: &T,
     ) -> Result<()>
@@ -283,7 +428,7 @@ where
         self.formatter
             .begin_data_key(&mut self.writer, true)
             .map_err(Error::io)?;
-        self.serialize_keystr(variant)?;
+        self.serialize_variant_key(variant_index, variant)?;
         self.formatter
             .end_data_key(&mut self.writer)
             .map_err(Error::io)?;
@@ -320,6 +465,7 @@ where
             Ok(Compound::Map {
                 ser: self,
                 state: State::Empty,
+                field: 0,
             })
         } else {
             self.formatter
@@ -328,6 +474,7 @@ where
             Ok(Compound::Map {
                 ser: self,
                 state: State::First,
+                field: 0,
             })
         }
     }
@@ -350,7 +497,7 @@ where
     fn serialize_tuple_variant(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeTupleVariant> {
@@ -360,7 +507,7 @@ where
         self.formatter
             .begin_data_key(&mut self.writer, true)
             .map_err(Error::io)?;
-        self.serialize_str(variant)?;
+        self.serialize_variant_key(variant_index, variant)?;
         self.formatter
             .end_data_key(&mut self.writer)
             .map_err(Error::io)?;
@@ -381,6 +528,7 @@ where
             Ok(Compound::Map {
                 ser: self,
                 state: State::Empty,
+                field: 0,
             })
         } else {
             self.formatter
@@ -389,6 +537,7 @@ where
             Ok(Compound::Map {
                 ser: self,
                 state: State::First,
+                field: 0,
             })
         }
     }
@@ -408,7 +557,7 @@ where
     fn serialize_struct_variant(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         variant: &'static str,
         len: usize,
     ) -> Result<Self::SerializeStructVariant> {
@@ -418,7 +567,7 @@ where
         self.formatter
             .begin_data_key(&mut self.writer, true)
             .map_err(Error::io)?;
-        self.serialize_str(variant)?;
+        self.serialize_variant_key(variant_index, variant)?;
         self.formatter
             .end_data_key(&mut self.writer)
             .map_err(Error::io)?;
@@ -434,6 +583,6 @@ where
     where
         T: ?Sized + Display,
     {
-        self.serialize_str(&value.to_string())
+        self.serialize_display(value)
     }
 }
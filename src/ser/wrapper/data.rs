@@ -1,13 +1,13 @@
 use crate::{
-    error::{Error, Result},
+    error::{Error, ErrorCode, Result},
     io,
     ser::{
         compound::Compound,
         formatter::Formatter,
         serializer::{Serializer, SerializerExtras},
+        TextEmitter,
     },
 };
-use alloc::string::ToString;
 use core::fmt::Display;
 use serde::ser::{self, Serialize};
 
@@ -193,18 +193,41 @@ where
     fn serialize_unit_variant(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         variant: &'static str,
     ) -> Result<()> {
-        self.serialize_str(variant)
+        if self.ser.variant_index {
+            self.serialize_u32(variant_index)
+        } else {
+            self.serialize_str(variant)
+        }
     }
 
     /// Serialize newtypes without an object wrapper.
     #[inline]
-    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
     {
+        if name == crate::datetime::TOKEN {
+            return value.serialize(TextEmitter::new(ErrorCode::InvalidDateTime, |text| {
+                self.ser
+                    .formatter
+                    .begin_data(&mut self.ser.writer)
+                    .map_err(Error::io)?;
+                self.ser.serialize_datetime(text)
+            }));
+        }
+        #[cfg(feature = "rust_decimal")]
+        if name == crate::decimal::TOKEN {
+            return value.serialize(TextEmitter::new(ErrorCode::InvalidNumber, |text| {
+                self.ser
+                    .formatter
+                    .begin_data(&mut self.ser.writer)
+                    .map_err(Error::io)?;
+                self.ser.serialize_number_str(text)
+            }));
+        }
         value.serialize(self)
     }
 
@@ -212,7 +235,7 @@ where
     fn serialize_newtype_variant<T>(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         variant: &'static str,
         value: &T,
     ) -> Result<()>
@@ -227,7 +250,7 @@ where
             .formatter
             .begin_data_key(&mut self.ser.writer, true)
             .map_err(Error::io)?;
-        self.ser.serialize_keystr(variant)?;
+        self.ser.serialize_variant_key(variant_index, variant)?;
         self.ser
             .formatter
             .end_data_key(&mut self.ser.writer)
@@ -320,6 +343,10 @@ where
     where
         T: ?Sized + Display,
     {
-        self.serialize_str(&value.to_string())
+        self.ser
+            .formatter
+            .begin_data(&mut self.ser.writer)
+            .map_err(Error::io)?;
+        self.ser.serialize_display(value)
     }
 }
@@ -1,13 +1,14 @@
+use super::DataSerializer;
 use crate::{
-    error::{Error, Result},
+    error::{Error, ErrorCode, Result},
     io,
     ser::{
         compound::{Compound, State},
         formatter::Formatter,
         serializer::{Serializer, SerializerExtras},
+        TextEmitter,
     },
 };
-use alloc::string::ToString;
 use core::fmt::Display;
 use serde::ser::{self, Serialize};
 
@@ -125,18 +126,33 @@ where
     fn serialize_unit_variant(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         variant: &'static str,
     ) -> Result<()> {
-        self.serialize_str(variant)
+        if self.ser.variant_index {
+            self.serialize_u32(variant_index)
+        } else {
+            self.serialize_str(variant)
+        }
     }
 
     /// Serialize newtypes without an object wrapper.
     #[inline]
-    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<()>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<()>
     where
         T: ?Sized + Serialize,
     {
+        if name == crate::datetime::TOKEN {
+            return value.serialize(TextEmitter::new(ErrorCode::InvalidDateTime, |text| {
+                self.ser.serialize_datetime(text)
+            }));
+        }
+        #[cfg(feature = "rust_decimal")]
+        if name == crate::decimal::TOKEN {
+            return value.serialize(TextEmitter::new(ErrorCode::InvalidNumber, |text| {
+                self.ser.serialize_number_str(text)
+            }));
+        }
         value.serialize(self)
     }
 
@@ -144,7 +160,7 @@ where
     fn serialize_newtype_variant<T>(
         self,
         _name: &'static str,
-        _variant_index: u32,
+        variant_index: u32,
         variant: &'static str,
         value: &T,
     ) -> Result<()>
@@ -160,7 +176,7 @@ where
             .formatter
             .begin_data_key(&mut self.ser.writer, true)
             .map_err(Error::io)?;
-        self.ser.serialize_keystr(variant)?;
+        self.ser.serialize_variant_key(variant_index, variant)?;
         self.ser
             .formatter
             .end_data_key(&mut self.ser.writer)
@@ -169,7 +185,7 @@ where
         .formatter
         .begin_object_value(&mut self.ser.writer)
         .map_err(Error::io)?; */
-        value.serialize(&mut *self.ser)?;
+        value.serialize(DataSerializer { ser: self.ser })?;
         self.ser
             .formatter
             .end_data(&mut self.ser.writer)
@@ -200,11 +216,13 @@ where
             Ok(Compound::Map {
                 ser: self.ser,
                 state: State::Empty,
+                field: 0,
             })
         } else {
             Ok(Compound::Map {
                 ser: self.ser,
                 state: State::Initial,
+                field: 0,
             })
         }
     }
@@ -241,11 +259,13 @@ where
             Ok(Compound::Map {
                 ser: self.ser,
                 state: State::Empty,
+                field: 0,
             })
         } else {
             Ok(Compound::Map {
                 ser: self.ser,
                 state: State::Initial,
+                field: 0,
             })
         }
     }
@@ -278,6 +298,6 @@ where
     where
         T: ?Sized + Display,
     {
-        self.serialize_str(&value.to_string())
+        self.ser.serialize_display(value)
     }
 }
@@ -6,7 +6,6 @@ use crate::{
         serializer::{Serializer, SerializerExtras},
     },
 };
-use alloc::string::ToString;
 use core::fmt::Display;
 use serde::ser::{self, Impossible, Serialize};
 
@@ -181,7 +180,9 @@ where
     }
 
     fn serialize_char(self, value: char) -> Result<()> {
-        self.ser.serialize_keystr(&value.to_string())
+        // A char encoded as UTF-8 takes 4 bytes at most.
+        let mut buf = [0; 4];
+        self.ser.serialize_keystr(value.encode_utf8(&mut buf))
     }
 
     fn serialize_bytes(self, _value: &[u8]) -> Result<()> {
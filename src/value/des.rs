@@ -1,7 +1,8 @@
 use super::{Map, Number, Value};
 use crate::{
+    datetime::{DateTimeAccess, DateTimeVisitor, MAX_LEN},
     error::{Error, ErrorCode},
-    seed::{BytesSeed, StrSeed, U64Seed},
+    seed::{BytesSeed, NumberSeed, StrSeed},
 };
 #[cfg(feature = "raw_value")]
 use alloc::string::ToString;
@@ -46,6 +47,16 @@ impl<'de> Visitor<'de> for ValueVisitor {
         Ok(Value::Number(value.into()))
     }
 
+    #[inline]
+    fn visit_i128<E>(self, value: i128) -> Result<Value, E> {
+        Ok(Value::Number(value.into()))
+    }
+
+    #[inline]
+    fn visit_u128<E>(self, value: u128) -> Result<Value, E> {
+        Ok(Value::Number(value.into()))
+    }
+
     #[inline]
     fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
         Ok(Number::from_f64(value).map_or(Value::Null, Value::Number))
@@ -107,7 +118,7 @@ impl<'de> Visitor<'de> for ValueVisitor {
             match elem {
                 Value::Number(_) => {
                     vec.push(elem);
-                    while let Some(elem) = access.next_element_seed(U64Seed)? {
+                    while let Some(elem) = access.next_element_seed(NumberSeed)? {
                         vec.push(elem);
                     }
                 }
@@ -154,6 +165,9 @@ impl<'de> Visitor<'de> for ValueVisitor {
                 let value = access.next_value_seed(crate::raw::BoxedFromString)?;
                 crate::from_str(value.get()).map_err(de::Error::custom)
             }
+            Some(KeyClass::DateTime) => {
+                access.next_value_seed(DateTimeVisitor).map(Value::DateTime)
+            }
             Some(KeyClass::Map(first_key)) => {
                 let mut values = Map::new();
 
@@ -264,6 +278,9 @@ impl<'de> serde::Deserializer<'de> for Value {
             #[cfg(any(feature = "std", feature = "alloc"))]
             Value::String(v) => visitor.visit_string(v),
             Value::Bytes(v) => visitor.visit_byte_buf(v),
+            Value::DateTime(dt) => {
+                visitor.visit_map(DateTimeAccess::new(dt.format(&mut [0; MAX_LEN])))
+            }
             Value::Array(v) => visit_array(v, visitor),
             Value::Object(v) => visit_object(v, visitor),
         }
@@ -455,13 +472,21 @@ impl<'de> serde::Deserializer<'de> for Value {
     #[inline]
     fn deserialize_tuple_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         _len: usize,
         visitor: V,
     ) -> Result<V::Value, Error>
     where
         V: Visitor<'de>,
     {
+        #[cfg(feature = "heapless")]
+        {
+            if name == crate::heapless::TOKEN {
+                return self.deserialize_any(visitor);
+            }
+        }
+
+        let _ = name;
         self.deserialize_seq(visitor)
     }
 
@@ -775,6 +800,9 @@ impl<'de> serde::Deserializer<'de> for &'de Value {
             Value::Number(n) => n.deserialize_any(visitor),
             Value::String(v) => visitor.visit_borrowed_str(v),
             Value::Bytes(v) => visitor.visit_borrowed_bytes(v),
+            Value::DateTime(dt) => {
+                visitor.visit_map(DateTimeAccess::new(dt.format(&mut [0; MAX_LEN])))
+            }
             Value::Array(v) => visit_array_ref(v, visitor),
             Value::Object(v) => visit_object_ref(v, visitor),
         }
@@ -962,13 +990,21 @@ impl<'de> serde::Deserializer<'de> for &'de Value {
     #[inline]
     fn deserialize_tuple_struct<V>(
         self,
-        _name: &'static str,
+        name: &'static str,
         _len: usize,
         visitor: V,
     ) -> Result<V::Value, Error>
     where
         V: Visitor<'de>,
     {
+        #[cfg(feature = "heapless")]
+        {
+            if name == crate::heapless::TOKEN {
+                return self.deserialize_any(visitor);
+            }
+        }
+
+        let _ = name;
         self.deserialize_seq(visitor)
     }
 
@@ -1308,6 +1344,7 @@ struct KeyClassifier;
 
 enum KeyClass {
     Map(String),
+    DateTime,
     #[cfg(feature = "arbitrary_precision")]
     Number,
     #[cfg(feature = "raw_value")]
@@ -1343,6 +1380,7 @@ impl<'de> Visitor<'de> for KeyClassifier {
             crate::number::TOKEN => Ok(KeyClass::Number),
             #[cfg(feature = "raw_value")]
             crate::raw::TOKEN => Ok(KeyClass::RawValue),
+            crate::datetime::TOKEN => Ok(KeyClass::DateTime),
             _ => Ok(KeyClass::Map(s.to_owned())),
         }
     }
@@ -1358,6 +1396,7 @@ impl<'de> Visitor<'de> for KeyClassifier {
             crate::number::TOKEN => Ok(KeyClass::Number),
             #[cfg(feature = "raw_value")]
             crate::raw::TOKEN => Ok(KeyClass::RawValue),
+            crate::datetime::TOKEN => Ok(KeyClass::DateTime),
             _ => Ok(KeyClass::Map(s)),
         }
     }
@@ -1380,6 +1419,7 @@ impl Value {
             Value::Number(n) => n.unexpected(),
             Value::String(s) => Unexpected::Str(s),
             Value::Bytes(s) => Unexpected::Bytes(s),
+            Value::DateTime(_) => Unexpected::Other("date-time"),
             Value::Array(_) => Unexpected::Seq,
             Value::Object(_) => Unexpected::Map,
         }
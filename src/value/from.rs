@@ -1,4 +1,5 @@
 use super::{Map, Number, Value};
+use crate::datetime::DateTime;
 use alloc::{
     borrow::Cow,
     string::{String, ToString},
@@ -18,13 +19,8 @@ macro_rules! from_integer {
 }
 
 from_integer! {
-    i8 i16 i32 i64 isize
-    u8 u16 u32 u64 usize
-}
-
-#[cfg(feature = "arbitrary_precision")]
-from_integer! {
-    i128 u128
+    i8 i16 i32 i64 i128 isize
+    u8 u16 u32 u64 u128 usize
 }
 
 impl From<f32> for Value {
@@ -132,6 +128,44 @@ impl<'a> From<Cow<'a, str>> for Value {
     }
 }
 
+impl<'a> From<Cow<'a, [u8]>> for Value {
+    /// Convert copy-on-write bytes to `Value::Bytes`
+    ///
+    /// A `Vec<u8>` or `&[u8]` converts to an array of numbers like any other
+    /// vector or slice, so bytes are built with `Value::Bytes` or from a
+    /// `Cow<[u8]>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_encom::Value;
+    /// use std::borrow::Cow;
+    ///
+    /// let b: Cow<[u8]> = Cow::Borrowed(b"\x01\xff");
+    /// let x: Value = b.into();
+    /// assert_eq!(x, Value::Bytes(vec![1, 255]));
+    /// ```
+    fn from(f: Cow<'a, [u8]>) -> Self {
+        Value::Bytes(f.into_owned())
+    }
+}
+
+impl From<DateTime> for Value {
+    /// Convert `DateTime` to `Value::DateTime`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_encom::{DateTime, Value};
+    ///
+    /// let x: Value = DateTime::UNIX_EPOCH.into();
+    /// assert!(x.is_datetime());
+    /// ```
+    fn from(f: DateTime) -> Self {
+        Value::DateTime(f)
+    }
+}
+
 impl From<Number> for Value {
     /// Convert `Number` to `Value`
     ///
@@ -176,6 +210,15 @@ impl<T: Into<Value>> From<Vec<T>> for Value {
     /// let v = vec!["lorem", "ipsum", "dolor"];
     /// let x: Value = v.into();
     /// ```
+    ///
+    /// A `Vec<u8>` is an array of numbers too. Use `Value::Bytes` for bytes.
+    ///
+    /// ```
+    /// use serde_encom::Value;
+    ///
+    /// let x: Value = vec![1u8, 255].into();
+    /// assert!(x.is_array());
+    /// ```
     fn from(f: Vec<T>) -> Self {
         Value::Array(f.into_iter().map(Into::into).collect())
     }
@@ -151,6 +151,7 @@ impl<'a> Display for Type<'a> {
             Value::Number(_) => formatter.write_str("number"),
             Value::String(_) => formatter.write_str("string"),
             Value::Bytes(_) => formatter.write_str("bytes"),
+            Value::DateTime(_) => formatter.write_str("date-time"),
             Value::Array(_) => formatter.write_str("array"),
             Value::Object(_) => formatter.write_str("object"),
         }
@@ -0,0 +1,224 @@
+//! Conversions between `serde_encom::Value` and `serde_json::Value`.
+//!
+//! Converting from JSON never fails. Converting to JSON has to deal with
+//! `Value::Bytes`, which JSON has no representation for; the
+//! [`BytesPolicy`] decides what happens to them. A `Value::DateTime`
+//! becomes a string of its RFC 3339 text, which stays a string when
+//! converted back. An integer that doesn't fit in 64 bits, or a float that
+//! is NaN or infinite, fails the conversion, since `serde_json::Number`
+//! can't hold it.
+
+use super::{push_token, Map, Number, Value};
+use crate::error::Error;
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use serde::de::Error as _;
+
+/// What to do with `Value::Bytes` when converting to `serde_json::Value`.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum BytesPolicy {
+    /// Fail the conversion.
+    ///
+    /// This is the policy used by the `TryFrom` impls.
+    #[default]
+    Error,
+    /// Convert to a string of standard, padded base64.
+    Base64,
+    /// Convert to an array of numbers, one per byte.
+    Array,
+}
+
+impl Value {
+    /// Converts into a `serde_json::Value`, handling `Value::Bytes` according
+    /// to `policy`.
+    ///
+    /// Integers stay integers and floats stay floats. With the
+    /// `preserve_order` feature map keys keep their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_encom::value::BytesPolicy;
+    /// use serde_encom::Value;
+    ///
+    /// let v: Value = serde_encom::from_slice(b"id:7 key:3~abc").unwrap();
+    ///
+    /// let json = v.clone().into_json(BytesPolicy::Base64).unwrap();
+    /// assert_eq!(json, serde_json::json!({ "id": 7, "key": "YWJj" }));
+    ///
+    /// let json = v.clone().into_json(BytesPolicy::Array).unwrap();
+    /// assert_eq!(json, serde_json::json!({ "id": 7, "key": [97, 98, 99] }));
+    ///
+    /// let err = v.into_json(BytesPolicy::Error).unwrap_err();
+    /// assert_eq!(err.to_string(), "bytes at `/key` can't be represented in JSON");
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with [`BytesPolicy::Error`] if the value contains bytes, and
+    /// with any policy if it contains an integer that doesn't fit in an
+    /// `i64` or a `u64`, rather than rounding it to a float, or a float that
+    /// is NaN or infinite, rather than turning it into `null`.
+    pub fn into_json(self, policy: BytesPolicy) -> Result<serde_json::Value, Error> {
+        self.to_json(policy)
+    }
+
+    /// Converts a reference into a `serde_json::Value`, the same way as
+    /// [`into_json`](Value::into_json).
+    pub fn to_json(&self, policy: BytesPolicy) -> Result<serde_json::Value, Error> {
+        let mut path = String::new();
+        to_json(self, policy, &mut path)
+    }
+}
+
+fn to_json(
+    value: &Value,
+    policy: BytesPolicy,
+    path: &mut String,
+) -> Result<serde_json::Value, Error> {
+    Ok(match value {
+        Value::Array(list) => {
+            let mut out = Vec::with_capacity(list.len());
+            for (i, value) in list.iter().enumerate() {
+                let len = path.len();
+                path.push('/');
+                path.push_str(itoa::Buffer::new().format(i));
+                out.push(to_json(value, policy, path)?);
+                path.truncate(len);
+            }
+            serde_json::Value::Array(out)
+        }
+        Value::Object(map) => {
+            let mut out = serde_json::Map::with_capacity(map.len());
+            for (key, value) in map {
+                let len = path.len();
+                push_token(path, key);
+                let value = to_json(value, policy, path)?;
+                path.truncate(len);
+                out.insert(key.clone(), value);
+            }
+            serde_json::Value::Object(out)
+        }
+        Value::String(s) => serde_json::Value::String(s.clone()),
+        Value::Bytes(bytes) => bytes_to_json(bytes, policy, path)?,
+        Value::DateTime(dt) => serde_json::Value::String(dt.to_string()),
+        Value::Number(n) => number_to_json(n, path)?,
+        Value::Bool(b) => serde_json::Value::Bool(*b),
+        Value::Null => serde_json::Value::Null,
+    })
+}
+
+fn bytes_to_json(
+    bytes: &[u8],
+    policy: BytesPolicy,
+    path: &str,
+) -> Result<serde_json::Value, Error> {
+    match policy {
+        BytesPolicy::Error => Err(Error::custom(format!(
+            "bytes at `{}` can't be represented in JSON",
+            path
+        ))),
+        BytesPolicy::Base64 => Ok(serde_json::Value::String(STANDARD.encode(bytes))),
+        BytesPolicy::Array => Ok(serde_json::Value::Array(
+            bytes.iter().map(|&b| serde_json::Value::from(b)).collect(),
+        )),
+    }
+}
+
+fn number_to_json(n: &Number, path: &str) -> Result<serde_json::Value, Error> {
+    if let Some(u) = n.as_u64() {
+        Ok(serde_json::Value::Number(u.into()))
+    } else if let Some(i) = n.as_i64() {
+        Ok(serde_json::Value::Number(i.into()))
+    } else if n.is_f64() {
+        n.as_f64()
+            .and_then(serde_json::Number::from_f64)
+            .map(serde_json::Value::Number)
+            .ok_or_else(|| {
+                Error::custom(format!(
+                    "float at `{}` is not finite and can't be represented in JSON",
+                    path
+                ))
+            })
+    } else {
+        Err(Error::custom(format!(
+            "integer {} at `{}` can't be represented in JSON",
+            n, path
+        )))
+    }
+}
+
+fn number_from_json(n: &serde_json::Number) -> Option<Number> {
+    if let Some(u) = n.as_u64() {
+        Some(u.into())
+    } else if let Some(i) = n.as_i64() {
+        Some(i.into())
+    } else {
+        n.as_f64().and_then(Number::from_f64)
+    }
+}
+
+impl From<serde_json::Value> for Value {
+    /// Converts a `serde_json::Value`. This never loses information: integers
+    /// stay integers, floats stay floats, and with the `preserve_order`
+    /// feature map keys keep their order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use serde_encom::Value;
+    ///
+    /// let json = serde_json::json!({ "name": "encom", "version": 4, "ratio": 0.5 });
+    /// let v = Value::from(json);
+    /// assert_eq!(v["version"].as_u64(), Some(4));
+    /// assert_eq!(v["ratio"].as_f64(), Some(0.5));
+    /// ```
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Value::Null,
+            serde_json::Value::Bool(b) => Value::Bool(b),
+            serde_json::Value::Number(n) => number_from_json(&n).map_or(Value::Null, Value::Number),
+            serde_json::Value::String(s) => Value::String(s),
+            serde_json::Value::Array(list) => {
+                Value::Array(list.into_iter().map(Value::from).collect())
+            }
+            serde_json::Value::Object(map) => {
+                let mut out = Map::new();
+                for (key, value) in map {
+                    out.insert(key, Value::from(value));
+                }
+                Value::Object(out)
+            }
+        }
+    }
+}
+
+impl From<&serde_json::Value> for Value {
+    fn from(value: &serde_json::Value) -> Self {
+        Value::from(value.clone())
+    }
+}
+
+impl TryFrom<Value> for serde_json::Value {
+    type Error = Error;
+
+    /// Converts with [`BytesPolicy::Error`]. Use [`Value::into_json`] to
+    /// choose another policy.
+    fn try_from(value: Value) -> Result<Self, Error> {
+        value.into_json(BytesPolicy::Error)
+    }
+}
+
+impl TryFrom<&Value> for serde_json::Value {
+    type Error = Error;
+
+    /// Converts with [`BytesPolicy::Error`]. Use [`Value::to_json`] to
+    /// choose another policy.
+    fn try_from(value: &Value) -> Result<Self, Error> {
+        value.to_json(BytesPolicy::Error)
+    }
+}
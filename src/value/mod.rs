@@ -89,11 +89,15 @@
 //! [from_slice]: crate::de::from_slice
 //! [from_reader]: crate::de::from_reader
 
+#[cfg(feature = "serde_json")]
+mod json;
 mod map;
 mod number;
+mod patch;
+pub mod query;
 pub(crate) mod seed;
 
-use crate::{error::Error, io};
+use crate::{bytes::BytesMode, datetime::DateTime, error::Error, io, options::Options};
 use alloc::{string::String, vec::Vec};
 use core::{
     fmt::{self, Debug, Display},
@@ -102,10 +106,14 @@ use core::{
 use serde::{de::DeserializeOwned, ser::Serialize};
 
 pub(crate) use self::des::ValueVisitor;
+#[cfg(feature = "serde_json")]
+pub use self::json::BytesPolicy;
 pub use self::{
     index::Index,
     map::{Entry, Map},
     number::Number,
+    patch::{diff, Patch, PatchOperation},
+    query::Query,
     ser::Serializer,
 };
 
@@ -153,15 +161,31 @@ pub enum Value {
     /// ```
     String(String),
 
-    /// Represents an EnCom bytes.
+    /// Represents EnCom bytes.
+    ///
+    /// Bytes are a leaf like strings: indexing into them gives `Null`, and
+    /// a pointer can't address a single byte.
     ///
     /// ```
     /// # use serde_encom::encom_from_json;
     /// #
-    /// let v = encom_from_json!("bytes");
+    /// let v = encom_from_json!(b"bytes");
     /// ```
     Bytes(Vec<u8>),
 
+    /// Represents an EnCom date-time.
+    ///
+    /// Date-times are read from `N@` values and written back the same, so
+    /// they don't turn into strings on the way through a `Value`.
+    ///
+    /// ```
+    /// # use serde_encom::Value;
+    /// #
+    /// let v: Value = serde_encom::from_str("at:20@2023-11-14T22:13:20Z").unwrap();
+    /// assert_eq!(v["at"].as_datetime().unwrap().timestamp(), 1_700_000_000);
+    /// ```
+    DateTime(DateTime),
+
     /// Represents an EnCom array.
     ///
     /// ```
@@ -195,6 +219,7 @@ impl Debug for Value {
             Value::Number(number) => Debug::fmt(number, formatter),
             Value::String(string) => write!(formatter, "String({:?})", string),
             Value::Bytes(bytes) => write!(formatter, "Bytes({:?})", bytes),
+            Value::DateTime(datetime) => Debug::fmt(datetime, formatter),
             Value::Array(vec) => {
                 formatter.write_str("Array ")?;
                 Debug::fmt(vec, formatter)
@@ -208,7 +233,7 @@ impl Debug for Value {
 }
 
 impl Display for Value {
-    /// Display an EnCom value as a string.
+    /// Display an EnCom value as a string. Bytes are written as base64.
     ///
     /// ```
     /// # use serde_encom::encom_from_json;
@@ -238,7 +263,7 @@ impl Display for Value {
         impl<'a, 'b> io::Write for WriterFormatter<'a, 'b> {
             fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
                 // Safety: the serializer below only emits valid utf8 when using
-                // the default formatter.
+                // the default formatter and encoded bytes.
                 let s = unsafe { str::from_utf8_unchecked(buf) };
                 self.inner.write_str(s).map_err(io_error)?;
                 Ok(buf.len())
@@ -257,17 +282,32 @@ impl Display for Value {
 
         let alternate = f.alternate();
         let mut wr = WriterFormatter { inner: f };
+        let options = Options::new().bytes(BytesMode::Base64);
         if alternate {
             // {:#}
-            super::ser::to_writer_pretty(&mut wr, self).map_err(|_| fmt::Error)
+            options
+                .to_writer_pretty(&mut wr, self)
+                .map_err(|_| fmt::Error)
         } else {
             // {}
-            super::ser::to_writer(&mut wr, self).map_err(|_| fmt::Error)
+            options.to_writer(&mut wr, self).map_err(|_| fmt::Error)
+        }
+    }
+}
+
+/// Appends `token` to an EnCom Pointer, escaping `~` and `/`.
+pub(crate) fn push_token(path: &mut String, token: &str) {
+    path.push('/');
+    for c in token.chars() {
+        match c {
+            '~' => path.push_str("~0"),
+            '/' => path.push_str("~1"),
+            c => path.push(c),
         }
     }
 }
 
-fn parse_index(s: &str) -> Option<usize> {
+pub(crate) fn parse_index(s: &str) -> Option<usize> {
     if s.starts_with('+') || (s.starts_with('0') && s.len() != 1) {
         return None;
     }
@@ -507,6 +547,95 @@ impl Value {
         }
     }
 
+    /// Returns true if the `Value` is Bytes. Returns false otherwise.
+    ///
+    /// For any Value on which `is_bytes` returns true, `as_bytes` is
+    /// guaranteed to return the byte slice.
+    ///
+    /// ```
+    /// # use serde_encom::encom_from_json;
+    /// #
+    /// let v = encom_from_json!({ "a": b"\x01\xff", "b": [1, 255] });
+    ///
+    /// assert!(v["a"].is_bytes());
+    ///
+    /// // An array of numbers is not bytes.
+    /// assert!(!v["b"].is_bytes());
+    /// ```
+    pub fn is_bytes(&self) -> bool {
+        self.as_bytes().is_some()
+    }
+
+    /// If the `Value` is Bytes, returns the associated byte slice. Returns
+    /// None otherwise.
+    ///
+    /// ```
+    /// # use serde_encom::encom_from_json;
+    /// #
+    /// let v = encom_from_json!({ "a": b"\x01\xff", "b": "\x01" });
+    ///
+    /// assert_eq!(v["a"].as_bytes(), Some(&b"\x01\xff"[..]));
+    ///
+    /// // Strings are not bytes, even when they're ASCII.
+    /// assert_eq!(v["b"].as_bytes(), None);
+    /// ```
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// If the `Value` is Bytes, returns the associated mutable vector.
+    /// Returns None otherwise.
+    ///
+    /// ```
+    /// # use serde_encom::encom_from_json;
+    /// #
+    /// let mut v = encom_from_json!({ "a": b"\x01" });
+    ///
+    /// v["a"].as_bytes_mut().unwrap().push(0xff);
+    /// assert_eq!(v, encom_from_json!({ "a": b"\x01\xff" }));
+    /// ```
+    pub fn as_bytes_mut(&mut self) -> Option<&mut Vec<u8>> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns true if the `Value` is a DateTime. Returns false otherwise.
+    ///
+    /// ```
+    /// # use serde_encom::Value;
+    /// #
+    /// let v: Value = serde_encom::from_str("a:20@2023-11-14T22:13:20Z b:20=2023-11-14T22:13:20Z").unwrap();
+    ///
+    /// assert!(v["a"].is_datetime());
+    ///
+    /// // A string is not a date-time, even when it looks like one.
+    /// assert!(!v["b"].is_datetime());
+    /// ```
+    pub fn is_datetime(&self) -> bool {
+        self.as_datetime().is_some()
+    }
+
+    /// If the `Value` is a DateTime, returns it. Returns None otherwise.
+    ///
+    /// ```
+    /// # use serde_encom::{DateTime, Value};
+    /// #
+    /// let v: Value = serde_encom::from_str("a:20@1970-01-01T00:00:00Z").unwrap();
+    ///
+    /// assert_eq!(v["a"].as_datetime(), Some(DateTime::UNIX_EPOCH));
+    /// ```
+    pub fn as_datetime(&self) -> Option<DateTime> {
+        match self {
+            Value::DateTime(datetime) => Some(*datetime),
+            _ => None,
+        }
+    }
+
     /// Returns true if the `Value` is a Number. Returns false otherwise.
     ///
     /// ```
@@ -665,6 +794,44 @@ impl Value {
         }
     }
 
+    /// If the `Value` is an integer, represent it as i128 if possible. Returns
+    /// None otherwise.
+    ///
+    /// ```
+    /// # use serde_encom::Value;
+    /// #
+    /// let v: Value = serde_encom::from_str("a:64 b:-100000000000000000000 c:256.0").unwrap();
+    ///
+    /// assert_eq!(v["a"].as_i128(), Some(64));
+    /// assert_eq!(v["b"].as_i128(), Some(-100_000_000_000_000_000_000));
+    /// assert_eq!(v["c"].as_i128(), None);
+    /// ```
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Value::Number(n) => n.as_i128(),
+            _ => None,
+        }
+    }
+
+    /// If the `Value` is an integer, represent it as u128 if possible. Returns
+    /// None otherwise.
+    ///
+    /// ```
+    /// # use serde_encom::Value;
+    /// #
+    /// let v: Value = serde_encom::from_str("a:64 b:100000000000000000000 c:-64").unwrap();
+    ///
+    /// assert_eq!(v["a"].as_u128(), Some(64));
+    /// assert_eq!(v["b"].as_u128(), Some(100_000_000_000_000_000_000));
+    /// assert_eq!(v["c"].as_u128(), None);
+    /// ```
+    pub fn as_u128(&self) -> Option<u128> {
+        match self {
+            Value::Number(n) => n.as_u128(),
+            _ => None,
+        }
+    }
+
     /// If the `Value` is a number, represent it as f64 if possible. Returns
     /// None otherwise.
     ///
@@ -32,6 +32,10 @@ enum N {
     NegInt(i64),
     /// Always finite.
     Float(f64),
+    /// Always greater than `u64::MAX`.
+    PosInt128(u128),
+    /// Always less than `i64::MIN`.
+    NegInt128(i128),
 }
 
 #[cfg(not(feature = "arbitrary_precision"))]
@@ -41,6 +45,8 @@ impl PartialEq for N {
             (N::PosInt(a), N::PosInt(b)) => a == b,
             (N::NegInt(a), N::NegInt(b)) => a == b,
             (N::Float(a), N::Float(b)) => a == b,
+            (N::PosInt128(a), N::PosInt128(b)) => a == b,
+            (N::NegInt128(a), N::NegInt128(b)) => a == b,
             _ => false,
         }
     }
@@ -66,6 +72,8 @@ impl Hash for N {
                     f.to_bits().hash(h);
                 }
             }
+            N::PosInt128(i) => i.hash(h),
+            N::NegInt128(i) => i.hash(h),
         }
     }
 }
@@ -100,7 +108,7 @@ impl Number {
         match self.n {
             N::PosInt(v) => v <= i64::MAX as u64,
             N::NegInt(_) => true,
-            N::Float(_) => false,
+            N::Float(_) | N::PosInt128(_) | N::NegInt128(_) => false,
         }
         #[cfg(feature = "arbitrary_precision")]
         self.as_i64().is_some()
@@ -129,7 +137,7 @@ impl Number {
         #[cfg(not(feature = "arbitrary_precision"))]
         match self.n {
             N::PosInt(_) => true,
-            N::NegInt(_) | N::Float(_) => false,
+            N::NegInt(_) | N::Float(_) | N::PosInt128(_) | N::NegInt128(_) => false,
         }
         #[cfg(feature = "arbitrary_precision")]
         self.as_u64().is_some()
@@ -159,7 +167,7 @@ impl Number {
         #[cfg(not(feature = "arbitrary_precision"))]
         match self.n {
             N::Float(_) => true,
-            N::PosInt(_) | N::NegInt(_) => false,
+            N::PosInt(_) | N::NegInt(_) | N::PosInt128(_) | N::NegInt128(_) => false,
         }
         #[cfg(feature = "arbitrary_precision")]
         {
@@ -197,7 +205,7 @@ impl Number {
                 }
             }
             N::NegInt(n) => Some(n),
-            N::Float(_) => None,
+            N::Float(_) | N::PosInt128(_) | N::NegInt128(_) => None,
         }
         #[cfg(feature = "arbitrary_precision")]
         self.n.parse().ok()
@@ -220,7 +228,63 @@ impl Number {
         #[cfg(not(feature = "arbitrary_precision"))]
         match self.n {
             N::PosInt(n) => Some(n),
-            N::NegInt(_) | N::Float(_) => None,
+            N::NegInt(_) | N::Float(_) | N::PosInt128(_) | N::NegInt128(_) => None,
+        }
+        #[cfg(feature = "arbitrary_precision")]
+        self.n.parse().ok()
+    }
+
+    /// If the `Number` is an integer, represent it as i128 if possible. Returns
+    /// None otherwise.
+    ///
+    /// Integers that don't fit in 64 bits are kept exactly, as long as they fit
+    /// in 128.
+    ///
+    /// ```
+    /// # use serde_encom::{Number, Value};
+    /// #
+    /// let v: Value = serde_encom::from_str("n:-100000000000000000000").unwrap();
+    /// let big = v["n"].as_number().unwrap();
+    ///
+    /// assert_eq!(big.as_i128(), Some(-100_000_000_000_000_000_000));
+    /// assert_eq!(big.as_i64(), None);
+    /// assert_eq!(Number::from(u128::MAX).as_i128(), None);
+    /// assert_eq!(Number::from_f64(256.0).unwrap().as_i128(), None);
+    /// ```
+    #[inline]
+    pub fn as_i128(&self) -> Option<i128> {
+        #[cfg(not(feature = "arbitrary_precision"))]
+        match self.n {
+            N::PosInt(n) => Some(n as i128),
+            N::NegInt(n) => Some(n as i128),
+            N::PosInt128(n) => i128::try_from(n).ok(),
+            N::NegInt128(n) => Some(n),
+            N::Float(_) => None,
+        }
+        #[cfg(feature = "arbitrary_precision")]
+        self.n.parse().ok()
+    }
+
+    /// If the `Number` is an integer, represent it as u128 if possible. Returns
+    /// None otherwise.
+    ///
+    /// ```
+    /// # use serde_encom::{Number, Value};
+    /// #
+    /// let v: Value = serde_encom::from_str("n:340282366920938463463374607431768211455").unwrap();
+    /// let big = v["n"].as_number().unwrap();
+    ///
+    /// assert_eq!(big.as_u128(), Some(u128::MAX));
+    /// assert_eq!(big.as_u64(), None);
+    /// assert_eq!(Number::from(-64).as_u128(), None);
+    /// ```
+    #[inline]
+    pub fn as_u128(&self) -> Option<u128> {
+        #[cfg(not(feature = "arbitrary_precision"))]
+        match self.n {
+            N::PosInt(n) => Some(n as u128),
+            N::PosInt128(n) => Some(n),
+            N::NegInt(_) | N::NegInt128(_) | N::Float(_) => None,
         }
         #[cfg(feature = "arbitrary_precision")]
         self.n.parse().ok()
@@ -244,6 +308,8 @@ impl Number {
             N::PosInt(n) => Some(n as f64),
             N::NegInt(n) => Some(n as f64),
             N::Float(n) => Some(n),
+            N::PosInt128(n) => Some(n as f64),
+            N::NegInt128(n) => Some(n as f64),
         }
         #[cfg(feature = "arbitrary_precision")]
         self.n.parse::<f64>().ok().filter(|float| float.is_finite())
@@ -313,6 +379,8 @@ impl Number {
             N::PosInt(n) => Some(n as f32),
             N::NegInt(n) => Some(n as f32),
             N::Float(n) => Some(n as f32),
+            N::PosInt128(n) => Some(n as f32),
+            N::NegInt128(n) => Some(n as f32),
         }
         #[cfg(feature = "arbitrary_precision")]
         self.n.parse::<f32>().ok().filter(|float| float.is_finite())
@@ -352,6 +420,8 @@ impl Display for Number {
             N::PosInt(u) => formatter.write_str(itoa::Buffer::new().format(u)),
             N::NegInt(i) => formatter.write_str(itoa::Buffer::new().format(i)),
             N::Float(f) => formatter.write_str(ryu::Buffer::new().format_finite(f)),
+            N::PosInt128(u) => formatter.write_str(itoa::Buffer::new().format(u)),
+            N::NegInt128(i) => formatter.write_str(itoa::Buffer::new().format(i)),
         }
     }
 
@@ -378,6 +448,8 @@ impl Serialize for Number {
             N::PosInt(u) => serializer.serialize_u64(u),
             N::NegInt(i) => serializer.serialize_i64(i),
             N::Float(f) => serializer.serialize_f64(f),
+            N::PosInt128(u) => serializer.serialize_u128(u),
+            N::NegInt128(i) => serializer.serialize_i128(i),
         }
     }
 
@@ -420,6 +492,16 @@ impl<'de> Deserialize<'de> for Number {
                 Ok(value.into())
             }
 
+            #[inline]
+            fn visit_i128<E>(self, value: i128) -> Result<Number, E> {
+                Ok(value.into())
+            }
+
+            #[inline]
+            fn visit_u128<E>(self, value: u128) -> Result<Number, E> {
+                Ok(value.into())
+            }
+
             #[inline]
             fn visit_f64<E>(self, value: f64) -> Result<Number, E>
             where
@@ -532,6 +614,8 @@ macro_rules! deserialize_any {
                 N::PosInt(u) => visitor.visit_u64(u),
                 N::NegInt(i) => visitor.visit_i64(i),
                 N::Float(f) => visitor.visit_f64(f),
+                N::PosInt128(u) => visitor.visit_u128(u),
+                N::NegInt128(i) => visitor.visit_i128(i),
             }
         }
 
@@ -716,6 +800,8 @@ impl From<ParserNumber> for Number {
                     i.to_string()
                 }
             }
+            ParserNumber::U128(u) => return u.into(),
+            ParserNumber::I128(i) => return i.into(),
             #[cfg(feature = "arbitrary_precision")]
             ParserNumber::String(s) => s,
         };
@@ -783,6 +869,32 @@ impl_from_unsigned!(u128);
 #[cfg(feature = "arbitrary_precision")]
 impl_from_signed!(i128);
 
+#[cfg(not(feature = "arbitrary_precision"))]
+impl From<u128> for Number {
+    #[inline]
+    fn from(u: u128) -> Self {
+        let n = match u64::try_from(u) {
+            Ok(u) => N::PosInt(u),
+            Err(_) => N::PosInt128(u),
+        };
+        Number { n }
+    }
+}
+
+#[cfg(not(feature = "arbitrary_precision"))]
+impl From<i128> for Number {
+    #[inline]
+    fn from(i: i128) -> Self {
+        let n = match (u64::try_from(i), i64::try_from(i)) {
+            (Ok(u), _) => N::PosInt(u),
+            (_, Ok(i)) => N::NegInt(i),
+            (Err(_), Err(_)) if i > 0 => N::PosInt128(i as u128),
+            (Err(_), Err(_)) => N::NegInt128(i),
+        };
+        Number { n }
+    }
+}
+
 impl Number {
     #[cfg(not(feature = "arbitrary_precision"))]
     #[cold]
@@ -791,6 +903,7 @@ impl Number {
             N::PosInt(u) => Unexpected::Unsigned(u),
             N::NegInt(i) => Unexpected::Signed(i),
             N::Float(f) => Unexpected::Float(f),
+            N::PosInt128(_) | N::NegInt128(_) => Unexpected::Other("integer"),
         }
     }
 
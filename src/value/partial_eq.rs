@@ -1,5 +1,6 @@
 use super::Value;
-use alloc::string::String;
+use crate::datetime::DateTime;
+use alloc::{string::String, vec::Vec};
 
 fn eq_i64(value: &Value, other: i64) -> bool {
     value.as_i64() == Some(other)
@@ -9,6 +10,14 @@ fn eq_u64(value: &Value, other: u64) -> bool {
     value.as_u64() == Some(other)
 }
 
+fn eq_i128(value: &Value, other: i128) -> bool {
+    value.as_i128() == Some(other)
+}
+
+fn eq_u128(value: &Value, other: u128) -> bool {
+    value.as_u128() == Some(other)
+}
+
 fn eq_f32(value: &Value, other: f32) -> bool {
     match value {
         Value::Number(n) => n.as_f32() == Some(other),
@@ -64,6 +73,70 @@ impl PartialEq<Value> for String {
     }
 }
 
+fn eq_bytes(value: &Value, other: &[u8]) -> bool {
+    value.as_bytes() == Some(other)
+}
+
+impl PartialEq<[u8]> for Value {
+    fn eq(&self, other: &[u8]) -> bool {
+        eq_bytes(self, other)
+    }
+}
+
+impl PartialEq<&[u8]> for Value {
+    fn eq(&self, other: &&[u8]) -> bool {
+        eq_bytes(self, other)
+    }
+}
+
+impl PartialEq<Value> for [u8] {
+    fn eq(&self, other: &Value) -> bool {
+        eq_bytes(other, self)
+    }
+}
+
+impl PartialEq<Value> for &[u8] {
+    fn eq(&self, other: &Value) -> bool {
+        eq_bytes(other, self)
+    }
+}
+
+impl<const N: usize> PartialEq<[u8; N]> for Value {
+    fn eq(&self, other: &[u8; N]) -> bool {
+        eq_bytes(self, other)
+    }
+}
+
+impl<const N: usize> PartialEq<&[u8; N]> for Value {
+    fn eq(&self, other: &&[u8; N]) -> bool {
+        eq_bytes(self, *other)
+    }
+}
+
+impl PartialEq<Vec<u8>> for Value {
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        eq_bytes(self, other)
+    }
+}
+
+impl PartialEq<Value> for Vec<u8> {
+    fn eq(&self, other: &Value) -> bool {
+        eq_bytes(other, self)
+    }
+}
+
+impl PartialEq<DateTime> for Value {
+    fn eq(&self, other: &DateTime) -> bool {
+        self.as_datetime() == Some(*other)
+    }
+}
+
+impl PartialEq<Value> for DateTime {
+    fn eq(&self, other: &Value) -> bool {
+        other.as_datetime() == Some(*self)
+    }
+}
+
 macro_rules! partialeq_numeric {
     ($($eq:ident [$($ty:ty)*])*) => {
         $($(
@@ -97,6 +170,8 @@ macro_rules! partialeq_numeric {
 partialeq_numeric! {
     eq_i64[i8 i16 i32 i64 isize]
     eq_u64[u8 u16 u32 u64 usize]
+    eq_i128[i128]
+    eq_u128[u128]
     eq_f32[f32]
     eq_f64[f64]
     eq_bool[bool]
@@ -0,0 +1,361 @@
+//! Structural diff between two `Value`s.
+//!
+//! A [`Patch`] is an ordered list of [`PatchOperation`]s, each addressed by an
+//! EnCom Pointer as accepted by [`Value::pointer`]. Use [`diff`] to compute the
+//! operations that turn one value into another and [`Value::apply`] to replay
+//! them.
+
+use super::{push_token, Map, Value};
+use crate::error::Error;
+use alloc::{format, string::String, vec::Vec};
+use core::slice;
+use serde::de::Error as _;
+
+/// A single operation of a [`Patch`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PatchOperation {
+    /// Inserts `value` at `path`.
+    ///
+    /// The parent of `path` must exist. For maps the key is inserted (or
+    /// overwritten), for arrays the value is inserted at the index, shifting
+    /// the following elements. The index may be equal to the array length or
+    /// be `-` to append.
+    Add {
+        /// EnCom Pointer of the inserted value.
+        path: String,
+        /// Inserted value.
+        value: Value,
+    },
+
+    /// Removes the value at `path`, which must exist.
+    ///
+    /// Removing the root leaves `Null` in its place.
+    Remove {
+        /// EnCom Pointer of the removed value.
+        path: String,
+    },
+
+    /// Replaces the value at `path`, which must exist, with `value`.
+    Replace {
+        /// EnCom Pointer of the replaced value.
+        path: String,
+        /// New value.
+        value: Value,
+    },
+}
+
+impl PatchOperation {
+    /// Returns the EnCom Pointer this operation is addressed to.
+    pub fn path(&self) -> &str {
+        match self {
+            PatchOperation::Add { path, .. }
+            | PatchOperation::Remove { path }
+            | PatchOperation::Replace { path, .. } => path,
+        }
+    }
+}
+
+/// An ordered list of operations produced by [`diff`].
+///
+/// Operations must be applied in order, since array indices of later
+/// operations take into account the effect of the earlier ones.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Patch(pub Vec<PatchOperation>);
+
+impl Patch {
+    /// Makes a new empty `Patch`.
+    #[inline]
+    pub fn new() -> Self {
+        Patch(Vec::new())
+    }
+
+    /// Returns true if the patch contains no operations.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of operations in the patch.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Gets an iterator over the operations of the patch.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, PatchOperation> {
+        self.0.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Patch {
+    type Item = &'a PatchOperation;
+    type IntoIter = slice::Iter<'a, PatchOperation>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl IntoIterator for Patch {
+    type Item = PatchOperation;
+    type IntoIter = alloc::vec::IntoIter<PatchOperation>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Computes the operations that turn `a` into `b`.
+///
+/// Maps are compared key by key and arrays element by element; everything
+/// else, including `Value::Bytes`, is a leaf that is replaced as a whole when
+/// it differs.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_encom::encom_from_json;
+/// use serde_encom::value::{diff, PatchOperation};
+///
+/// let a = encom_from_json!({ "name": "encom", "tags": ["a", "b"] });
+/// let b = encom_from_json!({ "name": "EnCom", "tags": ["a"] });
+///
+/// let patch = diff(&a, &b);
+/// assert_eq!(
+///     patch.0,
+///     vec![
+///         PatchOperation::Replace { path: "/name".to_owned(), value: "EnCom".into() },
+///         PatchOperation::Remove { path: "/tags/1".to_owned() },
+///     ]
+/// );
+///
+/// let mut c = a.clone();
+/// c.apply(&patch).unwrap();
+/// assert_eq!(c, b);
+/// ```
+pub fn diff(a: &Value, b: &Value) -> Patch {
+    let mut patch = Patch::new();
+    let mut path = String::new();
+    diff_into(&mut patch.0, &mut path, a, b);
+    patch
+}
+
+fn diff_into(ops: &mut Vec<PatchOperation>, path: &mut String, a: &Value, b: &Value) {
+    match (a, b) {
+        (Value::Object(a), Value::Object(b)) => diff_object(ops, path, a, b),
+        (Value::Array(a), Value::Array(b)) => diff_array(ops, path, a, b),
+        _ if a == b => {}
+        _ => ops.push(PatchOperation::Replace {
+            path: path.clone(),
+            value: b.clone(),
+        }),
+    }
+}
+
+fn diff_object(
+    ops: &mut Vec<PatchOperation>,
+    path: &mut String,
+    a: &Map<String, Value>,
+    b: &Map<String, Value>,
+) {
+    let len = path.len();
+    for (key, a_value) in a {
+        push_token(path, key);
+        match b.get(key) {
+            Some(b_value) => diff_into(ops, path, a_value, b_value),
+            None => ops.push(PatchOperation::Remove { path: path.clone() }),
+        }
+        path.truncate(len);
+    }
+    for (key, b_value) in b {
+        if !a.contains_key(key) {
+            push_token(path, key);
+            ops.push(PatchOperation::Add {
+                path: path.clone(),
+                value: b_value.clone(),
+            });
+            path.truncate(len);
+        }
+    }
+}
+
+fn diff_array(ops: &mut Vec<PatchOperation>, path: &mut String, a: &[Value], b: &[Value]) {
+    let len = path.len();
+    for (i, (a_value, b_value)) in a.iter().zip(b).enumerate() {
+        push_index(path, i);
+        diff_into(ops, path, a_value, b_value);
+        path.truncate(len);
+    }
+    // Remove from the back, so earlier indices stay valid.
+    for i in (b.len()..a.len()).rev() {
+        push_index(path, i);
+        ops.push(PatchOperation::Remove { path: path.clone() });
+        path.truncate(len);
+    }
+    for (i, b_value) in b.iter().enumerate().skip(a.len()) {
+        push_index(path, i);
+        ops.push(PatchOperation::Add {
+            path: path.clone(),
+            value: b_value.clone(),
+        });
+        path.truncate(len);
+    }
+}
+
+fn push_index(path: &mut String, index: usize) {
+    path.push('/');
+    path.push_str(itoa::Buffer::new().format(index));
+}
+
+/// Splits a pointer into its parent pointer and the unescaped last token.
+fn split_pointer(pointer: &str) -> Option<(&str, String)> {
+    let pos = pointer.rfind('/')?;
+    let token = pointer[pos + 1..].replace("~1", "/").replace("~0", "~");
+    Some((&pointer[..pos], token))
+}
+
+impl Value {
+    /// Applies an [RFC 7386](https://tools.ietf.org/html/rfc7386) merge patch.
+    ///
+    /// If `patch` is a map, its keys are merged into `self` recursively: a
+    /// `Null` value removes the key, any other value is merged into the
+    /// existing one. If `self` is not a map it is replaced by an empty one
+    /// first. Any other `patch` replaces `self` as a whole.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_encom::encom_from_json;
+    /// #
+    /// let mut config = encom_from_json!({
+    ///     "title": "Goodbye!",
+    ///     "author": { "givenName": "John", "familyName": "Doe" },
+    ///     "tags": ["example", "sample"]
+    /// });
+    ///
+    /// config.merge(&encom_from_json!({
+    ///     "title": "Hello!",
+    ///     "author": { "familyName": null },
+    ///     "tags": ["example"]
+    /// }));
+    ///
+    /// assert_eq!(config, encom_from_json!({
+    ///     "title": "Hello!",
+    ///     "author": { "givenName": "John" },
+    ///     "tags": ["example"]
+    /// }));
+    /// ```
+    pub fn merge(&mut self, patch: &Value) {
+        let patch = match patch {
+            Value::Object(patch) => patch,
+            _ => {
+                *self = patch.clone();
+                return;
+            }
+        };
+        if !self.is_object() {
+            *self = Value::Object(Map::new());
+        }
+        if let Value::Object(map) = self {
+            for (key, value) in patch {
+                if value.is_null() {
+                    map.remove(key);
+                } else {
+                    map.entry(key.as_str()).or_insert(Value::Null).merge(value);
+                }
+            }
+        }
+    }
+
+    /// Applies the operations of a [`Patch`] in order.
+    ///
+    /// Fails if an operation is addressed to a value that doesn't exist. In
+    /// that case the operations preceding the failed one stay applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_encom::encom_from_json;
+    /// use serde_encom::value::{Patch, PatchOperation};
+    ///
+    /// let mut v = encom_from_json!({ "list": [1, 3] });
+    /// let patch = Patch(vec![PatchOperation::Add {
+    ///     path: "/list/1".to_owned(),
+    ///     value: 2.into(),
+    /// }]);
+    ///
+    /// v.apply(&patch).unwrap();
+    /// assert_eq!(v, encom_from_json!({ "list": [1, 2, 3] }));
+    /// ```
+    pub fn apply(&mut self, patch: &Patch) -> Result<(), Error> {
+        for op in patch {
+            match op {
+                PatchOperation::Add { path, value } => self.apply_add(path, value)?,
+                PatchOperation::Remove { path } => self.apply_remove(path)?,
+                PatchOperation::Replace { path, value } => match self.pointer_mut(path) {
+                    Some(target) => *target = value.clone(),
+                    None => return Err(missing(path)),
+                },
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_add(&mut self, path: &str, value: &Value) -> Result<(), Error> {
+        if path.is_empty() {
+            *self = value.clone();
+            return Ok(());
+        }
+        let (parent, token) = split_pointer(path).ok_or_else(|| missing(path))?;
+        match self.pointer_mut(parent) {
+            Some(Value::Object(map)) => {
+                map.insert(token, value.clone());
+            }
+            Some(Value::Array(list)) => {
+                let index = if token == "-" {
+                    list.len()
+                } else {
+                    match super::parse_index(&token) {
+                        Some(index) if index <= list.len() => index,
+                        _ => return Err(missing(path)),
+                    }
+                };
+                list.insert(index, value.clone());
+            }
+            _ => return Err(missing(path)),
+        }
+        Ok(())
+    }
+
+    fn apply_remove(&mut self, path: &str) -> Result<(), Error> {
+        if path.is_empty() {
+            self.take();
+            return Ok(());
+        }
+        let (parent, token) = split_pointer(path).ok_or_else(|| missing(path))?;
+        let removed = match self.pointer_mut(parent) {
+            Some(Value::Object(map)) => map.remove(&token).is_some(),
+            Some(Value::Array(list)) => match super::parse_index(&token) {
+                Some(index) if index < list.len() => {
+                    list.remove(index);
+                    true
+                }
+                _ => false,
+            },
+            _ => false,
+        };
+        if removed {
+            Ok(())
+        } else {
+            Err(missing(path))
+        }
+    }
+}
+
+#[cold]
+fn missing(path: &str) -> Error {
+    Error::custom(format!("patch target `{}` does not exist", path))
+}
@@ -0,0 +1,724 @@
+//! JSONPath-like queries over `Value`.
+//!
+//! A query is a sequence of segments applied to the root value `$`, which
+//! can be omitted:
+//!
+//! - `.name` or `['name']` selects a map member,
+//! - `.*` or `[*]` selects every member of a map or every element of an array,
+//! - `[0]`, `[-1]` select array elements, counting from the end if negative,
+//! - `[start:end:step]` selects a range of array elements, each bound being
+//!   optional,
+//! - `[a, 'b', 1:3]` selects the union of several selectors,
+//! - `[?(predicate)]` selects the children for which the predicate holds,
+//! - `..` before any of the above applies it to every descendant as well.
+//!
+//! Predicates compare paths relative to the current child (`@.name`,
+//! `@[0]`) or to the root (`$.name`) with literals (`1`, `-2.5`, `'str'`,
+//! `"str"`, `true`, `false`, `null`) using `==`, `!=`, `<`, `<=`, `>` and
+//! `>=`. A bare path tests for existence. Predicates can be combined with
+//! `&&`, `||`, `!` and parentheses.
+//!
+//! Matches are returned in document order.
+
+use super::Value;
+use crate::error::Error;
+use alloc::{boxed::Box, format, string::String, vec::Vec};
+use core::{cmp::Ordering, str::FromStr};
+use serde::de::Error as _;
+
+/// A compiled query, see the [module documentation](crate::value::query) for the syntax.
+///
+/// Parsing a query once and reusing it avoids parsing it again for every
+/// document.
+///
+/// # Examples
+///
+/// ```
+/// # use serde_encom::encom_from_json;
+/// use serde_encom::value::Query;
+///
+/// let data = encom_from_json!({
+///     "admins": [
+///         { "balance": 10.4, "folder": "main" },
+///         { "balance": -2.3, "folder": "other" },
+///         { "balance": 7.5, "folder": "main" }
+///     ]
+/// });
+///
+/// let query = Query::parse("$.admins[?(@.folder == 'main')].balance").unwrap();
+/// assert_eq!(query.select(&data), [&10.4, &7.5]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Query {
+    segments: Vec<Segment>,
+    uses_root: bool,
+}
+
+#[derive(Clone, Debug)]
+struct Segment {
+    descendant: bool,
+    selectors: Vec<Selector>,
+}
+
+#[derive(Clone, Debug)]
+enum Selector {
+    Name(String),
+    Wildcard,
+    Index(i64),
+    Slice(Option<i64>, Option<i64>, i64),
+    Filter(Expr),
+}
+
+#[derive(Clone, Debug)]
+enum Expr {
+    Or(Box<Expr>, Box<Expr>),
+    And(Box<Expr>, Box<Expr>),
+    Not(Box<Expr>),
+    Exists(Operand),
+    Compare(Operand, CmpOp, Operand),
+}
+
+#[derive(Clone, Debug)]
+enum Operand {
+    Current(Vec<PathToken>),
+    Root(Vec<PathToken>),
+    Literal(Value),
+}
+
+#[derive(Clone, Debug)]
+enum PathToken {
+    Name(String),
+    Index(i64),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum CmpOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+#[derive(Clone, Copy)]
+enum Key<'a> {
+    Name(&'a str),
+    Index(usize),
+}
+
+impl Query {
+    /// Compiles a query.
+    ///
+    /// Fails if the query is not syntactically valid.
+    pub fn parse(query: &str) -> Result<Query, Error> {
+        Parser {
+            input: query.as_bytes(),
+            query,
+            index: 0,
+            uses_root: false,
+        }
+        .parse_query()
+    }
+
+    /// Returns the values matched by the query, each one before the values
+    /// matched inside it.
+    pub fn select<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let mut current = Vec::from([value]);
+        for segment in &self.segments {
+            let mut next = Vec::new();
+            for node in current {
+                segment.select(node, value, &mut next);
+            }
+            current = next;
+        }
+        current
+    }
+
+    /// Returns mutable references to the values matched by the query.
+    ///
+    /// Mutable references can't overlap, so a recursive descent (`..`) doesn't
+    /// look inside a value it has already matched. Filters referring to the
+    /// root (`$`) are evaluated against a copy of the value taken before the
+    /// query runs.
+    pub fn select_mut<'a>(&self, value: &'a mut Value) -> Vec<&'a mut Value> {
+        let root_copy;
+        let root = if self.uses_root {
+            root_copy = value.clone();
+            &root_copy
+        } else {
+            &Value::Null
+        };
+
+        let mut current = Vec::from([value]);
+        for segment in &self.segments {
+            let mut next = Vec::new();
+            for node in current {
+                segment.select_mut(node, root, &mut next);
+            }
+            current = next;
+        }
+        current
+    }
+}
+
+impl FromStr for Query {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Query, Error> {
+        Query::parse(s)
+    }
+}
+
+impl Segment {
+    fn select<'a>(&self, node: &'a Value, root: &Value, out: &mut Vec<&'a Value>) {
+        match node {
+            Value::Object(map) => {
+                for (key, child) in map {
+                    if self.matches(Key::Name(key), 0, child, root) {
+                        out.push(child);
+                    }
+                    if self.descendant {
+                        self.select(child, root, out);
+                    }
+                }
+            }
+            Value::Array(list) => {
+                for (i, child) in list.iter().enumerate() {
+                    if self.matches(Key::Index(i), list.len(), child, root) {
+                        out.push(child);
+                    }
+                    if self.descendant {
+                        self.select(child, root, out);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn select_mut<'a>(&self, node: &'a mut Value, root: &Value, out: &mut Vec<&'a mut Value>) {
+        match node {
+            Value::Object(map) => {
+                for (key, child) in map.iter_mut() {
+                    if self.matches(Key::Name(key), 0, child, root) {
+                        out.push(child);
+                    } else if self.descendant {
+                        self.select_mut(child, root, out);
+                    }
+                }
+            }
+            Value::Array(list) => {
+                let len = list.len();
+                for (i, child) in list.iter_mut().enumerate() {
+                    if self.matches(Key::Index(i), len, child, root) {
+                        out.push(child);
+                    } else if self.descendant {
+                        self.select_mut(child, root, out);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn matches(&self, key: Key, len: usize, child: &Value, root: &Value) -> bool {
+        self.selectors
+            .iter()
+            .any(|selector| selector.matches(key, len, child, root))
+    }
+}
+
+impl Selector {
+    fn matches(&self, key: Key, len: usize, child: &Value, root: &Value) -> bool {
+        match (self, key) {
+            (Selector::Wildcard, _) => true,
+            (Selector::Filter(expr), _) => expr.eval(child, root),
+            (Selector::Name(name), Key::Name(key)) => name == key,
+            (Selector::Index(index), Key::Index(i)) => normalize(*index, len) == Some(i),
+            (Selector::Slice(start, end, step), Key::Index(i)) => {
+                let len = len as i64;
+                let bound = |b: i64| if b < 0 { (len + b).max(0) } else { b.min(len) };
+                let start = start.map_or(0, bound);
+                let end = end.map_or(len, bound);
+                let i = i as i64;
+                start <= i && i < end && (i - start) % step == 0
+            }
+            _ => false,
+        }
+    }
+}
+
+fn normalize(index: i64, len: usize) -> Option<usize> {
+    if index < 0 {
+        len.checked_sub(index.unsigned_abs() as usize)
+    } else {
+        Some(index as usize)
+    }
+}
+
+impl Expr {
+    fn eval(&self, current: &Value, root: &Value) -> bool {
+        match self {
+            Expr::Or(a, b) => a.eval(current, root) || b.eval(current, root),
+            Expr::And(a, b) => a.eval(current, root) && b.eval(current, root),
+            Expr::Not(a) => !a.eval(current, root),
+            Expr::Exists(operand) => operand.resolve(current, root).is_some(),
+            Expr::Compare(a, op, b) => match (a.resolve(current, root), b.resolve(current, root)) {
+                (Some(a), Some(b)) => op.eval(a, b),
+                _ => false,
+            },
+        }
+    }
+}
+
+impl Operand {
+    fn resolve<'a>(&'a self, current: &'a Value, root: &'a Value) -> Option<&'a Value> {
+        let (start, path) = match self {
+            Operand::Current(path) => (current, path),
+            Operand::Root(path) => (root, path),
+            Operand::Literal(value) => return Some(value),
+        };
+        path.iter()
+            .try_fold(start, |target, token| match (target, token) {
+                (Value::Object(map), PathToken::Name(name)) => map.get(name),
+                (Value::Array(list), PathToken::Index(index)) => {
+                    normalize(*index, list.len()).and_then(|i| list.get(i))
+                }
+                _ => None,
+            })
+    }
+}
+
+impl CmpOp {
+    fn eval(self, a: &Value, b: &Value) -> bool {
+        let ordering = match (a, b) {
+            (Value::Number(a), Value::Number(b)) => match (a.as_f64(), b.as_f64()) {
+                (Some(a), Some(b)) => a.partial_cmp(&b),
+                _ => None,
+            },
+            (Value::String(a), Value::String(b)) => Some(a.cmp(b)),
+            _ => None,
+        };
+        match self {
+            CmpOp::Eq => ordering.map_or(a == b, Ordering::is_eq),
+            CmpOp::Ne => !ordering.map_or(a == b, Ordering::is_eq),
+            CmpOp::Lt => ordering == Some(Ordering::Less),
+            CmpOp::Le => matches!(ordering, Some(Ordering::Less | Ordering::Equal)),
+            CmpOp::Gt => ordering == Some(Ordering::Greater),
+            CmpOp::Ge => matches!(ordering, Some(Ordering::Greater | Ordering::Equal)),
+        }
+    }
+}
+
+impl Value {
+    /// Returns the values matched by a JSONPath-like query, see
+    /// [`Query`] for the syntax.
+    ///
+    /// An invalid query matches nothing. Use [`Value::try_query`] to get the
+    /// error, or [`Query::parse`] to reuse the compiled query for several
+    /// documents.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_encom::encom_from_json;
+    /// #
+    /// let data = encom_from_json!({
+    ///     "a": { "id": 1 },
+    ///     "b": [{ "id": 2 }, { "id": 3 }]
+    /// });
+    ///
+    /// assert_eq!(data.query("$..id"), [&1, &2, &3]);
+    /// assert_eq!(data.query("$.b[-1:].id"), [&3]);
+    /// ```
+    pub fn query(&self, query: &str) -> Vec<&Value> {
+        self.try_query(query).unwrap_or_default()
+    }
+
+    /// Returns mutable references to the values matched by a JSONPath-like
+    /// query, see [`Query::select_mut`].
+    ///
+    /// An invalid query matches nothing, see [`Value::try_query_mut`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_encom::encom_from_json;
+    /// #
+    /// let mut data = encom_from_json!({ "items": [{ "n": 1 }, { "n": 5 }] });
+    ///
+    /// for n in data.query_mut("$.items[?(@.n > 2)].n") {
+    ///     *n = 2.into();
+    /// }
+    /// assert_eq!(data, encom_from_json!({ "items": [{ "n": 1 }, { "n": 2 }] }));
+    /// ```
+    pub fn query_mut(&mut self, query: &str) -> Vec<&mut Value> {
+        self.try_query_mut(query).unwrap_or_default()
+    }
+
+    /// Like [`Value::query`], but reports an invalid query instead of
+    /// matching nothing, so that a typo isn't taken for a query without
+    /// matches.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the query is not syntactically valid.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use serde_encom::encom_from_json;
+    /// #
+    /// let data = encom_from_json!({ "b": [{ "id": 2 }, { "id": 3 }] });
+    ///
+    /// assert_eq!(data.try_query("$.b[-1:].id").unwrap(), [&3]);
+    /// assert!(data.try_query("$.b[-1:").is_err());
+    /// ```
+    pub fn try_query(&self, query: &str) -> Result<Vec<&Value>, Error> {
+        Query::parse(query).map(|query| query.select(self))
+    }
+
+    /// Like [`Value::query_mut`], but reports an invalid query instead of
+    /// matching nothing.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the query is not syntactically valid.
+    pub fn try_query_mut(&mut self, query: &str) -> Result<Vec<&mut Value>, Error> {
+        Query::parse(query).map(|query| query.select_mut(self))
+    }
+}
+
+struct Parser<'a> {
+    input: &'a [u8],
+    query: &'a str,
+    index: usize,
+    uses_root: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn parse_query(mut self) -> Result<Query, Error> {
+        self.skip_whitespace();
+        let mut segments = Vec::new();
+        if !self.eat(b'$') && !matches!(self.peek(), None | Some(b'.' | b'[')) {
+            // A leading name without `$.` prefix, e.g. `admins[0]`.
+            segments.push(Segment {
+                descendant: false,
+                selectors: Vec::from([self.parse_dot_selector()?]),
+            });
+        }
+        loop {
+            self.skip_whitespace();
+            let segment = match self.peek() {
+                None => break,
+                Some(b'.') => {
+                    self.index += 1;
+                    if self.eat(b'.') {
+                        let selectors = if self.peek() == Some(b'[') {
+                            self.parse_bracket()?
+                        } else {
+                            Vec::from([self.parse_dot_selector()?])
+                        };
+                        Segment {
+                            descendant: true,
+                            selectors,
+                        }
+                    } else {
+                        Segment {
+                            descendant: false,
+                            selectors: Vec::from([self.parse_dot_selector()?]),
+                        }
+                    }
+                }
+                Some(b'[') => Segment {
+                    descendant: false,
+                    selectors: self.parse_bracket()?,
+                },
+                Some(_) => return Err(self.error("expected `.` or `[`")),
+            };
+            segments.push(segment);
+        }
+        Ok(Query {
+            segments,
+            uses_root: self.uses_root,
+        })
+    }
+
+    fn parse_dot_selector(&mut self) -> Result<Selector, Error> {
+        if self.eat(b'*') {
+            Ok(Selector::Wildcard)
+        } else {
+            self.parse_name().map(Selector::Name)
+        }
+    }
+
+    fn parse_bracket(&mut self) -> Result<Vec<Selector>, Error> {
+        self.expect(b'[')?;
+        let mut selectors = Vec::new();
+        loop {
+            self.skip_whitespace();
+            selectors.push(self.parse_bracket_selector()?);
+            self.skip_whitespace();
+            if self.eat(b']') {
+                return Ok(selectors);
+            }
+            self.expect(b',')?;
+        }
+    }
+
+    fn parse_bracket_selector(&mut self) -> Result<Selector, Error> {
+        match self.peek() {
+            Some(b'*') => {
+                self.index += 1;
+                Ok(Selector::Wildcard)
+            }
+            Some(b'\'' | b'"') => self.parse_string().map(Selector::Name),
+            Some(b'?') => {
+                self.index += 1;
+                self.skip_whitespace();
+                self.parse_or().map(Selector::Filter)
+            }
+            _ => {
+                let start = self.parse_opt_int()?;
+                self.skip_whitespace();
+                if !self.eat(b':') {
+                    return start
+                        .map(Selector::Index)
+                        .ok_or_else(|| self.error("expected selector"));
+                }
+                self.skip_whitespace();
+                let end = self.parse_opt_int()?;
+                self.skip_whitespace();
+                let step = if self.eat(b':') {
+                    self.skip_whitespace();
+                    self.parse_opt_int()?.unwrap_or(1)
+                } else {
+                    1
+                };
+                if step <= 0 {
+                    return Err(self.error("slice step must be positive"));
+                }
+                Ok(Selector::Slice(start, end, step))
+            }
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<Expr, Error> {
+        let mut expr = self.parse_and()?;
+        while self.eat_str("||") {
+            let rhs = self.parse_and()?;
+            expr = Expr::Or(Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<Expr, Error> {
+        let mut expr = self.parse_unary()?;
+        while self.eat_str("&&") {
+            let rhs = self.parse_unary()?;
+            expr = Expr::And(Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn parse_unary(&mut self) -> Result<Expr, Error> {
+        self.skip_whitespace();
+        if self.eat(b'!') {
+            return Ok(Expr::Not(Box::new(self.parse_unary()?)));
+        }
+        if self.eat(b'(') {
+            let expr = self.parse_or()?;
+            self.skip_whitespace();
+            self.expect(b')')?;
+            return Ok(expr);
+        }
+
+        let lhs = self.parse_operand()?;
+        self.skip_whitespace();
+        let op = if self.eat_str("==") {
+            CmpOp::Eq
+        } else if self.eat_str("!=") {
+            CmpOp::Ne
+        } else if self.eat_str("<=") {
+            CmpOp::Le
+        } else if self.eat_str(">=") {
+            CmpOp::Ge
+        } else if self.eat(b'<') {
+            CmpOp::Lt
+        } else if self.eat(b'>') {
+            CmpOp::Gt
+        } else {
+            return match lhs {
+                Operand::Literal(_) => Err(self.error("expected comparison")),
+                lhs => Ok(Expr::Exists(lhs)),
+            };
+        };
+        self.skip_whitespace();
+        let rhs = self.parse_operand()?;
+        Ok(Expr::Compare(lhs, op, rhs))
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, Error> {
+        match self.peek() {
+            Some(b'@') => {
+                self.index += 1;
+                self.parse_path().map(Operand::Current)
+            }
+            Some(b'$') => {
+                self.index += 1;
+                self.uses_root = true;
+                self.parse_path().map(Operand::Root)
+            }
+            Some(b'\'' | b'"') => self
+                .parse_string()
+                .map(|s| Operand::Literal(Value::String(s))),
+            Some(b't') if self.eat_str("true") => Ok(Operand::Literal(Value::Bool(true))),
+            Some(b'f') if self.eat_str("false") => Ok(Operand::Literal(Value::Bool(false))),
+            Some(b'n') if self.eat_str("null") => Ok(Operand::Literal(Value::Null)),
+            _ => self.parse_number().map(Operand::Literal),
+        }
+    }
+
+    fn parse_path(&mut self) -> Result<Vec<PathToken>, Error> {
+        let mut path = Vec::new();
+        loop {
+            if self.eat(b'.') {
+                path.push(PathToken::Name(self.parse_name()?));
+            } else if self.eat(b'[') {
+                self.skip_whitespace();
+                let token = match self.peek() {
+                    Some(b'\'' | b'"') => PathToken::Name(self.parse_string()?),
+                    _ => match self.parse_opt_int()? {
+                        Some(index) => PathToken::Index(index),
+                        None => return Err(self.error("expected name or index")),
+                    },
+                };
+                self.skip_whitespace();
+                self.expect(b']')?;
+                path.push(token);
+            } else {
+                return Ok(path);
+            }
+        }
+    }
+
+    fn parse_name(&mut self) -> Result<String, Error> {
+        let start = self.index;
+        while let Some(c) = self.peek() {
+            if c <= b' ' || b".[]()=!<>&|,'\"".contains(&c) {
+                break;
+            }
+            self.index += 1;
+        }
+        if start == self.index {
+            return Err(self.error("expected name"));
+        }
+        Ok(String::from(&self.query[start..self.index]))
+    }
+
+    fn parse_string(&mut self) -> Result<String, Error> {
+        let quote = self.input[self.index];
+        self.index += 1;
+        let mut s = String::new();
+        let mut start = self.index;
+        loop {
+            match self.peek() {
+                None => return Err(self.error("unterminated string")),
+                Some(c) if c == quote => {
+                    s.push_str(&self.query[start..self.index]);
+                    self.index += 1;
+                    return Ok(s);
+                }
+                Some(b'\\') => {
+                    s.push_str(&self.query[start..self.index]);
+                    self.index += 1;
+                    match self.peek() {
+                        Some(c @ (b'\\' | b'\'' | b'"')) => s.push(c as char),
+                        _ => return Err(self.error("invalid escape")),
+                    }
+                    self.index += 1;
+                    start = self.index;
+                }
+                Some(_) => self.index += 1,
+            }
+        }
+    }
+
+    fn parse_opt_int(&mut self) -> Result<Option<i64>, Error> {
+        let start = self.index;
+        self.eat(b'-');
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.index += 1;
+        }
+        if start == self.index {
+            return Ok(None);
+        }
+        self.query[start..self.index]
+            .parse()
+            .map(Some)
+            .map_err(|_| self.error("invalid index"))
+    }
+
+    fn parse_number(&mut self) -> Result<Value, Error> {
+        let start = self.index;
+        while matches!(
+            self.peek(),
+            Some(b'0'..=b'9' | b'-' | b'+' | b'.' | b'e' | b'E')
+        ) {
+            self.index += 1;
+        }
+        let s = &self.query[start..self.index];
+        if let Ok(n) = s.parse::<u64>() {
+            Ok(n.into())
+        } else if let Ok(n) = s.parse::<i64>() {
+            Ok(n.into())
+        } else {
+            s.parse::<f64>()
+                .map(Value::from)
+                .map_err(|_| self.error("expected value"))
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.index += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.input.get(self.index).copied()
+    }
+
+    fn eat(&mut self, c: u8) -> bool {
+        if self.peek() == Some(c) {
+            self.index += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn eat_str(&mut self, s: &str) -> bool {
+        self.skip_whitespace();
+        if self.input[self.index..].starts_with(s.as_bytes()) {
+            self.index += s.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), Error> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected `{}`", c as char)))
+        }
+    }
+
+    #[cold]
+    fn error(&self, msg: &str) -> Error {
+        Error::custom(format!("invalid query at position {}: {}", self.index, msg))
+    }
+}
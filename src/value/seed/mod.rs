@@ -1,5 +1,5 @@
 mod bytes;
+mod number;
 mod str;
-mod u64;
 
-pub(crate) use self::{bytes::BytesSeed, str::StrSeed, u64::U64Seed};
+pub(crate) use self::{bytes::BytesSeed, number::NumberSeed, str::StrSeed};
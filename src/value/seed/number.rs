@@ -0,0 +1,18 @@
+use crate::{Value, ValueVisitor};
+use core::result::Result;
+use serde::de::DeserializeSeed;
+
+/// Reads the elements after a number. They go through `deserialize_any`,
+/// since an integer beyond 64 bits can follow one that fits.
+pub(crate) struct NumberSeed;
+
+impl<'de> DeserializeSeed<'de> for NumberSeed {
+    type Value = Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
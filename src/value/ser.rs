@@ -1,5 +1,8 @@
 use super::{to_value, Map, Value};
-use crate::error::{Error, ErrorCode, Result};
+use crate::{
+    error::{Error, ErrorCode, Result},
+    ser::TextEmitter,
+};
 use alloc::{
     borrow::ToOwned,
     string::{String, ToString},
@@ -20,6 +23,7 @@ impl Serialize for Value {
             Value::Number(n) => n.serialize(serializer),
             Value::String(s) => serializer.serialize_str(s),
             Value::Bytes(b) => serializer.serialize_bytes(b),
+            Value::DateTime(dt) => dt.serialize(serializer),
             Value::Array(v) => v.serialize(serializer),
             #[cfg(any(feature = "std", feature = "alloc"))]
             Value::Object(m) => {
@@ -93,7 +97,7 @@ impl serde::Serializer for Serializer {
         Ok(Value::Number(value.into()))
     }
 
-    #[cfg(feature = "arbitrary_precision")]
+    #[inline]
     fn serialize_i128(self, value: i128) -> Result<Value> {
         Ok(Value::Number(value.into()))
     }
@@ -118,20 +122,9 @@ impl serde::Serializer for Serializer {
         Ok(Value::Number(value.into()))
     }
 
+    #[inline]
     fn serialize_u128(self, value: u128) -> Result<Value> {
-        #[cfg(feature = "arbitrary_precision")]
-        {
-            Ok(Value::Number(value.into()))
-        }
-
-        #[cfg(not(feature = "arbitrary_precision"))]
-        {
-            if let Ok(value) = u64::try_from(value) {
-                Ok(Value::Number(value.into()))
-            } else {
-                Err(Error::syntax(ErrorCode::NumberOutOfRange, 0, 0))
-            }
-        }
+        Ok(Value::Number(value.into()))
     }
 
     #[inline]
@@ -182,10 +175,15 @@ impl serde::Serializer for Serializer {
     }
 
     #[inline]
-    fn serialize_newtype_struct<T>(self, _name: &'static str, value: &T) -> Result<Value>
+    fn serialize_newtype_struct<T>(self, name: &'static str, value: &T) -> Result<Value>
     where
         T: ?Sized + Serialize,
     {
+        if name == crate::datetime::TOKEN {
+            return value.serialize(TextEmitter::new(ErrorCode::InvalidDateTime, |text| {
+                text.parse().map(Value::DateTime)
+            }));
+        }
         value.serialize(self)
     }
 
@@ -514,6 +512,11 @@ impl serde::Serializer for MapKeySerializer {
         Ok(value.to_string())
     }
 
+    #[inline]
+    fn serialize_i128(self, value: i128) -> Result<String> {
+        Ok(value.to_string())
+    }
+
     #[inline]
     fn serialize_u8(self, value: u8) -> Result<String> {
         Ok(value.to_string())
@@ -534,6 +537,11 @@ impl serde::Serializer for MapKeySerializer {
         Ok(value.to_string())
     }
 
+    #[inline]
+    fn serialize_u128(self, value: u128) -> Result<String> {
+        Ok(value.to_string())
+    }
+
     fn serialize_f32(self, value: f32) -> Result<String> {
         if value.is_finite() {
             Ok(ryu::Buffer::new().format_finite(value).to_owned())
@@ -0,0 +1,522 @@
+//! Adapters for fields that need a different encoding than their `Serialize`
+//! impl, for use with `#[serde(with = "serde_encom::with::...")]`.
+//!
+//! - [`bytes`] writes `Vec<u8>` and other byte buffers as EnCom bytes
+//!   instead of an array of numbers, like `serde_bytes`.
+//! - [`display_fromstr`] writes a value as a length-prefixed string with
+//!   `Display` and reads it with `FromStr`, so `u128`, decimals and big
+//!   integers aren't rounded through `f64`.
+//! - [`duration`] writes a `Duration` as its number of nanoseconds.
+//! - [`system_time`] writes a `SystemTime` as nanoseconds since the Unix
+//!   epoch.
+//! - [`pairs`] writes a map as an array of `[key value]` arrays, so that the
+//!   keys can be of any type.
+//! - `chrono` and `time`, with the features of the same name, write
+//!   `chrono::DateTime` and `time::OffsetDateTime` as EnCom date-times.
+//! - [`rust_decimal`], with the feature of the same name, writes a
+//!   `rust_decimal::Decimal` as an EnCom number with its exact digits.
+//!
+//! The adapters work with other formats too, and read back what they write
+//! through a [`Value`](crate::Value).
+//!
+//! # Example
+//!
+//! ```
+//! use serde::{Deserialize, Serialize};
+//! use std::collections::BTreeMap;
+//! use std::time::Duration;
+//!
+//! #[derive(Debug, PartialEq, Serialize, Deserialize)]
+//! struct Job {
+//!     #[serde(with = "serde_encom::with::bytes")]
+//!     hash: Vec<u8>,
+//!     #[serde(with = "serde_encom::with::display_fromstr")]
+//!     budget: u128,
+//!     #[serde(with = "serde_encom::with::duration")]
+//!     timeout: Duration,
+//!     #[serde(with = "serde_encom::with::pairs")]
+//!     retries: BTreeMap<u16, String>,
+//! }
+//!
+//! let job = Job {
+//!     hash: vec![0xca, 0xfe],
+//!     budget: u128::MAX,
+//!     timeout: Duration::from_millis(1500),
+//!     retries: BTreeMap::from([(503, "later".to_owned())]),
+//! };
+//! let encom = serde_encom::to_vec(&job).unwrap();
+//! assert_eq!(
+//!     encom,
+//!     b"hash:2~\xca\xfe budget:39=340282366920938463463374607431768211455 \
+//!       timeout:1500000000 retries[[503 5=later]]",
+//! );
+//! assert_eq!(serde_encom::from_slice::<Job>(&encom).unwrap(), job);
+//! ```
+
+use core::fmt;
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serializer;
+
+/// Serialize and deserialize byte buffers as EnCom bytes.
+///
+/// Serializes anything that is `AsRef<[u8]>`, and deserializes anything that
+/// is `From<Vec<u8>>`, such as `Vec<u8>` and `Box<[u8]>`. Besides bytes, a
+/// string is read as well, and so is an array of numbers in a
+/// [`Value`](crate::Value).
+#[cfg(feature = "alloc")]
+pub mod bytes {
+    use super::*;
+    use alloc::vec::Vec;
+
+    /// Serializes `value` as bytes.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + AsRef<[u8]>,
+        S: Serializer,
+    {
+        serializer.serialize_bytes(value.as_ref())
+    }
+
+    /// Deserializes bytes.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: From<Vec<u8>>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_byte_buf(BytesVisitor).map(T::from)
+    }
+
+    struct BytesVisitor;
+
+    impl<'de> Visitor<'de> for BytesVisitor {
+        type Value = Vec<u8>;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("bytes")
+        }
+
+        fn visit_bytes<E>(self, v: &[u8]) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(v.to_vec())
+        }
+
+        fn visit_byte_buf<E>(self, v: Vec<u8>) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(v)
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            Ok(v.as_bytes().to_vec())
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0).min(4096));
+            while let Some(byte) = seq.next_element()? {
+                bytes.push(byte);
+            }
+            Ok(bytes)
+        }
+    }
+}
+
+/// Serialize a value as a string with `Display`, and deserialize it with
+/// `FromStr`.
+///
+/// EnCom strings are length-prefixed, so the value is read back exactly as
+/// it was written: this keeps `u128`, `rust_decimal::Decimal` or big
+/// integers lossless, including through a [`Value`](crate::Value).
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, Serialize, Deserialize)]
+/// struct Account {
+///     #[serde(with = "serde_encom::with::display_fromstr")]
+///     balance: i128,
+/// }
+///
+/// let account: Account = serde_encom::from_str("balance:4=-100").unwrap();
+/// assert_eq!(account.balance, -100);
+///
+/// let err = serde_encom::from_str::<Account>("balance:3=1e3").unwrap_err();
+/// assert!(err.is_data());
+/// ```
+pub mod display_fromstr {
+    use super::*;
+    use core::fmt::Display;
+    use core::marker::PhantomData;
+    use core::str::FromStr;
+
+    /// Serializes `value` as a string.
+    pub fn serialize<T, S>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        T: ?Sized + Display,
+        S: Serializer,
+    {
+        serializer.collect_str(value)
+    }
+
+    /// Deserializes a string and parses it.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: FromStr,
+        T::Err: Display,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_str(FromStrVisitor(PhantomData))
+    }
+
+    struct FromStrVisitor<T>(PhantomData<T>);
+
+    impl<'de, T> Visitor<'de> for FromStrVisitor<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("a string")
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: de::Error,
+        {
+            v.parse().map_err(E::custom)
+        }
+    }
+}
+
+/// Serialize and deserialize a `Duration` as its number of nanoseconds.
+///
+/// Nanoseconds that don't fit in a `u64`, which is over 584 years, are
+/// written as a `u128`.
+pub mod duration {
+    use super::*;
+    use core::time::Duration;
+    use serde::Deserialize;
+
+    const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+    /// Serializes `value` as nanoseconds.
+    pub fn serialize<S>(value: &Duration, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let nanos = value.as_nanos();
+        match u64::try_from(nanos) {
+            Ok(nanos) => serializer.serialize_u64(nanos),
+            Err(_) => serializer.serialize_u128(nanos),
+        }
+    }
+
+    /// Deserializes nanoseconds.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        from_nanos(u128::deserialize(deserializer)?)
+    }
+
+    pub(super) fn from_nanos<E>(nanos: u128) -> Result<Duration, E>
+    where
+        E: de::Error,
+    {
+        let secs = u64::try_from(nanos / NANOS_PER_SEC)
+            .map_err(|_| E::custom("overflow deserializing Duration"))?;
+        Ok(Duration::new(secs, (nanos % NANOS_PER_SEC) as u32))
+    }
+}
+
+/// Serialize and deserialize a `SystemTime` as nanoseconds since the Unix
+/// epoch, negative for times before it.
+///
+/// Times that don't fit in an `i64`, which is before 1677 or after 2262,
+/// are written as an `i128`.
+#[cfg(feature = "std")]
+pub mod system_time {
+    use super::*;
+    use serde::{ser, Deserialize};
+    use std::time::{SystemTime, UNIX_EPOCH};
+
+    /// Serializes `value` as nanoseconds since the Unix epoch.
+    pub fn serialize<S>(value: &SystemTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let nanos = match value.duration_since(UNIX_EPOCH) {
+            Ok(after) => i128::try_from(after.as_nanos()),
+            Err(before) => i128::try_from(before.duration().as_nanos()).map(|nanos| -nanos),
+        }
+        .map_err(|_| ser::Error::custom("SystemTime out of range"))?;
+        match i64::try_from(nanos) {
+            Ok(nanos) => serializer.serialize_i64(nanos),
+            Err(_) => serializer.serialize_i128(nanos),
+        }
+    }
+
+    /// Deserializes nanoseconds since the Unix epoch.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<SystemTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let nanos = i128::deserialize(deserializer)?;
+        let offset = super::duration::from_nanos(nanos.unsigned_abs())?;
+        if nanos < 0 {
+            UNIX_EPOCH.checked_sub(offset)
+        } else {
+            UNIX_EPOCH.checked_add(offset)
+        }
+        .ok_or_else(|| de::Error::custom("overflow deserializing SystemTime"))
+    }
+}
+
+/// Serialize and deserialize a map as an array of `[key value]` arrays.
+///
+/// EnCom map keys are strings, so this is how to keep keys like numbers,
+/// tuples or enums with data. Serializes any map that can be iterated by
+/// reference, and deserializes any map that is `FromIterator`, such as
+/// `HashMap` and `BTreeMap`, where later pairs replace earlier ones with the
+/// same key.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use std::collections::HashMap;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Grid {
+///     #[serde(with = "serde_encom::with::pairs")]
+///     cells: HashMap<(i32, i32), char>,
+/// }
+///
+/// let grid: Grid = serde_encom::from_str("cells[[[0 -1] 1=x]]").unwrap();
+/// assert_eq!(grid.cells[&(0, -1)], 'x');
+/// ```
+pub mod pairs {
+    use super::*;
+    use core::iter;
+    use core::marker::PhantomData;
+    use serde::{Deserialize, Serialize};
+
+    /// Serializes the entries of `value` as `[key value]` arrays.
+    pub fn serialize<'a, M, K, V, S>(value: &'a M, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        &'a M: IntoIterator<Item = (&'a K, &'a V)>,
+        K: Serialize + 'a,
+        V: Serialize + 'a,
+        S: Serializer,
+    {
+        serializer.collect_seq(value)
+    }
+
+    /// Deserializes `[key value]` arrays into a map.
+    pub fn deserialize<'de, M, K, V, D>(deserializer: D) -> Result<M, D::Error>
+    where
+        M: FromIterator<(K, V)>,
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_seq(PairsVisitor(PhantomData))
+    }
+
+    struct PairsVisitor<M, K, V>(PhantomData<(M, K, V)>);
+
+    impl<'de, M, K, V> Visitor<'de> for PairsVisitor<M, K, V>
+    where
+        M: FromIterator<(K, V)>,
+        K: Deserialize<'de>,
+        V: Deserialize<'de>,
+    {
+        type Value = M;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+            formatter.write_str("an array of [key value] arrays")
+        }
+
+        fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+        where
+            A: SeqAccess<'de>,
+        {
+            // Collects until the first error, which is then returned instead.
+            let mut err = None;
+            let map = iter::from_fn(|| {
+                seq.next_element::<(K, V)>().unwrap_or_else(|e| {
+                    err = Some(e);
+                    None
+                })
+            })
+            .collect();
+            match err {
+                Some(err) => Err(err),
+                None => Ok(map),
+            }
+        }
+    }
+}
+
+/// Serialize and deserialize a `chrono::DateTime` as an EnCom date-time.
+///
+/// Works with any time zone, whose offset is written along with the time;
+/// on the way back it is any type that converts from a
+/// [`DateTime`](crate::DateTime), like `chrono::DateTime<Utc>` or
+/// `chrono::DateTime<FixedOffset>`. Years outside of 0000 to 9999, leap
+/// seconds and offsets that aren't whole minutes fail to serialize.
+///
+/// ```
+/// use chrono::{DateTime, Utc};
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Event {
+///     #[serde(with = "serde_encom::with::chrono")]
+///     at: DateTime<Utc>,
+/// }
+///
+/// let event = Event {
+///     at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+/// };
+/// let encom = serde_encom::to_string(&event).unwrap();
+/// assert_eq!(encom, "at:20@2023-11-14T22:13:20Z");
+/// assert_eq!(serde_encom::from_str::<Event>(&encom).unwrap(), event);
+/// ```
+#[cfg(feature = "chrono")]
+#[cfg_attr(docsrs, doc(cfg(feature = "chrono")))]
+pub mod chrono {
+    use super::*;
+    use crate::datetime::DateTime;
+    use ::chrono::TimeZone;
+    use serde::{ser, Deserialize, Serialize};
+
+    /// Serializes `value` as a date-time.
+    pub fn serialize<Tz, S>(
+        value: &::chrono::DateTime<Tz>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error>
+    where
+        Tz: TimeZone,
+        S: Serializer,
+    {
+        DateTime::try_from(value.clone())
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    /// Deserializes a date-time.
+    pub fn deserialize<'de, T, D>(deserializer: D) -> Result<T, D::Error>
+    where
+        T: From<DateTime>,
+        D: Deserializer<'de>,
+    {
+        DateTime::deserialize(deserializer).map(T::from)
+    }
+}
+
+/// Serialize and deserialize a `time::OffsetDateTime` as an EnCom date-time.
+///
+/// Years before 0000 and offsets that aren't whole minutes fail to
+/// serialize.
+///
+/// ```
+/// use serde::{Deserialize, Serialize};
+/// use time::OffsetDateTime;
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Event {
+///     #[serde(with = "serde_encom::with::time")]
+///     at: OffsetDateTime,
+/// }
+///
+/// let event = Event {
+///     at: OffsetDateTime::from_unix_timestamp(1_700_000_000).unwrap(),
+/// };
+/// let encom = serde_encom::to_string(&event).unwrap();
+/// assert_eq!(encom, "at:20@2023-11-14T22:13:20Z");
+/// assert_eq!(serde_encom::from_str::<Event>(&encom).unwrap(), event);
+/// ```
+#[cfg(feature = "time")]
+#[cfg_attr(docsrs, doc(cfg(feature = "time")))]
+pub mod time {
+    use super::*;
+    use crate::datetime::DateTime;
+    use ::time::OffsetDateTime;
+    use serde::{ser, Deserialize, Serialize};
+
+    /// Serializes `value` as a date-time.
+    pub fn serialize<S>(value: &OffsetDateTime, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        DateTime::try_from(*value)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+
+    /// Deserializes a date-time.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<OffsetDateTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        DateTime::deserialize(deserializer).map(OffsetDateTime::from)
+    }
+}
+
+/// Serialize and deserialize a `rust_decimal::Decimal` as an EnCom number.
+///
+/// The number is written with the digits and scale of the decimal, e.g.
+/// `12.50`, and read back from its text, so nothing is rounded through
+/// `f64`. Numbers with more digits than a decimal holds fail to
+/// deserialize. Other formats see a string, and a number is accepted from
+/// them as well.
+///
+/// ```
+/// use rust_decimal::Decimal;
+/// use serde::{Deserialize, Serialize};
+///
+/// #[derive(Debug, PartialEq, Serialize, Deserialize)]
+/// struct Payment {
+///     #[serde(with = "serde_encom::with::rust_decimal")]
+///     amount: Decimal,
+/// }
+///
+/// let payment = Payment {
+///     amount: Decimal::new(1250, 2),
+/// };
+/// let encom = serde_encom::to_string(&payment).unwrap();
+/// assert_eq!(encom, "amount:12.50");
+/// assert_eq!(serde_encom::from_str::<Payment>(&encom).unwrap(), payment);
+/// ```
+#[cfg(feature = "rust_decimal")]
+#[cfg_attr(docsrs, doc(cfg(feature = "rust_decimal")))]
+pub mod rust_decimal {
+    use super::*;
+    use crate::decimal::{self, DecimalVisitor, MAX_LEN, TOKEN};
+    use ::rust_decimal::Decimal;
+
+    /// Serializes `value` as a number.
+    pub fn serialize<S>(value: &Decimal, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_newtype_struct(TOKEN, decimal::format(value, &mut [0; MAX_LEN]))
+    }
+
+    /// Deserializes a decimal.
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Decimal, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        deserializer.deserialize_newtype_struct(TOKEN, DecimalVisitor)
+    }
+}
@@ -0,0 +1,148 @@
+#![cfg(feature = "actix")]
+
+use actix_web::http::{header, StatusCode};
+use actix_web::test::{call_service, init_service, read_body, TestRequest};
+use actix_web::{error, mime, rt, web, App, HttpResponse};
+use serde::{Deserialize, Serialize};
+use serde_encom::actix::{Encom, EncomConfig, EncomPayloadError};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct User {
+    id: u8,
+    name: String,
+}
+
+async fn rename(mut user: Encom<User>) -> Encom<User> {
+    user.name.make_ascii_uppercase();
+    user
+}
+
+/// Posts `body` to a `rename` service with `config`, and returns the status
+/// and the body of the response.
+fn post(
+    config: Option<EncomConfig>,
+    content_type: Option<&str>,
+    body: &'static [u8],
+) -> (StatusCode, Vec<u8>) {
+    rt::System::new().block_on(async {
+        let mut app = App::new();
+        if let Some(config) = config {
+            app = app.app_data(config);
+        }
+        let app = init_service(app.route("/", web::post().to(rename))).await;
+        let mut req = TestRequest::post().uri("/").set_payload(body);
+        if let Some(content_type) = content_type {
+            req = req.insert_header((header::CONTENT_TYPE, content_type));
+        }
+        let res = call_service(&app, req.to_request()).await;
+        if res.status() == StatusCode::OK {
+            let content_type = res.headers().get(header::CONTENT_TYPE).unwrap();
+            assert_eq!(content_type, "application/encom");
+        }
+        (res.status(), read_body(res).await.to_vec())
+    })
+}
+
+#[test]
+fn test_roundtrip() {
+    let (status, body) = post(None, Some("application/encom"), b"id:1 name:3=ann");
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, b"id:1 name:3=ANN");
+}
+
+#[test]
+fn test_content_types() {
+    for content_type in [
+        "application/encom; charset=utf-8",
+        "application/vnd.api+encom",
+    ] {
+        let (status, _) = post(None, Some(content_type), b"id:1 name:3=ann");
+        assert_eq!(status, StatusCode::OK, "{}", content_type);
+    }
+    for content_type in [None, Some("application/json"), Some("text/plain")] {
+        let (status, body) = post(None, content_type, b"id:1 name:3=ann");
+        assert_eq!(status, StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            body,
+            b"Expected request with `Content-Type: application/encom`"
+        );
+    }
+
+    let config = EncomConfig::default().content_type(|mime| mime == mime::TEXT_PLAIN);
+    let (status, _) = post(Some(config), Some("text/plain"), b"id:1 name:3=ann");
+    assert_eq!(status, StatusCode::OK);
+
+    let config = EncomConfig::default().content_type_required(false);
+    let (status, _) = post(Some(config), None, b"id:1 name:3=ann");
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[test]
+fn test_deserialize_errors() {
+    let (status, body) = post(None, Some("application/encom"), b"id:1 name:9=ann");
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body,
+        b"EnCom deserialize error: EOF while parsing a string at line 1 column 15"
+    );
+
+    let (status, body) = post(None, Some("application/encom"), b"id:300 name:3=ann");
+    assert_eq!(status, StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(body.starts_with(b"EnCom deserialize error: "));
+}
+
+#[test]
+fn test_limit() {
+    let config = EncomConfig::default().limit(8);
+    let (status, body) = post(Some(config), Some("application/encom"), b"id:1 name:3=ann");
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(
+        body,
+        b"EnCom payload (15 bytes) is larger than allowed (limit: 8 bytes)."
+    );
+
+    let config = EncomConfig::default().limit(15);
+    let (status, _) = post(Some(config), Some("application/encom"), b"id:1 name:3=ann");
+    assert_eq!(status, StatusCode::OK);
+}
+
+#[test]
+fn test_error_handler() {
+    let config = EncomConfig::default().error_handler(|err, _| {
+        let res = match &err {
+            EncomPayloadError::Deserialize(e) => {
+                HttpResponse::Conflict().body(format!("{}:{}", e.line(), e.column()))
+            }
+            _ => HttpResponse::ImATeapot().finish(),
+        };
+        error::InternalError::from_response(err, res).into()
+    });
+    let (status, body) = post(
+        Some(config.clone()),
+        Some("application/encom"),
+        b"id:1\nname:x",
+    );
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert_eq!(body, b"2:5");
+
+    let (status, _) = post(Some(config), None, b"");
+    assert_eq!(status, StatusCode::IM_A_TEAPOT);
+}
+
+#[test]
+fn test_data_config() {
+    let config = web::Data::new(EncomConfig::default().limit(1));
+    let status = rt::System::new().block_on(async {
+        let app = App::new()
+            .app_data(config)
+            .route("/", web::post().to(rename));
+        let app = init_service(app).await;
+        let req = TestRequest::post()
+            .uri("/")
+            .insert_header((header::CONTENT_TYPE, "application/encom"))
+            .set_payload(&b"id:1 name:3=ann"[..])
+            .to_request();
+        call_service(&app, req).await.status()
+    });
+    assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+}
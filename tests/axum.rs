@@ -0,0 +1,107 @@
+#![cfg(feature = "axum")]
+
+use axum::body::{to_bytes, Body};
+use axum::extract::DefaultBodyLimit;
+use axum::http::{header, Request, StatusCode};
+use axum::response::Response;
+use axum::routing::post;
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use serde_encom::axum::{Encom, EncomRejection};
+use tower::ServiceExt;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct User {
+    id: u8,
+    name: String,
+}
+
+async fn rename(Encom(mut user): Encom<User>) -> Encom<User> {
+    user.name.make_ascii_uppercase();
+    Encom(user)
+}
+
+fn app() -> Router {
+    Router::new().route("/", post(rename))
+}
+
+async fn send(app: Router, content_type: Option<&str>, body: &'static [u8]) -> (Response, Vec<u8>) {
+    let mut req = Request::post("/");
+    if let Some(content_type) = content_type {
+        req = req.header(header::CONTENT_TYPE, content_type);
+    }
+    let res = app
+        .oneshot(req.body(Body::from(body)).unwrap())
+        .await
+        .unwrap();
+    let (parts, body) = res.into_parts();
+    let body = to_bytes(body, usize::MAX).await.unwrap().to_vec();
+    (Response::from_parts(parts, Body::empty()), body)
+}
+
+#[tokio::test]
+async fn test_roundtrip() {
+    let (res, body) = send(app(), Some("application/encom"), b"id:1 name:3=ann").await;
+    assert_eq!(res.status(), StatusCode::OK);
+    assert_eq!(res.headers()[header::CONTENT_TYPE], "application/encom");
+    assert_eq!(body, b"id:1 name:3=ANN");
+}
+
+#[tokio::test]
+async fn test_content_types() {
+    for content_type in [
+        "application/encom; charset=utf-8",
+        "Application/EnCom",
+        "application/vnd.api+encom",
+    ] {
+        let (res, _) = send(app(), Some(content_type), b"id:1 name:3=ann").await;
+        assert_eq!(res.status(), StatusCode::OK, "{}", content_type);
+    }
+    for content_type in [None, Some("application/json"), Some("text/encom")] {
+        let (res, body) = send(app(), content_type, b"id:1 name:3=ann").await;
+        assert_eq!(res.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+        assert_eq!(
+            body,
+            b"Expected request with `Content-Type: application/encom`"
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_syntax_error() {
+    let (res, body) = send(app(), Some("application/encom"), b"id:1 name:9=ann").await;
+    assert_eq!(res.status(), StatusCode::BAD_REQUEST);
+    assert!(String::from_utf8(body)
+        .unwrap()
+        .starts_with("Failed to parse the request body as EnCom: "));
+}
+
+#[tokio::test]
+async fn test_data_error() {
+    let (res, body) = send(app(), Some("application/encom"), b"id:300 name:3=ann").await;
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+    assert!(String::from_utf8(body)
+        .unwrap()
+        .starts_with("Failed to deserialize the EnCom body into the target type: "));
+
+    let (res, _) = send(app(), Some("application/encom"), b"id:1").await;
+    assert_eq!(res.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
+
+#[tokio::test]
+async fn test_body_limit() {
+    let app = app().layer(DefaultBodyLimit::max(8));
+    let (res, _) = send(app, Some("application/encom"), b"id:1 name:3=ann").await;
+    assert_eq!(res.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[test]
+fn test_rejection() {
+    let err = Encom::<User>::from_bytes(b"id:1 name:").unwrap_err();
+    assert!(matches!(err, EncomRejection::Syntax(ref err) if err.is_eof()));
+    assert_eq!(err.status(), StatusCode::BAD_REQUEST);
+
+    let err = Encom::<User>::from_bytes(b"id:-1 name:0=").unwrap_err();
+    assert!(matches!(err, EncomRejection::Data(_)));
+    assert_eq!(err.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -9,3 +9,134 @@ fn look_at_bytes() {
     assert_eq!(b'\0', bytes[1]);
     assert_eq!(b'\xe5', bytes[2]);
 }
+
+mod modes {
+    use serde::{Deserialize, Serialize};
+    use serde_encom::{BytesMode, Options, Value};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Blob {
+        #[serde(with = "serde_bytes")]
+        data: Vec<u8>,
+        name: String,
+    }
+
+    fn blob(data: &[u8]) -> Blob {
+        Blob {
+            data: data.to_vec(),
+            name: "b".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_bytes_modes() {
+        let blob = blob(b"\x00\xff\x10");
+        let cases = [
+            (BytesMode::Raw, &b"data:3~\x00\xff\x10 name:1=b"[..]),
+            (BytesMode::Hex, b"data:6#00ff10 name:1=b"),
+            (BytesMode::Base64, b"data:4$AP8Q name:1=b"),
+        ];
+        for (mode, expected) in cases {
+            let encom = Options::new().bytes(mode).to_vec(&blob).unwrap();
+            assert_eq!(encom, expected);
+            assert_eq!(serde_encom::from_slice::<Blob>(&encom).unwrap(), blob);
+
+            let value: Value = serde_encom::from_slice(&encom).unwrap();
+            assert_eq!(value["data"], Value::Bytes(blob.data.clone()));
+        }
+    }
+
+    #[test]
+    fn test_bytes_lengths() {
+        for len in 0..20 {
+            let data: Vec<u8> = (0..len).map(|i| (i * 37) as u8).collect();
+            for mode in [BytesMode::Raw, BytesMode::Hex, BytesMode::Base64] {
+                let encom = Options::new().bytes(mode).to_vec(&blob(&data)).unwrap();
+                let back: Blob = serde_encom::from_slice(&encom).unwrap();
+                assert_eq!(back.data, data);
+            }
+        }
+
+        // Long enough to be encoded in several chunks.
+        let data: Vec<u8> = (0..1000).map(|i| (i * 7) as u8).collect();
+        for mode in [BytesMode::Hex, BytesMode::Base64] {
+            let encom = Options::new().bytes(mode).to_vec(&blob(&data)).unwrap();
+            let back: Blob = serde_encom::from_slice(&encom).unwrap();
+            assert_eq!(back.data, data);
+        }
+    }
+
+    #[test]
+    fn test_bytes_to_string() {
+        let blob = blob(b"\x00\xff\x10");
+        let encom = serde_encom::to_string(&blob).unwrap();
+        assert_eq!(encom, "data:4$AP8Q name:1=b");
+        assert_eq!(serde_encom::from_str::<Blob>(&encom).unwrap(), blob);
+
+        let encom = serde_encom::to_string_pretty(&blob).unwrap();
+        assert_eq!(serde_encom::from_str::<Blob>(&encom).unwrap(), blob);
+
+        let encom = Options::new()
+            .bytes(BytesMode::Hex)
+            .to_string(&blob)
+            .unwrap();
+        assert_eq!(encom, "data:6#00ff10 name:1=b");
+
+        let err = Options::new()
+            .bytes(BytesMode::Raw)
+            .to_string(&blob)
+            .unwrap_err();
+        assert!(err.is_data());
+        let err = Options::new()
+            .bytes(BytesMode::Raw)
+            .to_string_pretty(&blob)
+            .unwrap_err();
+        assert!(err.is_data());
+
+        let text = self::blob(b"text");
+        let encom = Options::new()
+            .bytes(BytesMode::Raw)
+            .to_string(&text)
+            .unwrap();
+        assert_eq!(encom, "data:4~text name:1=b");
+    }
+
+    #[test]
+    fn test_bytes_display() {
+        let value = Value::Bytes(vec![0x00, 0xff, 0x10]);
+        assert_eq!(value.to_string(), "4$AP8Q");
+        assert_eq!(format!("{value:#}"), "4$AP8Q");
+
+        let value: Value = serde_encom::from_slice(b"a:3~\xff\xfe\xfd b[1~\x80]").unwrap();
+        let text = value.to_string();
+        assert_eq!(text, "a:4$//79 b[2$gA]");
+        assert_eq!(serde_encom::from_str::<Value>(&text).unwrap(), value);
+    }
+
+    #[test]
+    fn test_bytes_invalid() {
+        for data in [
+            &b"data:3#00f name:1=b"[..],
+            b"data:2#0g name:1=b",
+            b"data:1$A name:1=b",
+            b"data:4$AP8= name:1=b",
+            b"data:3$AP9 name:1=b",
+        ] {
+            let err = serde_encom::from_slice::<Blob>(data).unwrap_err();
+            assert!(err.is_syntax(), "{}", err);
+        }
+
+        let err = serde_encom::from_slice::<Blob>(b"data:4$AP8Q name:4$AP8Q").unwrap_err();
+        assert!(err.is_data(), "{}", err);
+    }
+
+    #[test]
+    fn test_bytes_skip_and_extract() {
+        let data = b"skip:4$AP8Q other[6#00ff10 1=x] name:2=ok";
+        let name: Option<&str> = serde_encom::extract(data, "/name").unwrap();
+        assert_eq!(name, Some("ok"));
+
+        let value: Value = serde_encom::from_slice(&data[..]).unwrap();
+        assert_eq!(value["other"][0], Value::Bytes(vec![0x00, 0xff, 0x10]));
+    }
+}
@@ -0,0 +1,132 @@
+#![cfg(feature = "cli")]
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Runs `encom` with `input` on stdin, and returns whether it succeeded,
+/// its stdout and its stderr.
+fn encom(args: &[&str], input: &str) -> (bool, String, String) {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_encom"))
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+        String::from_utf8(output.stderr).unwrap(),
+    )
+}
+
+const DOC: &str = "id:7 user{name:3=Ann tags[1=a 1=b]} key:2~ab ratio:0.5 ok:t gone:n";
+
+#[test]
+fn test_fmt() {
+    let (ok, out, _) = encom(&["fmt"], "a{b[1 2]} c:1=x");
+    assert!(ok);
+    assert_eq!(out, "a{\n  b[\n    1\n    2\n  ]\n}\nc:1=x\n");
+
+    let (ok, out, _) = encom(&["fmt", "--compact", "-"], &out);
+    assert!(ok);
+    assert_eq!(out, "a{b[1 2]} c:1=x\n");
+}
+
+#[test]
+fn test_json() {
+    let (ok, out, _) = encom(&["from-json", "-c"], r#"{"a": [1, 2], "b": "x y"}"#);
+    assert!(ok);
+    assert_eq!(out, "a[1 2] b:3=x y\n");
+
+    let (ok, out, _) = encom(&["to-json", "-c"], "a[1 2] b:3=x y");
+    assert!(ok);
+    assert_eq!(out, "{\"a\":[1,2],\"b\":\"x y\"}\n");
+
+    let (ok, _, err) = encom(&["to-json"], "key:2~ab");
+    assert!(!ok);
+    assert_eq!(
+        err,
+        "encom: <stdin>: bytes at `/key` can't be represented in JSON\n"
+    );
+
+    let (ok, out, _) = encom(&["to-json", "-c", "--bytes", "base64"], "key:2~ab");
+    assert!(ok);
+    assert_eq!(out, "{\"key\":\"YWI=\"}\n");
+}
+
+#[test]
+fn test_validate() {
+    let (ok, out, err) = encom(&["validate"], DOC);
+    assert!(ok, "{err}");
+    assert_eq!(out, "");
+
+    let (ok, _, err) = encom(&["validate"], "a:1\nb{c:2=x");
+    assert!(!ok);
+    assert_eq!(
+        err,
+        "encom: <stdin>: EOF while parsing a string at line 2 column 7\n"
+    );
+
+    let dir = std::env::temp_dir().join(format!("encom-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let schema = dir.join("schema.encom");
+    std::fs::write(&schema, "fields{id{int{max:5}} user{type:6=object}}").unwrap();
+    let schema = schema.to_str().unwrap();
+
+    let (ok, _, err) = encom(&["validate", "--schema", schema], DOC);
+    assert!(!ok);
+    assert_eq!(err, "<stdin>: 7 is greater than the maximum 5 at `/id`\n");
+
+    let (ok, _, err) = encom(&["validate", "--schema", schema], "id:1 user:1");
+    assert!(!ok);
+    assert_eq!(err, "<stdin>: expected object, found int at `/user`\n");
+
+    let (ok, _, _) = encom(&["validate", "--schema", schema], "id:1 user{a:1}");
+    assert!(ok);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_get() {
+    let (ok, out, _) = encom(&["get", "/user/tags/1"], DOC);
+    assert!(ok);
+    assert_eq!(out, "1=b\n");
+
+    let (ok, out, _) = encom(&["get", "--raw", "/user/name"], DOC);
+    assert!(ok);
+    assert_eq!(out, "Ann\n");
+
+    let (ok, out, _) = encom(&["get", "-c", "/user"], DOC);
+    assert!(ok);
+    assert_eq!(out, "name:3=Ann tags[1=a 1=b]\n");
+
+    let (ok, _, err) = encom(&["get", "/user/age"], DOC);
+    assert!(!ok);
+    assert_eq!(err, "encom: <stdin>: no value at `/user/age`\n");
+}
+
+#[test]
+fn test_stats() {
+    let (ok, out, _) = encom(&["stats"], DOC);
+    assert!(ok);
+    assert_eq!(
+        out,
+        "size:66\ndepth:3\nvalues:11\nkeys:8\nnull:1\nbool:1\nint:1\nfloat:1\n\
+         string:3\nbytes:1\ndatetime:0\narray:1\nobject:2\n"
+    );
+}
+
+#[test]
+fn test_missing_file() {
+    let (ok, _, err) = encom(&["fmt", "does/not/exist.encom"], "");
+    assert!(!ok);
+    assert!(err.starts_with("encom: does/not/exist.encom: "), "{err}");
+}
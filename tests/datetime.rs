@@ -0,0 +1,429 @@
+#![cfg(feature = "std")]
+
+use serde::{Deserialize, Serialize};
+use serde_encom::schema::{self, Schema};
+use serde_encom::{DateTime, Value};
+
+fn dt(s: &str) -> DateTime {
+    s.parse().unwrap()
+}
+
+#[test]
+fn test_datetime_parse() {
+    let cases = [
+        ("1970-01-01T00:00:00Z", 0, 0, 0),
+        ("2023-11-14T22:13:20Z", 1_700_000_000, 0, 0),
+        (
+            "2023-11-14T23:13:20.5+01:00",
+            1_700_000_000,
+            500_000_000,
+            60,
+        ),
+        (
+            "2023-11-14T16:43:20.000000001-05:30",
+            1_700_000_000,
+            1,
+            -330,
+        ),
+        ("2024-02-29T00:00:00Z", 1_709_164_800, 0, 0),
+        ("0000-01-01T00:00:00Z", -62_167_219_200, 0, 0),
+        (
+            "9999-12-31T23:59:59.999999999Z",
+            253_402_300_799,
+            999_999_999,
+            0,
+        ),
+    ];
+    for (text, secs, nanos, offset) in cases {
+        let dt = dt(text);
+        assert_eq!(dt.timestamp(), secs, "{}", text);
+        assert_eq!(dt.timestamp_subsec_nanos(), nanos, "{}", text);
+        assert_eq!(dt.offset_minutes(), offset, "{}", text);
+    }
+}
+
+#[test]
+fn test_datetime_parse_strict() {
+    let invalid = [
+        "",
+        "2023-11-14",
+        "2023-11-14T22:13:20",
+        "2023-11-14t22:13:20Z",
+        "2023-11-14T22:13:20z",
+        "2023-11-14 22:13:20Z",
+        "2023-11-14T22:13Z",
+        "23-11-14T22:13:20Z",
+        "+2023-11-14T22:13:20Z",
+        "2023-13-14T22:13:20Z",
+        "2023-00-14T22:13:20Z",
+        "2023-11-31T22:13:20Z",
+        "2023-02-29T22:13:20Z",
+        "1900-02-29T22:13:20Z",
+        "2023-11-14T24:00:00Z",
+        "2023-11-14T22:60:20Z",
+        "2023-11-14T23:59:60Z",
+        "2023-11-14T22:13:20.Z",
+        "2023-11-14T22:13:20.0000000001Z",
+        "2023-11-14T22:13:20+01",
+        "2023-11-14T22:13:20+0100",
+        "2023-11-14T22:13:20+24:00",
+        "2023-11-14T22:13:20+01:60",
+        "2023-11-14T22:13:20Z ",
+        "0000-01-01T00:00:00+00:01",
+        "9999-12-31T23:59:59-00:01",
+    ];
+    for text in invalid {
+        let err = text.parse::<DateTime>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid RFC 3339 date-time", "{:?}", text);
+    }
+}
+
+#[test]
+fn test_datetime_format() {
+    let cases = [
+        ("2023-11-14T22:13:20.000Z", "2023-11-14T22:13:20Z"),
+        ("2023-11-14T22:13:20.120Z", "2023-11-14T22:13:20.12Z"),
+        ("2023-11-14T22:13:20+00:00", "2023-11-14T22:13:20Z"),
+        ("2023-11-14T22:13:20-00:00", "2023-11-14T22:13:20Z"),
+        ("2023-11-14T22:13:20+23:59", "2023-11-14T22:13:20+23:59"),
+        ("0001-03-01T00:00:00-00:30", "0001-03-01T00:00:00-00:30"),
+    ];
+    for (text, formatted) in cases {
+        assert_eq!(dt(text).to_string(), formatted);
+    }
+    assert_eq!(
+        format!("{:?}", DateTime::UNIX_EPOCH),
+        "DateTime(1970-01-01T00:00:00Z)"
+    );
+
+    // Every day of a leap and a common year formats back to itself.
+    for secs in (1_704_067_200..1_767_225_600).step_by(86_400) {
+        let dt = DateTime::from_timestamp(secs, 0).unwrap();
+        assert_eq!(dt.to_string().parse::<DateTime>().unwrap(), dt);
+    }
+}
+
+#[test]
+fn test_datetime_range() {
+    assert_eq!(DateTime::from_timestamp(-62_167_219_201, 0), None);
+    assert_eq!(DateTime::from_timestamp(253_402_300_800, 0), None);
+    assert_eq!(DateTime::from_timestamp(0, 1_000_000_000), None);
+    assert_eq!(DateTime::UNIX_EPOCH.with_offset(1440), None);
+    assert_eq!(DateTime::MIN.with_offset(-1), None);
+    assert_eq!(DateTime::MAX.with_offset(1), None);
+    assert!(DateTime::MIN < DateTime::UNIX_EPOCH);
+
+    // Equal instants at different offsets aren't equal.
+    let utc = dt("2023-11-14T22:13:20Z");
+    let local = dt("2023-11-14T23:13:20+01:00");
+    assert_eq!(utc.timestamp(), local.timestamp());
+    assert_ne!(utc, local);
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Event {
+    at: DateTime,
+    times: Vec<DateTime>,
+    until: Option<DateTime>,
+}
+
+#[test]
+fn test_datetime_roundtrip() {
+    let event = Event {
+        at: dt("2023-11-14T23:13:20.25+01:00"),
+        times: vec![DateTime::MIN, DateTime::MAX],
+        until: None,
+    };
+    let encom = serde_encom::to_string(&event).unwrap();
+    assert_eq!(
+        encom,
+        "at:28@2023-11-14T23:13:20.25+01:00 \
+         times[20@0000-01-01T00:00:00Z 30@9999-12-31T23:59:59.999999999Z] until:n"
+    );
+    assert_eq!(serde_encom::from_str::<Event>(&encom).unwrap(), event);
+
+    let pretty = serde_encom::to_string_pretty(&event).unwrap();
+    assert_eq!(serde_encom::from_str::<Event>(&pretty).unwrap(), event);
+
+    // Strings are parsed too, as they come from other formats.
+    let event: Event =
+        serde_encom::from_str("at:20=1970-01-01T00:00:00Z times[] until:20=1970-01-01T00:00:00Z")
+            .unwrap();
+    assert_eq!(event.at, DateTime::UNIX_EPOCH);
+    assert_eq!(event.until, Some(DateTime::UNIX_EPOCH));
+}
+
+#[test]
+fn test_datetime_invalid() {
+    // Length prefix doesn't match.
+    let err = serde_encom::from_str::<Event>("at:19@2023-11-14T22:13:20Z times[]").unwrap_err();
+    assert!(err.is_syntax(), "{}", err);
+    let err = serde_encom::from_str::<Event>("at:21@2023-11-14T22:13:20Z times[]").unwrap_err();
+    assert!(err.is_syntax(), "{}", err);
+
+    // Not a date-time.
+    let err = serde_encom::from_str::<Event>("at:20@2023-11-14T22:13:20z times[]").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid RFC 3339 date-time at line 1 column 26"
+    );
+    let err = serde_encom::from_str::<Value>("at:3@abc").unwrap_err();
+    assert!(err.is_syntax(), "{}", err);
+
+    // A string that isn't a date-time is a data error.
+    let err = serde_encom::from_str::<Event>("at:3=abc times[]").unwrap_err();
+    assert!(err.is_data(), "{}", err);
+
+    // Nor is a date-time a string or a number.
+    #[derive(Debug, Deserialize)]
+    struct Text {
+        #[allow(dead_code)]
+        at: String,
+    }
+    let err = serde_encom::from_str::<Text>("at:20@2023-11-14T22:13:20Z").unwrap_err();
+    assert!(err.is_data(), "{}", err);
+}
+
+#[test]
+fn test_datetime_value() {
+    let encom = "at:20@2023-11-14T22:13:20Z \
+                 mixed[1=x 20@2023-11-14T22:13:20Z] \
+                 list[20@2023-11-14T22:13:20Z 20@1970-01-01T00:00:00Z] \
+                 text:20=2023-11-14T22:13:20Z";
+    let value: Value = serde_encom::from_str(encom).unwrap();
+    let at = dt("2023-11-14T22:13:20Z");
+    assert_eq!(value["at"], Value::DateTime(at));
+    assert_eq!(value["at"], at);
+    assert_eq!(value["mixed"][1], at);
+    assert_eq!(value["list"][1], DateTime::UNIX_EPOCH);
+    assert!(value["text"].is_string());
+    assert_eq!(
+        format!("{:?}", value["at"]),
+        "DateTime(2023-11-14T22:13:20Z)"
+    );
+
+    // Written back the same.
+    let back = serde_encom::to_string(&value).unwrap();
+    assert_eq!(serde_encom::from_str::<Value>(&back).unwrap(), value);
+
+    // And through `to_value` and `from_value`.
+    let event = Event {
+        at,
+        times: vec![at],
+        until: Some(at),
+    };
+    let value = serde_encom::to_value(&event).unwrap();
+    assert_eq!(value["until"], at);
+    assert_eq!(
+        serde_encom::from_value::<Event>(value.clone()).unwrap(),
+        event
+    );
+    assert_eq!(Event::deserialize(&value).unwrap(), event);
+
+    // Through formats that write it as a string.
+    #[cfg(feature = "serde_json")]
+    {
+        let json = serde_json::to_string(&event).unwrap();
+        assert_eq!(serde_json::from_str::<Event>(&json).unwrap(), event);
+
+        let json = value
+            .to_json(serde_encom::value::BytesPolicy::Error)
+            .unwrap();
+        assert_eq!(json["at"], "2023-11-14T22:13:20Z");
+    }
+}
+
+#[test]
+fn test_datetime_untagged() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    #[serde(untagged)]
+    enum Either {
+        Event { at: DateTime },
+        Other { at: u64 },
+    }
+
+    let either: Either = serde_encom::from_str("at:20@2023-11-14T22:13:20Z").unwrap();
+    assert_eq!(
+        either,
+        Either::Event {
+            at: dt("2023-11-14T22:13:20Z")
+        }
+    );
+    let either: Either = serde_encom::from_str("at:5").unwrap();
+    assert_eq!(either, Either::Other { at: 5 });
+}
+
+#[test]
+fn test_datetime_skip_and_extract() {
+    let data = b"skip:20@2023-11-14T22:13:20Z other[20@1970-01-01T00:00:00Z 1=x] name:2=ok";
+    let name: Option<&str> = serde_encom::extract(data, "/name").unwrap();
+    assert_eq!(name, Some("ok"));
+
+    let value: Value = serde_encom::from_slice(data).unwrap();
+    assert_eq!(value["other"][0], DateTime::UNIX_EPOCH);
+    assert_eq!(value["name"], "ok");
+}
+
+#[test]
+fn test_datetime_schema() {
+    let values: Vec<Value> = ["at:20@2023-11-14T22:13:20Z", "at:20@1970-01-01T00:00:00Z"]
+        .iter()
+        .map(|doc| serde_encom::from_str(doc).unwrap())
+        .collect();
+    let inferred = schema::infer(&values);
+    let at = &inferred.object.as_ref().unwrap().fields["at"];
+    assert_eq!(at.types().collect::<Vec<_>>(), ["datetime"]);
+    let bounds = at.datetime.unwrap();
+    assert_eq!(
+        (bounds.min, bounds.max),
+        (DateTime::UNIX_EPOCH, dt("2023-11-14T22:13:20Z"))
+    );
+    assert!(inferred
+        .to_rust("Event")
+        .contains("pub at: serde_encom::DateTime,"));
+    assert!(inferred
+        .validate_slice(b"at:20@2000-01-01T00:00:00Z")
+        .is_ok());
+
+    let text = inferred.to_string();
+    assert!(
+        text.contains("datetime{\n      min:20@1970-01-01T00:00:00Z"),
+        "{}",
+        text
+    );
+    assert_eq!(text.parse::<Schema>().unwrap(), inferred);
+
+    let schema: Schema = "fields{at{type:8=datetime datetime{min:20@2000-01-01T00:00:00Z}}}"
+        .parse()
+        .unwrap();
+    let cases: [(&str, &[&str]); 5] = [
+        ("at:20@2023-11-14T22:13:20Z", &[]),
+        (
+            "at:20@1999-12-31T23:59:59Z",
+            &["1999-12-31T23:59:59Z is less than the minimum 2000-01-01T00:00:00Z at `/at`"],
+        ),
+        (
+            "at:20=2023-11-14T22:13:20Z",
+            &["expected datetime, found string at `/at`"],
+        ),
+        ("at{a:1}", &["expected datetime, found object at `/at`"]),
+        ("x:1", &["missing required key at `/at`"]),
+    ];
+    for (doc, expected) in cases {
+        let messages = |errors: Vec<schema::ValidationError>| -> Vec<String> {
+            errors.iter().map(ToString::to_string).collect()
+        };
+        let streamed = schema
+            .validate_slice(doc.as_bytes())
+            .err()
+            .map_or_else(Vec::new, messages);
+        assert_eq!(streamed, expected, "{}", doc);
+        let value: Value = serde_encom::from_str(doc).unwrap();
+        let checked = schema
+            .validate(&value)
+            .err()
+            .map_or_else(Vec::new, messages);
+        assert_eq!(checked, expected, "{}", doc);
+    }
+
+    // A date-time isn't an object, so its key isn't a field.
+    let schema: Schema = "type:6=object fields{a{type:3=int}}".parse().unwrap();
+    let errors = schema
+        .validate_slice(b"a:20@2023-11-14T22:13:20Z")
+        .unwrap_err();
+    assert_eq!(
+        errors[0].to_string(),
+        "expected int, found datetime at `/a`"
+    );
+}
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use chrono::{DateTime, FixedOffset, TimeZone, Utc};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Event {
+        #[serde(with = "serde_encom::with::chrono")]
+        utc: DateTime<Utc>,
+        #[serde(with = "serde_encom::with::chrono")]
+        local: DateTime<FixedOffset>,
+    }
+
+    #[test]
+    fn test_chrono_roundtrip() {
+        let utc = Utc.timestamp_opt(1_700_000_000, 123_000_000).unwrap();
+        let local = FixedOffset::west_opt(330 * 60)
+            .unwrap()
+            .timestamp_opt(1_700_000_000, 0)
+            .unwrap();
+        let event = Event { utc, local };
+        let encom = serde_encom::to_string(&event).unwrap();
+        assert_eq!(
+            encom,
+            "utc:24@2023-11-14T22:13:20.123Z local:25@2023-11-14T16:43:20-05:30"
+        );
+        assert_eq!(serde_encom::from_str::<Event>(&encom).unwrap(), event);
+
+        let dt = serde_encom::DateTime::try_from(local).unwrap();
+        assert_eq!(dt.offset_minutes(), -330);
+        assert_eq!(DateTime::<FixedOffset>::from(dt), local);
+        assert_eq!(DateTime::<Utc>::from(dt), local);
+    }
+
+    #[test]
+    fn test_chrono_out_of_range() {
+        let leap = Utc.timestamp_opt(1_700_000_039, 1_500_000_000).unwrap();
+        let seconds = FixedOffset::east_opt(30)
+            .unwrap()
+            .timestamp_opt(0, 0)
+            .unwrap();
+        let ancient = Utc.with_ymd_and_hms(-1, 1, 1, 0, 0, 0).unwrap();
+        for err in [
+            serde_encom::DateTime::try_from(leap).unwrap_err(),
+            serde_encom::DateTime::try_from(seconds).unwrap_err(),
+            serde_encom::DateTime::try_from(ancient).unwrap_err(),
+        ] {
+            assert_eq!(err.to_string(), "date-time out of range");
+            assert!(err.is_data());
+        }
+
+        let event = Event {
+            utc: leap,
+            local: seconds,
+        };
+        assert!(serde_encom::to_string(&event).is_err());
+    }
+}
+
+#[cfg(feature = "time")]
+mod time {
+    use serde::{Deserialize, Serialize};
+    use time::{OffsetDateTime, UtcOffset};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Event {
+        #[serde(with = "serde_encom::with::time")]
+        at: OffsetDateTime,
+    }
+
+    #[test]
+    fn test_time_roundtrip() {
+        let at = OffsetDateTime::from_unix_timestamp_nanos(1_700_000_000_000_000_001)
+            .unwrap()
+            .to_offset(UtcOffset::from_hms(5, 45, 0).unwrap());
+        let event = Event { at };
+        let encom = serde_encom::to_string(&event).unwrap();
+        assert_eq!(encom, "at:35@2023-11-15T03:58:20.000000001+05:45");
+        let back: Event = serde_encom::from_str(&encom).unwrap();
+        assert_eq!(back, event);
+        assert_eq!(back.at.offset(), at.offset());
+    }
+
+    #[test]
+    fn test_time_out_of_range() {
+        let seconds = OffsetDateTime::UNIX_EPOCH.to_offset(UtcOffset::from_hms(0, 0, 30).unwrap());
+        let err = serde_encom::DateTime::try_from(seconds).unwrap_err();
+        assert_eq!(err.to_string(), "date-time out of range");
+    }
+}
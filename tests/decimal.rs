@@ -0,0 +1,100 @@
+#![cfg(feature = "rust_decimal")]
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use serde_encom::Value;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Payment {
+    #[serde(with = "serde_encom::with::rust_decimal")]
+    amount: Decimal,
+    note: String,
+}
+
+fn dec(s: &str) -> Decimal {
+    Decimal::from_str_exact(s).unwrap()
+}
+
+#[test]
+fn test_decimal_roundtrip() {
+    for text in [
+        "0",
+        "12.50",
+        "-0.1",
+        "0.0000000000000000000000000001",
+        "-0.0000000000000000000000000001",
+        "79228162514264337593543950335",
+        "-79228162514264337593543950335",
+        "7.9228162514264337593543950335",
+    ] {
+        let payment = Payment {
+            amount: dec(text),
+            note: "x".to_owned(),
+        };
+        let encom = serde_encom::to_string(&payment).unwrap();
+        assert_eq!(encom, format!("amount:{text} note:1=x"));
+        let back: Payment = serde_encom::from_str(&encom).unwrap();
+        assert_eq!(back, payment);
+        // The scale is kept too, not only the value.
+        assert_eq!(back.amount.to_string(), text);
+
+        let pretty = serde_encom::to_string_pretty(&payment).unwrap();
+        assert_eq!(serde_encom::from_str::<Payment>(&pretty).unwrap(), payment);
+    }
+}
+
+#[test]
+fn test_decimal_read() {
+    for (encom, expected) in [
+        ("amount:1e3 note:0=", "1000"),
+        ("amount:-2.5E-2 note:0=", "-0.025"),
+        ("amount:7 note:0=", "7"),
+        // A string is read as well.
+        ("amount:5=12.50 note:0=", "12.50"),
+    ] {
+        let payment: Payment = serde_encom::from_str(encom).unwrap();
+        assert_eq!(payment.amount.to_string(), expected);
+    }
+
+    // More digits than a decimal holds aren't rounded.
+    assert!(
+        serde_encom::from_str::<Payment>("amount:0.00000000000000000000000000001 note:0=").is_err()
+    );
+    assert!(
+        serde_encom::from_str::<Payment>("amount:79228162514264337593543950336 note:0=").is_err()
+    );
+    assert!(serde_encom::from_str::<Payment>("amount:1.2.3 note:0=").is_err());
+    assert!(serde_encom::from_str::<Payment>("amount:t note:0=").is_err());
+}
+
+#[test]
+fn test_decimal_value() {
+    let payment = Payment {
+        amount: dec("0.10"),
+        note: String::new(),
+    };
+
+    // A `Value` keeps the digits as a string, and reads them back.
+    let value = serde_encom::to_value(&payment).unwrap();
+    assert_eq!(value["amount"], "0.10");
+    assert_eq!(serde_encom::from_value::<Payment>(value).unwrap(), payment);
+
+    // A number parsed into a `Value` is read back from its float.
+    let value: Value = serde_encom::from_str("amount:0.1 note:0=").unwrap();
+    let back: Payment = serde_encom::from_value(value).unwrap();
+    assert_eq!(back.amount, dec("0.1"));
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn test_decimal_json() {
+    let payment = Payment {
+        amount: dec("12.50"),
+        note: String::new(),
+    };
+    let json = serde_json::to_string(&payment).unwrap();
+    assert_eq!(json, r#"{"amount":"12.50","note":""}"#);
+    assert_eq!(serde_json::from_str::<Payment>(&json).unwrap(), payment);
+    let back: Payment = serde_json::from_str(r#"{"amount":1.25,"note":""}"#).unwrap();
+    assert_eq!(back.amount, dec("1.25"));
+}
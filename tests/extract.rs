@@ -0,0 +1,113 @@
+use serde::Deserialize;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct B1<'a> {
+    b1: u64,
+    b2: &'a str,
+}
+
+const DATA: &[u8] = b"a1:3 a2:11=a2 :{[]} a2 a3[3=a31 3=a32] a5{b1:21 b2:4=a5b2} \
+    a6[{b1:65 b2:4=a6b2} {b1:87 b2:7=h\xF0\x9F\x94\xA5ty}] a8[U64:7 Vecu64[2 5 7] U64:9] \
+    a13:4=fash a14{String:3=a14} a17[[1 2] [3 4]] a18:t a20:n a22:1.5 a23:-1.5 \
+    bytes:3~\xE5\x00\xE5 a/b:1 m~n:2";
+
+#[test]
+fn test_scalars() {
+    assert_eq!(serde_encom::extract::<u64>(DATA, "/a1").unwrap(), Some(3));
+    assert_eq!(
+        serde_encom::extract::<&str>(DATA, "/a2").unwrap(),
+        Some("a2 :{[]} a2")
+    );
+    assert_eq!(
+        serde_encom::extract::<bool>(DATA, "/a18").unwrap(),
+        Some(true)
+    );
+    assert_eq!(
+        serde_encom::extract::<Option<u64>>(DATA, "/a20").unwrap(),
+        Some(None)
+    );
+    assert_eq!(
+        serde_encom::extract::<f64>(DATA, "/a23").unwrap(),
+        Some(-1.5)
+    );
+    assert_eq!(
+        serde_encom::extract::<&[u8]>(DATA, "/bytes").unwrap(),
+        Some(&b"\xE5\x00\xE5"[..])
+    );
+    assert_eq!(serde_encom::extract::<u64>(DATA, "/a~1b").unwrap(), Some(1));
+    assert_eq!(serde_encom::extract::<u64>(DATA, "/m~0n").unwrap(), Some(2));
+}
+
+#[test]
+fn test_nested() {
+    assert_eq!(
+        serde_encom::extract::<&str>(DATA, "/a3/1").unwrap(),
+        Some("a32")
+    );
+    assert_eq!(
+        serde_encom::extract::<&str>(DATA, "/a5/b2").unwrap(),
+        Some("a5b2")
+    );
+    assert_eq!(
+        serde_encom::extract::<&str>(DATA, "/a6/1/b2").unwrap(),
+        Some("h🔥ty")
+    );
+    assert_eq!(
+        serde_encom::extract::<B1>(DATA, "/a6/0").unwrap(),
+        Some(B1 { b1: 65, b2: "a6b2" })
+    );
+    assert_eq!(
+        serde_encom::extract::<Vec<u64>>(DATA, "/a8/1/Vecu64").unwrap(),
+        Some(vec![2, 5, 7])
+    );
+    assert_eq!(
+        serde_encom::extract::<u64>(DATA, "/a8/2/U64").unwrap(),
+        Some(9)
+    );
+    assert_eq!(
+        serde_encom::extract::<&str>(DATA, "/a14/String").unwrap(),
+        Some("a14")
+    );
+    assert_eq!(
+        serde_encom::extract::<(u64, u64)>(DATA, "/a17/1").unwrap(),
+        Some((3, 4))
+    );
+}
+
+#[test]
+fn test_missing() {
+    for pointer in ["/a0", "/a3/2", "/a3/x", "/a1/b", "/a5/b3", "/a17/0/5", "a1"] {
+        assert_eq!(serde_encom::extract::<u64>(DATA, pointer).unwrap(), None);
+    }
+}
+
+#[test]
+fn test_top_level_seq() {
+    let data = b"5=first {b1:1 b2:3=two} [3 4]";
+    assert_eq!(
+        serde_encom::extract::<&str>(data, "/0").unwrap(),
+        Some("first")
+    );
+    assert_eq!(
+        serde_encom::extract::<B1>(data, "/1").unwrap(),
+        Some(B1 { b1: 1, b2: "two" })
+    );
+    assert_eq!(serde_encom::extract::<u64>(data, "/2/1").unwrap(), Some(4));
+    assert_eq!(serde_encom::extract::<u64>(data, "/3").unwrap(), None);
+}
+
+#[test]
+fn test_whole() {
+    let data = b"b1:1 b2:3=two";
+    assert_eq!(
+        serde_encom::extract::<B1>(data, "").unwrap(),
+        Some(B1 { b1: 1, b2: "two" })
+    );
+}
+
+#[test]
+fn test_errors() {
+    assert!(serde_encom::extract::<u64>(b"a:100=short b:1", "/b").is_err());
+    assert!(serde_encom::extract::<u64>(b"a{b:1", "/c").is_err());
+    assert!(serde_encom::extract::<u64>(b"a:5=hello", "/a").is_err());
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "heapless")]
+
+use heapless::{String, Vec};
+use serde::{Deserialize, Serialize};
+use serde_encom::Value;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Sensor {
+    #[serde(with = "serde_encom::heapless::string")]
+    name: String<8>,
+    #[serde(with = "serde_encom::heapless::bytes")]
+    key: Vec<u8, 4>,
+    label: String<4>,
+    readings: Vec<i16, 3>,
+}
+
+fn sensor() -> Sensor {
+    Sensor {
+        name: String::try_from("boiler").unwrap(),
+        key: Vec::from_slice(b"\x01\x02").unwrap(),
+        label: String::try_from("b1").unwrap(),
+        readings: Vec::from_slice(&[3, -1]).unwrap(),
+    }
+}
+
+#[test]
+fn test_heapless_roundtrip() {
+    let encom = serde_encom::to_string(&sensor()).unwrap();
+    assert_eq!(encom, "name:6=boiler key:3$AQI label:2=b1 readings[3 -1]");
+
+    let de: Sensor = serde_encom::from_str(&encom).unwrap();
+    assert_eq!(de, sensor());
+
+    let mut buf = [0; 64];
+    let len = serde_encom::to_slice(&mut buf, &sensor()).unwrap();
+    assert_eq!(
+        serde_encom::from_slice::<Sensor>(&buf[..len]).unwrap(),
+        sensor()
+    );
+}
+
+#[test]
+fn test_heapless_full_capacity() {
+    let data = b"name:8=boilers2 key:4~\x01\x02\x03\x04 label:4=abcd readings[1 2 3]";
+    let de: Sensor = serde_encom::from_slice(data).unwrap();
+    assert_eq!(de.name, "boilers2");
+    assert_eq!(de.key, [1, 2, 3, 4]);
+    assert_eq!(de.label, "abcd");
+    assert_eq!(de.readings, [1, 2, 3]);
+}
+
+#[test]
+fn test_heapless_capacity_exceeded() {
+    // The prefix is checked before the value, which here is cut short.
+    let data = b"name:1000=boiler";
+    let err = serde_encom::from_slice::<Sensor>(data).unwrap_err();
+    assert!(err.is_capacity_exceeded());
+    assert!(err.is_data());
+    assert_eq!(
+        err.to_string(),
+        "length exceeds capacity at line 1 column 10"
+    );
+
+    let data = b"name:6=boiler key:5~\x01\x02\x03\x04\x05 label:2=b1 readings[]";
+    let err = serde_encom::from_slice::<Sensor>(data).unwrap_err();
+    assert!(err.is_capacity_exceeded());
+
+    // Without an adapter the heapless impls fail once the value is read.
+    let data = b"name:6=boiler key:2~\x01\x02 label:5=label readings[]";
+    let err = serde_encom::from_slice::<Sensor>(data).unwrap_err();
+    assert!(!err.is_capacity_exceeded());
+    assert!(err.is_data());
+
+    let data = b"name:6=boiler key:2~\x01\x02 label:2=b1 readings[1 2 3 4]";
+    let err = serde_encom::from_slice::<Sensor>(data).unwrap_err();
+    assert!(err.is_data());
+}
+
+#[test]
+fn test_heapless_value() {
+    let value: Value =
+        serde_encom::from_str("name:6=boiler key:2~\x01\x02 label:2=b1 readings[3 -1]").unwrap();
+    let de = Sensor::deserialize(&value).unwrap();
+    assert_eq!(de, sensor());
+    assert_eq!(serde_encom::from_value::<Sensor>(value).unwrap(), sensor());
+
+    let value: Value =
+        serde_encom::from_str("name:10=lighthouse key:0~ label:2=b1 readings[3]").unwrap();
+    let err = serde_encom::from_value::<Sensor>(value).unwrap_err();
+    assert!(err.is_data());
+}
+
+#[test]
+fn test_heapless_encoded_capacity() {
+    let data = b"name:6=boiler key:8#01020304 label:2=b1 readings[]";
+    let de: Sensor = serde_encom::from_slice(data).unwrap();
+    assert_eq!(de.key, [1, 2, 3, 4]);
+
+    let data = b"name:6=boiler key:10#0102030405 label:2=b1 readings[]";
+    let err = serde_encom::from_slice::<Sensor>(data).unwrap_err();
+    assert!(err.is_capacity_exceeded());
+
+    let data = b"name:6=boiler key:7$AQIDBAU label:2=b1 readings[]";
+    let err = serde_encom::from_slice::<Sensor>(data).unwrap_err();
+    assert!(err.is_capacity_exceeded());
+}
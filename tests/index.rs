@@ -0,0 +1,110 @@
+use serde::Deserialize;
+use serde_encom::index::Index;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, Debug, PartialEq)]
+struct Record<'a> {
+    id: u64,
+    name: &'a str,
+}
+
+const DATA: &[u8] = b"meta{version:2 note:8=a:{b} [c} \
+    records[{id:1 name:5=first} {id:2 name:6=second} {id:3 name:5=third}] \
+    raw:3~\x00\xFF\x00 kinds[U64:7 Str:1=x] a/b:1";
+
+#[test]
+fn test_get() {
+    let index = Index::build(DATA).unwrap();
+    assert_eq!(index.get(""), Some(DATA));
+    assert_eq!(index.get("/meta/version"), Some(&b"2"[..]));
+    assert_eq!(index.get("/meta/note"), Some(&b"8=a:{b} [c"[..]));
+    assert_eq!(index.get("/records/1"), Some(&b"{id:2 name:6=second}"[..]));
+    assert_eq!(index.get("/raw"), Some(&b"3~\x00\xFF\x00"[..]));
+    assert_eq!(index.get("/kinds/1/Str"), Some(&b"1=x"[..]));
+    assert_eq!(index.get("/a~1b"), Some(&b"1"[..]));
+    assert_eq!(index.get("/records/3"), None);
+    assert_eq!(index.get("/meta/missing"), None);
+}
+
+#[test]
+fn test_deserialize_at() {
+    let index = Index::build(DATA).unwrap();
+    assert_eq!(
+        index.deserialize_at::<Record>("/records/2").unwrap(),
+        Some(Record {
+            id: 3,
+            name: "third"
+        })
+    );
+    assert_eq!(
+        index.deserialize_at::<&str>("/meta/note").unwrap(),
+        Some("a:{b} [c")
+    );
+    assert_eq!(
+        index.deserialize_at::<&[u8]>("/raw").unwrap(),
+        Some(&b"\x00\xFF\x00"[..])
+    );
+    assert_eq!(
+        index
+            .deserialize_at::<Vec<Record>>("/records")
+            .unwrap()
+            .map(|r| r.len()),
+        Some(3)
+    );
+    assert_eq!(index.deserialize_at::<u64>("/none").unwrap(), None);
+    assert!(index.deserialize_at::<u64>("/meta/note").is_err());
+}
+
+#[test]
+fn test_depth() {
+    let full = Index::build(DATA).unwrap();
+    let index = Index::build_to_depth(DATA, 1).unwrap();
+    assert!(index.len() < full.len());
+    assert!(index.spans().contains_key("/records"));
+    assert!(!index.spans().contains_key("/records/0"));
+
+    for pointer in full.spans().keys() {
+        assert_eq!(index.get(pointer), full.get(pointer), "{pointer}");
+    }
+    assert_eq!(index.get("/records/5/id"), None);
+    assert_eq!(
+        index.deserialize_at::<Record>("/records/0").unwrap(),
+        Some(Record {
+            id: 1,
+            name: "first"
+        })
+    );
+
+    let index = Index::build_to_depth(DATA, 0).unwrap();
+    assert_eq!(index.len(), 1);
+    assert_eq!(index.get("/records/1/id"), Some(&b"2"[..]));
+}
+
+#[test]
+fn test_top_level_seq() {
+    let data = b"5=first {id:2 name:1=b} [3 4]";
+    let index = Index::build(data).unwrap();
+    assert_eq!(index.get("/0"), Some(&b"5=first"[..]));
+    assert_eq!(index.get("/2/1"), Some(&b"4"[..]));
+    assert_eq!(
+        index.deserialize_at::<Record>("/1").unwrap(),
+        Some(Record { id: 2, name: "b" })
+    );
+}
+
+#[test]
+fn test_persist() {
+    let index = Index::build_to_depth(DATA, 2).unwrap();
+    let saved = serde_encom::to_vec(index.spans()).unwrap();
+
+    let spans: BTreeMap<String, std::ops::Range<usize>> = serde_encom::from_slice(&saved).unwrap();
+    let restored = Index::from_spans(DATA, spans, index.max_depth());
+    assert_eq!(restored.get("/records/1/name"), Some(&b"6=second"[..]));
+}
+
+#[test]
+fn test_errors() {
+    assert!(Index::build(b"a:10=short").is_err());
+    assert!(Index::build(b"a{b:1").is_err());
+    assert!(Index::build(b"a{b:1}}").is_err());
+}
@@ -55,3 +55,38 @@ fn i64_err() {
         panic!("error");
     }
 }
+
+#[test]
+fn i128() {
+    for example in [i128::MIN, -1, 0, i128::MAX] {
+        let example_str = serde_encom::to_string(&example).unwrap();
+
+        let example_des: i128 = serde_encom::from_slice(example_str.as_bytes()).unwrap();
+        assert_eq!(example, example_des);
+    }
+}
+
+#[test]
+fn u128() {
+    for example in [0, u64::MAX as u128 + 1, u128::MAX] {
+        let example_str = serde_encom::to_string(&example).unwrap();
+
+        let example_des: u128 = serde_encom::from_slice(example_str.as_bytes()).unwrap();
+        assert_eq!(example, example_des);
+    }
+}
+
+#[test]
+fn int128_err() {
+    let example_des = serde_encom::from_str::<i128>("170141183460469231731687303715884105728");
+    assert!(example_des.is_err());
+
+    let example_des = serde_encom::from_str::<i128>("-170141183460469231731687303715884105729");
+    assert!(example_des.is_err());
+
+    let example_des = serde_encom::from_str::<u128>("340282366920938463463374607431768211456");
+    assert!(example_des.is_err());
+
+    let example_des = serde_encom::from_str::<u128>("-1");
+    assert!(example_des.is_err());
+}
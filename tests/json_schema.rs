@@ -0,0 +1,244 @@
+#![cfg(feature = "schemars")]
+
+use schemars::{json_schema, JsonSchema};
+use serde::{Deserialize, Serialize};
+use serde_encom::schema::{Bounds, Integer, Schema};
+use serde_encom::Value;
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct Pos {
+    x: i32,
+    y: Option<u8>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+enum Cmd {
+    Stop,
+    Move(i8),
+    Jump(u32, u32),
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+enum Kind {
+    User,
+    Admin,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct Tree {
+    name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    kids: Vec<Tree>,
+}
+
+#[derive(Serialize, Deserialize, JsonSchema)]
+struct Top {
+    id: u64,
+    ratio: f32,
+    letter: char,
+    list: Vec<u8>,
+    #[serde(with = "serde_bytes")]
+    #[schemars(with = "serde_encom::schema::Bytes")]
+    key: Vec<u8>,
+    pos: Pos,
+    cmd: Cmd,
+    kind: Kind,
+    map: BTreeMap<String, u16>,
+    pair: (u8, i16),
+    named: (String, String),
+    tree: Tree,
+}
+
+fn top() -> Top {
+    Top {
+        id: u64::MAX,
+        ratio: 0.5,
+        letter: 'é',
+        list: vec![1, 2],
+        key: vec![0, 255],
+        pos: Pos { x: -3, y: Some(4) },
+        cmd: Cmd::Move(-4),
+        kind: Kind::Admin,
+        map: BTreeMap::from([("a".to_owned(), 1)]),
+        pair: (1, -1),
+        named: ("one".to_owned(), "two".to_owned()),
+        tree: Tree {
+            name: "root".to_owned(),
+            kids: vec![Tree {
+                name: "leaf".to_owned(),
+                kids: vec![Tree {
+                    name: "deep".to_owned(),
+                    kids: vec![],
+                }],
+            }],
+        },
+    }
+}
+
+fn types(schema: &Schema) -> Vec<&'static str> {
+    schema.types().collect()
+}
+
+#[test]
+fn test_for_type() {
+    let schema = Schema::for_type::<Top>().unwrap();
+    assert_eq!(types(&schema), ["object"]);
+    let object = schema.object.as_ref().unwrap();
+    let field = |key: &str| &object.fields[key];
+    assert!(!object.is_optional("tree"));
+
+    assert_eq!(
+        field("id").int,
+        Some(Bounds {
+            min: Integer::from(0),
+            max: Integer::from(u64::MAX)
+        })
+    );
+    assert_eq!(types(field("ratio")), ["int", "float"]);
+    assert_eq!(field("letter").string, Some(Bounds { min: 1, max: 4 }));
+
+    let list = field("list").array.as_ref().unwrap();
+    assert_eq!(
+        list.items.int,
+        Some(Bounds {
+            min: Integer::from(0),
+            max: Integer::from(255)
+        })
+    );
+    assert_eq!(types(field("key")), ["bytes"]);
+
+    let pos = field("pos").object.as_ref().unwrap();
+    assert_eq!(types(&pos.fields["y"]), ["null", "int"]);
+    assert!(pos.is_optional("y"));
+
+    assert_eq!(types(field("cmd")), ["string", "object"]);
+    assert_eq!(field("cmd").values, None);
+    let variants = field("cmd").object.as_ref().unwrap();
+    assert_eq!(variants.fields.keys().collect::<Vec<_>>(), ["Jump", "Move"]);
+    assert!(variants.required.is_empty());
+
+    assert_eq!(
+        field("kind").values,
+        Some(vec![Value::from("User"), Value::from("Admin")])
+    );
+    assert!(field("map").object.as_ref().unwrap().fields.is_empty());
+
+    let pair = field("pair").array.as_ref().unwrap();
+    assert_eq!(pair.len, Bounds { min: 2, max: 2 });
+    assert_eq!(
+        pair.items.int,
+        Some(Bounds {
+            min: Integer::from(-32768),
+            max: Integer::from(32767)
+        })
+    );
+    assert_eq!(types(field("named")), ["array"]);
+
+    // The recursion is cut at the first `$ref` back into `Tree`.
+    let tree = field("tree").object.as_ref().unwrap();
+    assert!(tree.is_optional("kids"));
+    let kids = tree.fields["kids"].array.as_ref().unwrap();
+    assert_eq!(types(&kids.items), Vec::<&str>::new());
+}
+
+#[test]
+fn test_for_type_validates() {
+    let schema = Schema::for_type::<Top>().unwrap();
+    let encom = serde_encom::to_string(&top()).unwrap();
+    assert_eq!(schema.validate_slice(encom.as_bytes()), Ok(()), "{encom}");
+    let value: Value = serde_encom::from_str(&encom).unwrap();
+    assert_eq!(schema.validate(&value), Ok(()));
+
+    let encom = serde_encom::to_string(&Cmd::Jump(3, 7)).unwrap();
+    let schema = Schema::for_type::<Cmd>().unwrap();
+    assert_eq!(schema.validate_slice(encom.as_bytes()), Ok(()), "{encom}");
+
+    let schema = Schema::for_type::<Kind>().unwrap();
+    assert_eq!(schema.validate_slice(b"4=User"), Ok(()));
+    let errors = schema.validate_slice(b"4=User 5=Admin").unwrap_err();
+    assert_eq!(
+        errors[0].message(),
+        "expected a single value, found a sequence"
+    );
+
+    let schema = Schema::for_type::<Pos>().unwrap();
+    let errors = schema.validate_slice(b"x:2147483648 y:-1").unwrap_err();
+    let errors: Vec<String> = errors.iter().map(ToString::to_string).collect();
+    assert_eq!(
+        errors,
+        [
+            "2147483648 is greater than the maximum 2147483647 at `/x`",
+            "-1 is less than the minimum 0 at `/y`",
+        ]
+    );
+}
+
+#[test]
+fn test_from_json_schema() {
+    let schema = Schema::from_json_schema(&json_schema!({
+        "$defs": {
+            "Base": {
+                "properties": { "id": { "type": "integer", "exclusiveMinimum": 0 } },
+                "required": ["id"],
+            },
+        },
+        "allOf": [
+            { "$ref": "#/$defs/Base" },
+            {
+                "properties": {
+                    "tag": {
+                        "anyOf": [
+                            { "enum": ["none", 0] },
+                            { "type": "object", "properties": { "v": true } },
+                        ],
+                    },
+                    "version": { "const": 2 },
+                    "ratio": { "minimum": 0.5, "exclusiveMaximum": 1 },
+                },
+                "required": ["tag"],
+            },
+        ],
+    }))
+    .unwrap();
+    let object = schema.object.as_ref().unwrap();
+    assert_eq!(object.required.iter().collect::<Vec<_>>(), ["id", "tag"]);
+    assert_eq!(
+        object.fields["id"].int,
+        Some(Bounds {
+            min: Integer::from(1),
+            max: Integer::MAX
+        })
+    );
+    assert_eq!(object.fields["version"].values, Some(vec![Value::from(2)]));
+    assert_eq!(
+        object.fields["ratio"].float,
+        Some(Bounds { min: 0.5, max: 1.0 })
+    );
+    assert_eq!(types(&object.fields["tag"]), ["int", "string", "object"]);
+
+    assert!(schema.validate_slice(b"id:1 tag:4=none version:2").is_ok());
+    assert!(schema.validate_slice(b"id:1 tag{v[1 2]}").is_ok());
+    assert!(schema.validate_slice(b"id:0 tag:t version:3").is_err());
+}
+
+#[test]
+fn test_from_json_schema_errors() {
+    for (schema, message) in [
+        (
+            json_schema!({ "type": "text" }),
+            "unknown type `text` at `/type`",
+        ),
+        (
+            json_schema!({ "properties": { "a": { "$ref": "other.json" } } }),
+            "unresolvable `$ref` at `/properties/a`",
+        ),
+        (
+            json_schema!({ "items": { "$ref": "#/$defs/A" }, "$defs": { "A": false } }),
+            "expected a schema at `/$defs/A`",
+        ),
+    ] {
+        let err = Schema::from_json_schema(&schema).unwrap_err();
+        assert_eq!(err.to_string(), message);
+    }
+}
@@ -1,16 +1,39 @@
 #[cfg(feature = "std")]
 use serde_encom::encom_from_json;
 
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "axum")]
+mod axum;
 mod bytes;
+#[cfg(feature = "cli")]
+mod cli;
+#[cfg(feature = "std")]
+mod datetime;
+#[cfg(feature = "rust_decimal")]
+mod decimal;
+mod extract;
+#[cfg(feature = "heapless")]
+mod heapless;
+mod index;
 mod int;
+#[cfg(feature = "schemars")]
+mod json_schema;
 #[cfg(feature = "std")]
 mod map;
 mod option_int;
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "std")]
+mod schema;
+mod slice;
 mod struc;
 #[cfg(feature = "std")]
 mod value;
 #[cfg(feature = "std")]
 mod vec;
+#[cfg(feature = "std")]
+mod whitespace;
 
 #[cfg(feature = "std")]
 #[test]
@@ -72,6 +95,7 @@ fn stream() {
     let remaining = &data[stream.byte_offset()..];
 } */
 
+#[cfg(feature = "std")]
 pub(crate) fn to_string(input: &[u8]) -> String {
     let mut res = String::with_capacity(input.len());
     for &val in input {
@@ -0,0 +1,149 @@
+#![cfg(feature = "rayon")]
+
+use serde::{Deserialize, Serialize};
+use serde_encom::par::from_slice_vec;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Record {
+    id: u64,
+    name: String,
+    tags: Vec<String>,
+    score: f64,
+}
+
+fn records(n: u64) -> Vec<Record> {
+    (0..n)
+        .map(|id| Record {
+            id,
+            name: format!("record {id}\n{{]"),
+            tags: vec!["a".to_owned(), "b c".to_owned()],
+            score: id as f64 / 4.0,
+        })
+        .collect()
+}
+
+fn assert_same<'a, T>(data: &'a [u8])
+where
+    T: Deserialize<'a> + Send + PartialEq + std::fmt::Debug,
+{
+    match (
+        serde_encom::from_slice::<Vec<T>>(data),
+        from_slice_vec::<T>(data),
+    ) {
+        (Ok(seq), Ok(par)) => assert_eq!(seq, par),
+        (Err(seq), Err(par)) => {
+            assert_eq!(seq.to_string(), par.to_string());
+            assert_eq!((seq.line(), seq.column()), (par.line(), par.column()));
+        }
+        (seq, par) => panic!("from_slice: {seq:?}, par: {par:?}"),
+    }
+}
+
+#[test]
+fn test_par_records() {
+    let expected = records(1000);
+    let encom = serde_encom::to_string(&expected).unwrap();
+    assert_eq!(
+        from_slice_vec::<Record>(encom.as_bytes()).unwrap(),
+        expected
+    );
+
+    let pretty = serde_encom::to_string_pretty(&expected).unwrap();
+    assert_eq!(
+        from_slice_vec::<Record>(pretty.as_bytes()).unwrap(),
+        expected
+    );
+
+    assert_eq!(from_slice_vec::<Record>(b"").unwrap(), []);
+    assert_eq!(from_slice_vec::<Record>(b" \n ").unwrap(), []);
+}
+
+#[test]
+fn test_par_scalars() {
+    assert_eq!(from_slice_vec::<u32>(b"1 2 3").unwrap(), [1, 2, 3]);
+    assert_eq!(
+        from_slice_vec::<bool>(b"t f t").unwrap(),
+        [true, false, true]
+    );
+    assert_eq!(
+        from_slice_vec::<&str>(b"1=a 3=b c 0=").unwrap(),
+        ["a", "b c", ""]
+    );
+    assert_same::<f64>(b"1.5 -2 3e2");
+    assert_same::<Vec<u8>>(b"[1 2] [] [3]");
+}
+
+#[test]
+fn test_par_errors() {
+    let mut encom = serde_encom::to_string_pretty(&records(100)).unwrap();
+    assert_same::<Record>(encom.as_bytes());
+
+    // The first failing element gives the error, even when later ones fail
+    // too.
+    let bad = encom.find("id:7\n").unwrap();
+    encom.replace_range(bad..bad + 4, "id:x");
+    let bad = encom.find("id:42\n").unwrap();
+    encom.replace_range(bad..bad + 5, "id:-1");
+    assert_same::<Record>(encom.as_bytes());
+    let err = from_slice_vec::<Record>(encom.as_bytes()).unwrap_err();
+    assert!(err.line() > 1);
+
+    for data in [
+        &b"1 2 x 4"[..],
+        b"1 2 ] 4",
+        b"1 2 } 4",
+        b"1 2 [3",
+        b"1 2 5=abc",
+        b"1 2 3:4",
+        b"1 2 99999999999999999999 4",
+    ] {
+        assert_same::<u32>(data);
+    }
+    assert_same::<Vec<u32>>(b"[1] [2 x] [3");
+    assert_same::<String>(b"1=a 2=b");
+}
+
+#[test]
+fn test_par_brackets() {
+    let expected = records(1000);
+    let encom = serde_encom::to_string(&expected).unwrap();
+    let pretty = serde_encom::to_string_pretty(&expected).unwrap();
+    for data in [encom, pretty] {
+        let seq = serde_encom::from_slice::<Vec<Record>>(data.as_bytes()).unwrap();
+        assert_eq!(seq, expected);
+        assert_eq!(from_slice_vec::<Record>(data.as_bytes()).unwrap(), seq);
+        let bracketed = format!(" [\n{data}\n] \n");
+        assert_eq!(from_slice_vec::<Record>(bracketed.as_bytes()).unwrap(), seq);
+    }
+
+    let seq = serde_encom::from_slice::<Vec<u32>>(b"1 2 3").unwrap();
+    assert_eq!(from_slice_vec::<u32>(b"[1 2 3]").unwrap(), seq);
+    assert_eq!(from_slice_vec::<u32>(b"[]").unwrap(), Vec::<u32>::new());
+    assert_eq!(
+        from_slice_vec::<Vec<u8>>(b"[[1 2] [] [3]]").unwrap(),
+        serde_encom::from_slice::<Vec<Vec<u8>>>(b"[1 2] [] [3]").unwrap()
+    );
+
+    // A single array that isn't one of `T`s is an element, as without
+    // brackets only one level is taken off.
+    assert_same::<Vec<u8>>(b"[1 2]");
+    assert_same::<Vec<u8>>(b"[1 2] [3]");
+    assert_same::<u32>(b"[1 2] 3");
+    assert_same::<Vec<u32>>(b"[1 x] [2]");
+    assert!(from_slice_vec::<u32>(b"[1 2 3").unwrap_err().is_eof());
+
+    // Errors are those of the elements, one column after them.
+    for (bracketed, plain) in [
+        (&b"[1 2 x 4]"[..], &b"1 2 x 4"[..]),
+        (b"[1 2 3:4]", b"1 2 3:4"),
+        (b"[1 2 5=abc]", b"1 2 5=abc"),
+    ] {
+        let par = from_slice_vec::<u32>(bracketed).unwrap_err();
+        let seq = serde_encom::from_slice::<Vec<u32>>(plain).unwrap_err();
+        assert_eq!(
+            par.to_string().split(" at ").next(),
+            seq.to_string().split(" at ").next()
+        );
+        assert_eq!((par.line(), par.column()), (seq.line(), seq.column() + 1));
+    }
+}
@@ -0,0 +1,424 @@
+use serde_encom::schema::{self, Bounds, Integer, Schema};
+use serde_encom::Value;
+
+fn samples(docs: &[&str]) -> Vec<Value> {
+    docs.iter()
+        .map(|doc| serde_encom::from_str(doc).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_fields() {
+    let values = samples(&[
+        "id:1 name:3=Ann score:1.5 key:2~ab",
+        "id:18446744073709551615 name:5=Bobby score:-2 extra:t",
+        "id:-3 name:2~ab score:n",
+    ]);
+    let schema = schema::infer(&values);
+    assert_eq!(schema.count, 3);
+    assert_eq!(schema.types().collect::<Vec<_>>(), ["object"]);
+
+    let object = schema.object.as_ref().unwrap();
+    assert_eq!(object.count, 3);
+    assert_eq!(
+        object.fields.keys().collect::<Vec<_>>(),
+        ["extra", "id", "key", "name", "score"]
+    );
+
+    let id = &object.fields["id"];
+    assert_eq!(
+        id.int,
+        Some(Bounds {
+            min: Integer::from(-3),
+            max: Integer::from(u64::MAX)
+        })
+    );
+    assert_eq!(id.float, None);
+    assert!(!object.is_optional("id"));
+
+    let name = &object.fields["name"];
+    assert_eq!(name.types().collect::<Vec<_>>(), ["string", "bytes"]);
+    assert_eq!(name.string, Some(Bounds { min: 3, max: 5 }));
+    assert_eq!(name.bytes, Some(Bounds { min: 2, max: 2 }));
+
+    let score = &object.fields["score"];
+    assert_eq!(score.types().collect::<Vec<_>>(), ["null", "int", "float"]);
+    assert_eq!(
+        score.int,
+        Some(Bounds {
+            min: Integer::from(-2),
+            max: Integer::from(-2)
+        })
+    );
+    assert_eq!(score.float, Some(Bounds { min: 1.5, max: 1.5 }));
+
+    assert!(object.is_optional("key"));
+    assert!(object.is_optional("extra"));
+    assert!(object.is_optional("missing"));
+    assert_eq!(object.fields["extra"].count, 1);
+    assert!(object.fields["extra"].bool);
+}
+
+#[test]
+fn test_arrays() {
+    let values = samples(&["list[1 2 3] nested[[1=a] [1=b 1=c]]", "list[{x:1} 4]"]);
+    let schema = schema::infer(&values);
+    let object = schema.object.as_ref().unwrap();
+
+    let list = object.fields["list"].array.as_ref().unwrap();
+    assert_eq!(list.len, Bounds { min: 2, max: 3 });
+    assert_eq!(list.items.count, 5);
+    assert_eq!(list.items.types().collect::<Vec<_>>(), ["int", "object"]);
+    assert_eq!(
+        list.items.int,
+        Some(Bounds {
+            min: Integer::from(1),
+            max: Integer::from(4)
+        })
+    );
+
+    let nested = object.fields["nested"].array.as_ref().unwrap();
+    let inner = nested.items.array.as_ref().unwrap();
+    assert_eq!(inner.len, Bounds { min: 1, max: 2 });
+    assert_eq!(inner.items.string, Some(Bounds { min: 1, max: 1 }));
+}
+
+#[test]
+fn test_observe() {
+    let values = samples(&["a:1", "a:5"]);
+    let mut schema = schema::infer(&values[..1]);
+    schema.observe(&values[1]);
+    assert_eq!(schema, schema::infer(&values));
+    assert_eq!(schema::infer(&[]), Schema::default());
+}
+
+#[test]
+fn test_display_union() {
+    let values = samples(&["v:1", "v:2=ab", "v:n"]);
+    let schema = schema::infer(&values);
+    let compact = serde_encom::to_string(&schema).unwrap();
+    assert!(
+        compact.contains("v{count:3 type[4=null 3=int 6=string] int{"),
+        "{compact}"
+    );
+
+    let printed = schema.to_string();
+
+    let reparsed: Value = serde_encom::from_str(&printed).unwrap();
+    assert_eq!(reparsed["fields"]["v"]["int"]["max"], 1);
+    assert_eq!(reparsed["fields"]["v"]["string"]["min_len"], 2);
+}
+
+#[test]
+fn test_rust() {
+    let values = samples(&[
+        "type:4=user items[{sku:3=A-1 qty:2}] raw:2~ab 2fa:t",
+        "type:5=admin items[{sku:3=B-2 qty:1 note:1=x}] raw:1~a",
+    ]);
+    let schema = schema::infer(&values);
+    assert_eq!(
+        schema.to_rust("order"),
+        "\
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Order {
+    #[serde(rename = \"2fa\")]
+    pub field_2fa: Option<bool>,
+    pub items: Vec<Item>,
+    pub raw: serde_bytes::ByteBuf,
+    pub r#type: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Item {
+    pub note: Option<String>,
+    pub qty: u64,
+    pub sku: String,
+}
+"
+    );
+}
+
+#[test]
+fn test_rust_ident_collision() {
+    let values = samples(&["a-b:1 a_b:2 aB:3 type:4 Type:5"]);
+    let schema = schema::infer(&values);
+    assert_eq!(
+        schema.to_rust("row"),
+        "\
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Row {
+    #[serde(rename = \"Type\")]
+    pub r#type: u64,
+    #[serde(rename = \"a-b\")]
+    pub a_b: u64,
+    #[serde(rename = \"aB\")]
+    pub a_b_2: u64,
+    #[serde(rename = \"a_b\")]
+    pub a_b_3: u64,
+    #[serde(rename = \"type\")]
+    pub type_2: u64,
+}
+"
+    );
+}
+
+#[test]
+fn test_rust_int_width() {
+    let values =
+        samples(&["a:18446744073709551616 b:-9223372036854775809 c:-1 d:18446744073709551615"]);
+    let rust = schema::infer(&values).to_rust("row");
+    assert!(rust.contains("pub a: u128,"), "{rust}");
+    assert!(rust.contains("pub b: i128,"), "{rust}");
+    assert!(rust.contains("pub c: i64,"), "{rust}");
+    assert!(rust.contains("pub d: u64,"), "{rust}");
+}
+
+#[test]
+fn test_int_above_i128() {
+    let values = samples(&[
+        "a:340282366920938463463374607431768211455 b:-170141183460469231731687303715884105728",
+    ]);
+    let schema = schema::infer(&values);
+    let fields = &schema.object.as_ref().unwrap().fields;
+    assert_eq!(fields["a"].types().collect::<Vec<_>>(), ["int"]);
+    assert_eq!(
+        fields["a"].int,
+        Some(Bounds {
+            min: Integer::from(u128::MAX),
+            max: Integer::from(u128::MAX)
+        })
+    );
+    assert_eq!(
+        fields["b"].int,
+        Some(Bounds {
+            min: Integer::MIN,
+            max: Integer::MIN
+        })
+    );
+    assert!(schema.validate(&values[0]).is_ok());
+    assert!(schema
+        .validate_slice(
+            b"a:340282366920938463463374607431768211455 b:-170141183460469231731687303715884105728"
+        )
+        .is_ok());
+
+    let below =
+        "a:340282366920938463463374607431768211454 b:-170141183460469231731687303715884105728";
+    let errors = schema.validate(&samples(&[below])[0]).unwrap_err();
+    assert_eq!(
+        errors[0].to_string(),
+        "340282366920938463463374607431768211454 is less than the minimum \
+         340282366920938463463374607431768211455 at `/a`"
+    );
+    assert!(schema.validate_slice(below.as_bytes()).is_err());
+
+    let rust = schema.to_rust("row");
+    assert!(rust.contains("pub a: u128,"), "{rust}");
+    assert!(rust.contains("pub b: i128,"), "{rust}");
+
+    let reparsed: Schema = schema.to_string().parse().unwrap();
+    assert_eq!(reparsed, schema);
+}
+
+#[test]
+fn test_rust_root_array() {
+    let values = samples(&["{a:1} {a:-2 b:n}"]);
+    let schema = schema::infer(&values);
+    assert_eq!(
+        schema.to_rust("Rows"),
+        "\
+use serde::{Deserialize, Serialize};
+
+pub type Rows = Vec<Row>;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Row {
+    pub a: i64,
+    pub b: Option<serde_encom::Value>,
+}
+"
+    );
+}
+
+const DECLARED: &str = "\
+type:6=object
+fields{
+  id{type:3=int int{min:1 max:1000}}
+  name{type:6=string string{min_len:1 max_len:8}}
+  kind{enum[4=user 5=admin]}
+  score{optional:t type:6=number float{min:0 max:1}}
+  key{optional:t type[4=null 5=bytes] bytes{max_len:4}}
+  tags{type:5=array array{max_len:2 items{type:6=string}}}
+  meta{optional:t type:6=object}
+}";
+
+fn declared() -> Schema {
+    DECLARED.parse().unwrap()
+}
+
+fn errors(schema: &Schema, doc: &str) -> Vec<String> {
+    let value: Value = serde_encom::from_str(doc).unwrap();
+    let tree = match schema.validate(&value) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(ToString::to_string).collect(),
+    };
+    let streamed = match schema.validate_slice(doc.as_bytes()) {
+        Ok(()) => Vec::new(),
+        Err(errors) => errors.iter().map(ToString::to_string).collect(),
+    };
+    // Without `preserve_order` a `Value` map visits its keys sorted, and
+    // `validate_slice` visits them in document order.
+    let (mut tree, mut streamed) = (tree, streamed);
+    tree.sort();
+    streamed.sort();
+    assert_eq!(tree, streamed, "{doc}");
+    tree
+}
+
+#[test]
+fn test_validate_ok() {
+    let schema = declared();
+    for doc in [
+        "id:1 name:3=Ann kind:4=user tags[1=a]",
+        "id:1000 name:8=Benjamin kind:5=admin score:1 key:n tags[1=a 1=b] meta{x:1}",
+        "id:2 name:1=C kind:4=user score:0.5 key:4~abcd tags[1=a] extra[1 2]",
+    ] {
+        assert_eq!(errors(&schema, doc), Vec::<String>::new());
+    }
+}
+
+#[test]
+fn test_validate_errors() {
+    let schema = declared();
+    assert_eq!(
+        errors(
+            &schema,
+            "id:0 name:9=Christina kind:5=guest tags[1=a 1=b 1=c]"
+        ),
+        [
+            "0 is less than the minimum 1 at `/id`",
+            "length 3 is greater than the maximum 2 at `/tags`",
+            "length 9 is greater than the maximum 8 at `/name`",
+            "value is not one of the allowed values at `/kind`",
+        ]
+    );
+    assert_eq!(
+        errors(
+            &schema,
+            "id:1.5 name:3~Ann kind:4=user score:2.5 key:5~abcde meta:t"
+        ),
+        [
+            "2.5 is greater than the maximum 1 at `/score`",
+            "expected int, found float at `/id`",
+            "expected object, found bool at `/meta`",
+            "expected string, found bytes at `/name`",
+            "length 5 is greater than the maximum 4 at `/key`",
+            "missing required key at `/tags`",
+        ]
+    );
+    assert_eq!(
+        errors(&schema, "id:5 name:1=a kind:4=user tags[1 2]"),
+        [
+            "expected string, found int at `/tags/0`",
+            "expected string, found int at `/tags/1`",
+        ]
+    );
+}
+
+#[test]
+fn test_validate_root() {
+    let schema: Schema = "type:6=object fields{a{type:3=int}}".parse().unwrap();
+    let errors = schema.validate(&Value::from("x")).unwrap_err();
+    assert_eq!(errors[0].pointer(), "");
+    assert_eq!(errors[0].message(), "expected object, found string");
+
+    let errors = schema.validate_slice(b"b:1").unwrap_err();
+    assert_eq!(errors[0].pointer(), "/a");
+    assert_eq!(errors[0].message(), "missing required key");
+}
+
+#[test]
+fn test_validate_syntax_error() {
+    let schema = declared();
+    let errors = schema
+        .validate_slice(b"id:0 name:3=Ann tags[1=a 9=b]")
+        .unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].pointer(), "/id");
+    assert_eq!(errors[1].pointer(), "/tags/1");
+    assert!(errors[1].message().contains("EOF"), "{}", errors[1]);
+}
+
+#[test]
+fn test_inferred_validates_samples() {
+    let docs = [
+        "id:1 name:3=Ann tags[1=a] pos{x:1.5 y:2}",
+        "id:20 name:5=Bobby pos{x:-1 y:0} key:2~ab",
+    ];
+    let schema = schema::infer(&samples(&docs));
+    for doc in docs {
+        assert_eq!(errors(&schema, doc), Vec::<String>::new());
+    }
+    assert_eq!(
+        errors(&schema, "id:21 name:3=Ann pos{x:1.5}"),
+        [
+            "21 is greater than the maximum 20 at `/id`",
+            "missing required key at `/pos/y`",
+        ]
+    );
+}
+
+#[test]
+fn test_schema_roundtrip() {
+    let schema = declared();
+    let printed = schema.to_string();
+    let reparsed: Schema = printed.parse().unwrap();
+    assert_eq!(schema, reparsed, "{printed}");
+
+    let inferred = schema::infer(&samples(&["a:1 b[1=x] c{d:2.5}", "a:-4 e:n"]));
+    let reparsed: Schema = serde_encom::from_str(&inferred.to_string()).unwrap();
+    assert_eq!(inferred, reparsed);
+}
+
+#[test]
+fn test_schema_errors() {
+    for (doc, message) in [
+        ("typo:3=int", "unknown schema key at `/typo`"),
+        ("type:4=text", "unknown type `text` at `/type`"),
+        (
+            "type:6=string int{min:1}",
+            "`int` is not a listed type at ``",
+        ),
+        (
+            "fields{a{int{least:1}}}",
+            "unknown schema key at `/fields/a/int/least`",
+        ),
+        (
+            "array{items{optional:1}}",
+            "expected `t` or `f` at `/array/items/optional`",
+        ),
+    ] {
+        let err = doc.parse::<Schema>().unwrap_err();
+        assert!(err.to_string().starts_with(message), "{doc}: {err}");
+    }
+}
+
+#[test]
+fn test_validate_slice_single_value() {
+    let schema: Schema = "type:3=int int{max:9}".parse().unwrap();
+    assert_eq!(schema.validate_slice(b"5"), Ok(()));
+    let errors = schema.validate_slice(b"12").unwrap_err();
+    assert_eq!(
+        errors[0].to_string(),
+        "12 is greater than the maximum 9 at ``"
+    );
+    let errors = schema.validate_slice(b"1 2").unwrap_err();
+    assert_eq!(
+        errors[0].message(),
+        "expected a single value, found a sequence"
+    );
+}
@@ -0,0 +1,112 @@
+use serde::Serialize;
+use serde_encom::Options;
+use std::collections::BTreeMap;
+use std::fmt;
+
+struct Version(u8, u8);
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "v{}.{}", self.0, self.1)
+    }
+}
+
+impl Serialize for Version {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[derive(Serialize)]
+enum Mode {
+    Idle,
+    Run(u16),
+}
+
+#[derive(Serialize)]
+struct Device<'a> {
+    id: u32,
+    name: &'a str,
+    #[serde(with = "serde_bytes")]
+    key: &'a [u8],
+    version: Version,
+    mode: Mode,
+    flags: BTreeMap<char, bool>,
+    readings: [f32; 2],
+}
+
+fn device() -> Device<'static> {
+    Device {
+        id: 42,
+        name: "sensor",
+        key: b"\x01\x02",
+        version: Version(1, 10),
+        mode: Mode::Run(300),
+        flags: BTreeMap::from([('a', true), ('b', false)]),
+        readings: [21.5, -3.0],
+    }
+}
+
+#[test]
+fn test_to_slice() {
+    let device = device();
+    let mut buf = [0; 256];
+
+    let len = serde_encom::to_slice(&mut buf, &device).unwrap();
+    assert_eq!(&buf[..len], serde_encom::to_vec(&device).unwrap());
+
+    let len = serde_encom::to_slice_pretty(&mut buf, &device).unwrap();
+    assert_eq!(&buf[..len], serde_encom::to_vec_pretty(&device).unwrap());
+}
+
+#[test]
+fn test_to_slice_exact() {
+    let device = device();
+    let expected = serde_encom::to_vec(&device).unwrap();
+
+    let mut buf = vec![0; expected.len()];
+    let len = serde_encom::to_slice(&mut buf, &device).unwrap();
+    assert_eq!(len, expected.len());
+    assert_eq!(buf, expected);
+}
+
+#[test]
+fn test_to_slice_full() {
+    let device = device();
+    let expected = serde_encom::to_vec(&device).unwrap();
+
+    for size in 0..expected.len() {
+        let mut buf = vec![0; size];
+        let err = serde_encom::to_slice(&mut buf, &device).unwrap_err();
+        assert!(err.is_buffer_full(), "{size}: {err}");
+        assert!(err.is_io());
+        assert_eq!(err.to_string(), "buffer is full");
+    }
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_to_slice_full_io_error() {
+    let mut buf = [0; 4];
+    let err = serde_encom::to_slice(&mut buf, &device()).unwrap_err();
+    let err = std::io::Error::from(err);
+    assert_eq!(err.kind(), std::io::ErrorKind::WriteZero);
+}
+
+#[test]
+fn test_to_slice_error() {
+    let map = BTreeMap::from([((1, 2), 3)]);
+    let mut buf = [0; 16];
+    let err = serde_encom::to_slice(&mut buf, &map).unwrap_err();
+    assert!(!err.is_buffer_full());
+}
+
+#[test]
+fn test_options_to_slice() {
+    let options = Options::new().variants_by_index(true);
+    let mut buf = [0; 16];
+    let len = options
+        .to_slice(&mut buf, &[Mode::Idle, Mode::Run(7)])
+        .unwrap();
+    assert_eq!(&buf[..len], b"0 1:7");
+}
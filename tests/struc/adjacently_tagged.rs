@@ -0,0 +1,75 @@
+use serde::{Deserialize, Serialize};
+
+pub type ExType = Vec<Event>;
+pub fn get_example() -> ExType {
+    vec![
+        Event::Started,
+        Event::Progress(75),
+        Event::Delta(-3),
+        Event::Log("build ok".to_owned()),
+        Event::Range(3, 9),
+        Event::Items(vec!["x".to_owned(), "y z".to_owned()]),
+        Event::Finished {
+            code: 0,
+            message: Some("done".to_owned()),
+        },
+    ]
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "t", content = "c")]
+pub enum Event {
+    Started,
+    Progress(u64),
+    Delta(i64),
+    Log(String),
+    Range(u64, u64),
+    Items(Vec<String>),
+    Finished { code: i32, message: Option<String> },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Batch {
+    id: u64,
+    events: Vec<Event>,
+    last: Event,
+}
+
+#[test]
+fn self_test() {
+    let example = get_example();
+    let example_str = serde_encom::to_string(&example).unwrap();
+
+    let example_des: ExType = serde_encom::from_slice(example_str.as_bytes()).unwrap();
+    assert_eq!(example, example_des);
+
+    let example_des: ExType = serde_encom::from_str(&example_str).unwrap();
+    assert_eq!(example, example_des);
+}
+
+#[test]
+fn top_level() {
+    for event in get_example() {
+        let s = serde_encom::to_string(&event).unwrap();
+        let des: Event = serde_encom::from_slice(s.as_bytes()).unwrap();
+        assert_eq!(event, des, "{s}");
+    }
+}
+
+#[test]
+fn nested() {
+    let batch = Batch {
+        id: 1,
+        events: get_example(),
+        last: Event::Log("end".to_owned()),
+    };
+    let s = serde_encom::to_string(&batch).unwrap();
+    let des: Batch = serde_encom::from_str(&s).unwrap();
+    assert_eq!(batch, des, "{s}");
+}
+
+#[test]
+fn content_first() {
+    let des: Event = serde_encom::from_str("c:5 t:8=Progress").unwrap();
+    assert_eq!(des, Event::Progress(5));
+}
@@ -156,7 +156,7 @@ fn self_test_no_none() {
     let example_des: ExType = serde_encom::from_slice(example_str.as_bytes()).unwrap();
     assert_eq!(example, example_des);
 
-    let example_des: ExType = serde_encom::from_str(&example_str).unwrap();
+    let example_des: ExType = serde_encom::from_str(example_str).unwrap();
     assert_eq!(example, example_des);
 }
 
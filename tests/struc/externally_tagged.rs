@@ -0,0 +1,97 @@
+use serde::{Deserialize, Serialize};
+
+pub type ExType = Vec<Command>;
+pub fn get_example() -> ExType {
+    vec![
+        Command::Stop,
+        Command::Move(-4),
+        Command::Say("hi there".to_owned()),
+        Command::Jump(3, 7),
+        Command::Teleport { x: 10, y: -20 },
+    ]
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub enum Command {
+    Stop,
+    Move(i64),
+    Say(String),
+    Jump(u64, u64),
+    Teleport { x: i64, y: i64 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Script {
+    first: Command,
+    commands: Vec<Command>,
+}
+
+#[test]
+fn self_test() {
+    let example = get_example();
+    let example_str = serde_encom::to_string(&example).unwrap();
+    assert_eq!(
+        example_str,
+        "4=Stop Move:-4 Say:8=hi there {Jump[3 7]} {Teleport{x:10 y:-20}}"
+    );
+
+    let example_des: ExType = serde_encom::from_slice(example_str.as_bytes()).unwrap();
+    assert_eq!(example, example_des);
+
+    let example_des: ExType = serde_encom::from_str(&example_str).unwrap();
+    assert_eq!(example, example_des);
+}
+
+#[test]
+fn top_level() {
+    for command in get_example() {
+        let s = serde_encom::to_string(&command).unwrap();
+        let des: Command = serde_encom::from_slice(s.as_bytes()).expect(&s);
+        assert_eq!(command, des, "{s}");
+    }
+}
+
+#[test]
+fn nested() {
+    for first in get_example() {
+        let script = Script {
+            first,
+            commands: get_example(),
+        };
+        let s = serde_encom::to_string(&script).unwrap();
+        let des: Script = serde_encom::from_str(&s).unwrap();
+        assert_eq!(script, des, "{s}");
+    }
+}
+
+#[test]
+fn unit_variant_length() {
+    let err = serde_encom::from_str::<Command>("9=Stop").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "EOF while parsing a string at line 1 column 6"
+    );
+}
+
+#[test]
+fn from_reader() {
+    let example = get_example();
+    let s = serde_encom::to_string(&example).unwrap();
+    let des: ExType = serde_encom::from_reader(s.as_bytes()).unwrap();
+    assert_eq!(example, des);
+
+    for command in get_example() {
+        let s = serde_encom::to_string(&command).unwrap();
+        let des: Command = serde_encom::from_reader(s.as_bytes()).unwrap();
+        assert_eq!(command, des, "{s}");
+    }
+
+    // A `Deserializer` over an `io::Read` peeks a single byte, which doesn't
+    // tell a length prefixed variant name from other digits.
+    let mut de = serde_encom::Deserializer::from_reader("4=Stop".as_bytes());
+    let err = Command::deserialize(&mut de).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "a length prefixed identifier can't be read from an io::Read at line 1 column 1"
+    );
+}
@@ -0,0 +1,153 @@
+use serde::{Deserialize, Serialize};
+use serde_encom::Options;
+use std::collections::BTreeMap;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Profile {
+    id: u64,
+    name: String,
+    tags: Vec<String>,
+    home: Point,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nick: Option<String>,
+    shape: Shape,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Point {
+    x: i64,
+    y: i64,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub enum Shape {
+    Dot,
+    Rect { w: u64, h: u64 },
+}
+
+#[derive(Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(variant_identifier)]
+pub enum Slot {
+    #[serde(rename = "1")]
+    First,
+    #[serde(rename = "0")]
+    Second,
+}
+
+#[derive(Deserialize, Debug, PartialEq)]
+pub struct Slots {
+    slots: BTreeMap<Slot, u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct ProfileV2 {
+    id: u64,
+    name: String,
+    tags: Vec<String>,
+    home: Point,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    nick: Option<String>,
+    shape: Shape,
+    #[serde(default)]
+    score: u32,
+}
+
+pub fn get_example() -> Profile {
+    Profile {
+        id: 7,
+        name: "Ann".to_owned(),
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        home: Point { x: 3, y: -4 },
+        nick: None,
+        shape: Shape::Rect { w: 2, h: 5 },
+    }
+}
+
+fn options() -> Options {
+    Options::new().fields_by_index(true)
+}
+
+#[test]
+fn self_test() {
+    let example = get_example();
+    let example_str = options().to_string(&example).unwrap();
+    assert_eq!(
+        example_str,
+        "0:7 1:3=Ann 2[1=a 1=b] 3{0:3 1:-4} 5{Rect{0:2 1:5}}"
+    );
+
+    let example_des: Profile = options().from_slice(example_str.as_bytes()).unwrap();
+    assert_eq!(example, example_des);
+
+    let s = options().to_string_pretty(&example).unwrap();
+    let des: Profile = options().from_str(&s).unwrap();
+    assert_eq!(example, des, "{s}");
+}
+
+#[test]
+fn skipped_field_keeps_position() {
+    let mut example = get_example();
+    example.nick = Some("an".to_owned());
+    let s = options().to_string(&example).unwrap();
+    assert!(s.contains(" 4:2=an "), "{s}");
+
+    let des: Profile = options().from_str(&s).unwrap();
+    assert_eq!(example, des);
+}
+
+#[test]
+fn appended_field() {
+    let s = options().to_string(&get_example()).unwrap();
+    let des: ProfileV2 = options().from_str(&s).unwrap();
+    assert_eq!(des.score, 0);
+    assert_eq!(des.home, Point { x: 3, y: -4 });
+
+    let v2 = ProfileV2 { score: 9, ..des };
+    let s = options().to_string(&v2).unwrap();
+    assert!(s.ends_with(" 6:9"), "{s}");
+    let des: Profile = options().from_str(&s).unwrap();
+    assert_eq!(des, get_example());
+}
+
+#[test]
+fn with_variant_index() {
+    let options = options().variants_by_index(true);
+    let example = get_example();
+    let s = options.to_string(&example).unwrap();
+    assert_eq!(s, "0:7 1:3=Ann 2[1=a 1=b] 3{0:3 1:-4} 5{1{0:2 1:5}}");
+
+    let des: Profile = options.from_str(&s).unwrap();
+    assert_eq!(example, des);
+}
+
+#[test]
+fn maps_keep_keys() {
+    let mut map = BTreeMap::new();
+    map.insert("10".to_owned(), Point { x: 1, y: 2 });
+    map.insert("b".to_owned(), Point { x: 3, y: 4 });
+    let s = options().to_string(&map).unwrap();
+    assert_eq!(s, "10{0:1 1:2} b{0:3 1:4}");
+
+    let des: BTreeMap<String, Point> = options().from_str(&s).unwrap();
+    assert_eq!(map, des);
+}
+
+#[test]
+fn numeric_variant_keys() {
+    let expected = BTreeMap::from([(Slot::First, 5), (Slot::Second, 6)]);
+    let des: BTreeMap<Slot, u64> = options().from_str("1:5 0:6").unwrap();
+    assert_eq!(des, expected);
+
+    let des: Slots = options().from_str("0{1:5 0:6}").unwrap();
+    assert_eq!(des, Slots { slots: expected });
+}
+
+#[test]
+fn default_unchanged() {
+    let example = get_example();
+    assert_eq!(
+        Options::new().to_string(&example).unwrap(),
+        serde_encom::to_string(&example).unwrap()
+    );
+    assert!(serde_encom::from_str::<Point>("0:3 1:-4").is_err());
+}
@@ -0,0 +1,115 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub type ExType = Vec<Request>;
+pub fn get_example() -> ExType {
+    vec![
+        Request {
+            header: Header {
+                version: 2,
+                trace: Some("abc def".to_owned()),
+            },
+            path: "/users".to_owned(),
+            query: vec![1, 2, 3],
+            extra: BTreeMap::new(),
+        },
+        Request {
+            header: Header {
+                version: 1,
+                trace: None,
+            },
+            path: "/".to_owned(),
+            query: vec![9],
+            extra: BTreeMap::from([
+                ("debug".to_owned(), "t🔥".to_owned()),
+                ("user".to_owned(), "me".to_owned()),
+            ]),
+        },
+    ]
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Header {
+    version: u32,
+    trace: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Request {
+    #[serde(flatten)]
+    header: Header,
+    path: String,
+    query: Vec<u64>,
+    #[serde(flatten)]
+    extra: BTreeMap<String, String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Envelope {
+    #[serde(flatten)]
+    header: Header,
+    #[serde(flatten)]
+    body: Body,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "kind")]
+pub enum Body {
+    Text { text: String },
+    Sum { values: Vec<i64>, total: i64 },
+}
+
+#[test]
+fn self_test() {
+    let example = get_example();
+    let example_str = serde_encom::to_string(&example).unwrap();
+
+    let example_des: ExType = serde_encom::from_slice(example_str.as_bytes()).unwrap();
+    assert_eq!(example, example_des);
+
+    let example_des: ExType = serde_encom::from_str(&example_str).unwrap();
+    assert_eq!(example, example_des);
+}
+
+#[test]
+fn top_level() {
+    for request in get_example() {
+        let s = serde_encom::to_string(&request).unwrap();
+        let des: Request = serde_encom::from_slice(s.as_bytes()).unwrap();
+        assert_eq!(request, des, "{s}");
+    }
+}
+
+#[test]
+fn flattened_enum() {
+    let envelopes = vec![
+        Envelope {
+            header: Header {
+                version: 3,
+                trace: None,
+            },
+            body: Body::Text {
+                text: "hi".to_owned(),
+            },
+        },
+        Envelope {
+            header: Header {
+                version: 3,
+                trace: Some("t".to_owned()),
+            },
+            body: Body::Sum {
+                values: vec![-1, 5],
+                total: 4,
+            },
+        },
+    ];
+    for envelope in &envelopes {
+        let s = serde_encom::to_string(envelope).unwrap();
+        let des: Envelope = serde_encom::from_str(&s).unwrap();
+        assert_eq!(envelope, &des, "{s}");
+    }
+
+    let s = serde_encom::to_string(&envelopes).unwrap();
+    let des: Vec<Envelope> = serde_encom::from_str(&s).unwrap();
+    assert_eq!(envelopes, des, "{s}");
+}
@@ -0,0 +1,99 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub type ExType = Vec<Message>;
+pub fn get_example() -> ExType {
+    vec![
+        Message::Ping,
+        Message::Request {
+            id: 42,
+            method: "get user".to_owned(),
+            params: vec!["a".to_owned(), "b".to_owned()],
+        },
+        Message::Response(Response {
+            id: 42,
+            ok: true,
+            body: Some("n🔥".to_owned()),
+        }),
+        Message::Error(Failure {
+            code: -32600,
+            details: BTreeMap::from([("field".to_owned(), 7), ("limit".to_owned(), 10)]),
+        }),
+        Message::Nested {
+            inner: Kind::Float { value: 2.5 },
+        },
+    ]
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "type")]
+pub enum Message {
+    Ping,
+    Request {
+        id: u64,
+        method: String,
+        params: Vec<String>,
+    },
+    Response(Response),
+    Error(Failure),
+    Nested {
+        inner: Kind,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Response {
+    id: u64,
+    ok: bool,
+    body: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Failure {
+    code: i64,
+    details: BTreeMap<String, u64>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(tag = "kind", rename_all = "lowercase")]
+pub enum Kind {
+    Int { value: i64 },
+    Float { value: f64 },
+}
+
+#[test]
+fn self_test() {
+    let example = get_example();
+    let example_str = serde_encom::to_string(&example).unwrap();
+
+    let example_des: ExType = serde_encom::from_slice(example_str.as_bytes()).unwrap();
+    assert_eq!(example, example_des);
+
+    let example_des: ExType = serde_encom::from_str(&example_str).unwrap();
+    assert_eq!(example, example_des);
+}
+
+#[test]
+fn top_level() {
+    for message in get_example() {
+        let s = serde_encom::to_string(&message).unwrap();
+        let des: Message = serde_encom::from_slice(s.as_bytes()).unwrap();
+        assert_eq!(message, des, "{s}");
+    }
+}
+
+#[test]
+fn format() {
+    let s = serde_encom::to_string(&get_example()[1]).unwrap();
+    assert_eq!(s, "type:7=Request id:42 method:8=get user params[1=a 1=b]");
+
+    let des: Message = serde_encom::from_str("id:1 params[] type:7=Request method:0=").unwrap();
+    assert_eq!(
+        des,
+        Message::Request {
+            id: 1,
+            method: String::new(),
+            params: Vec::new(),
+        }
+    );
+}
@@ -1,14 +1,32 @@
 #[cfg(feature = "std")]
+mod adjacently_tagged;
+#[cfg(feature = "std")]
 mod big;
 #[cfg(feature = "std")]
 mod big_rename;
 mod bool;
 #[cfg(feature = "std")]
 mod bytes;
+#[cfg(feature = "std")]
+mod externally_tagged;
+#[cfg(feature = "std")]
+mod field_index;
+#[cfg(feature = "std")]
+mod flatten;
+#[cfg(feature = "std")]
+mod internally_tagged;
 mod option_int;
 mod option_string;
 #[cfg(feature = "std")]
 mod small_rename;
 mod str_slice;
 #[cfg(feature = "std")]
+mod unknown_fields;
+#[cfg(feature = "std")]
+mod untagged;
+#[cfg(feature = "std")]
+mod variant_index;
+#[cfg(feature = "std")]
 mod vec_str;
+#[cfg(feature = "std")]
+mod with;
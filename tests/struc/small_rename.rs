@@ -41,7 +41,7 @@ pub struct Second {
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq)]
-// #[serde(untagged)]
+#[serde(untagged)]
 pub enum SecondEnum {
     #[serde(rename = "1")]
     U64(u64),
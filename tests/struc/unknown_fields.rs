@@ -0,0 +1,128 @@
+use serde::de::IgnoredAny;
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Small {
+    id: u64,
+    name: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Inner {
+    text: String,
+    more: Vec<Inner>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Big {
+    id: u64,
+    body: String,
+    #[serde(with = "serde_bytes")]
+    raw: Vec<u8>,
+    floats: Vec<f64>,
+    nested: Inner,
+    flags: Vec<bool>,
+    missing: Option<u8>,
+    name: String,
+}
+
+fn big() -> Big {
+    Big {
+        id: 7,
+        // Delimiters inside strings and bytes are jumped over.
+        body: "a:1 b[2] c{3}} ]".repeat(10),
+        raw: b"]}\x00 :{[".to_vec(),
+        floats: vec![-1.5, 2e-300, 340282366920938463463374607431768211456.0],
+        nested: Inner {
+            text: "x".to_owned(),
+            more: vec![Inner {
+                text: "}".to_owned(),
+                more: vec![],
+            }],
+        },
+        flags: vec![true, false],
+        missing: None,
+        name: "big".to_owned(),
+    }
+}
+
+#[test]
+fn test_skip_unknown_fields() {
+    let encom = serde_encom::to_string(&big()).unwrap();
+    let small: Small = serde_encom::from_str(&encom).unwrap();
+    assert_eq!(
+        small,
+        Small {
+            id: 7,
+            name: "big".to_owned()
+        }
+    );
+
+    let pretty = serde_encom::to_string_pretty(&big()).unwrap();
+    assert_eq!(serde_encom::from_str::<Small>(&pretty).unwrap(), small);
+}
+
+#[test]
+fn test_skip_values() {
+    for value in [
+        "5",
+        "-5.5e3",
+        "1E+2",
+        "t",
+        "f",
+        "n",
+        "5=a b:c",
+        "0=",
+        "3~]}:",
+        "4$YWJj",
+        "6#616263",
+        "20@2023-11-14T22:13:20Z",
+        "340282366920938463463374607431768211456",
+        "[]",
+        "{}",
+        "[1 2 3]",
+        "[{a:1} [2] 1=q]",
+        "{a:5=}}}}} b{c:1} d[n]}",
+        "{1.5:t 0:1=x}",
+    ] {
+        let encom = format!("x:{value} id:1 name:1=n");
+        let small: Small = serde_encom::from_str(&encom).unwrap();
+        assert_eq!(small.id, 1, "{encom}");
+    }
+
+    // A map value after the key, without a colon.
+    let small: Small = serde_encom::from_str("x[1 2] y{z:1} id:1 name:0=").unwrap();
+    assert_eq!(small.id, 1);
+}
+
+#[test]
+fn test_skip_errors() {
+    for encom in [
+        // The length prefix has to cover the value.
+        "x:10=abc id:1 name:0=",
+        "x:2=abc id:1 name:0=",
+        "x[1 2 id:1 name:0=",
+        "x{a:1 id:1 name:0=",
+        "x:] id:1 name:0=",
+        "x:ab=c id:1 name:0=",
+        "x:",
+    ] {
+        assert!(serde_encom::from_str::<Small>(encom).is_err(), "{encom}");
+    }
+
+    let deep = format!("x:{}1{} id:1 name:0=", "[".repeat(200), "]".repeat(200));
+    assert!(serde_encom::from_str::<Small>(&deep).is_err());
+    // Keys after keys are skipped without recursion.
+    let chain = format!("x:{}1 id:1 name:0=", "a:".repeat(100_000));
+    assert!(serde_encom::from_str::<Small>(&chain).is_ok());
+}
+
+#[test]
+fn test_ignored_any() {
+    let encom = serde_encom::to_string(&big()).unwrap();
+    serde_encom::from_str::<IgnoredAny>(&encom).unwrap();
+    serde_encom::from_str::<Vec<IgnoredAny>>("1 5=hello [1 2] {a:1}").unwrap();
+    let (first, _, last): (u8, IgnoredAny, u8) =
+        serde_encom::from_str("1 {a[5=x]}}] ] b:2} 3").unwrap();
+    assert_eq!((first, last), (1, 3));
+}
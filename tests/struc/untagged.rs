@@ -0,0 +1,84 @@
+use serde::{Deserialize, Serialize};
+
+pub type ExType = Vec<Shape>;
+pub fn get_example() -> ExType {
+    vec![
+        Shape::Number(7),
+        Shape::Signed(-3),
+        Shape::Float(1.5),
+        Shape::Flag(true),
+        Shape::Text("hello world".to_owned()),
+        Shape::List(vec![2, 5, 7]),
+        Shape::Point { x: 1, y: 2 },
+        Shape::Named {
+            name: "n🔥".to_owned(),
+            tags: vec!["a".to_owned(), "b c".to_owned()],
+        },
+        Shape::Empty,
+    ]
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(untagged)]
+pub enum Shape {
+    Number(u64),
+    Signed(i64),
+    Float(f64),
+    Flag(bool),
+    Text(String),
+    List(Vec<u64>),
+    Point { x: u64, y: u64 },
+    Named { name: String, tags: Vec<String> },
+    Empty,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Holder {
+    first: Shape,
+    second: Option<Shape>,
+    rest: Vec<Shape>,
+}
+
+#[test]
+fn self_test() {
+    let example = get_example();
+    let example_str = serde_encom::to_string(&example).unwrap();
+
+    let example_des: ExType = serde_encom::from_slice(example_str.as_bytes()).unwrap();
+    assert_eq!(example, example_des);
+
+    let example_des: ExType = serde_encom::from_str(&example_str).unwrap();
+    assert_eq!(example, example_des);
+}
+
+#[test]
+fn each_variant() {
+    for shape in get_example() {
+        let holder = Holder {
+            first: shape,
+            second: Some(Shape::Point { x: 3, y: 4 }),
+            rest: vec![Shape::Text("x".to_owned()), Shape::Number(1)],
+        };
+        let s = serde_encom::to_string(&holder).unwrap();
+        let des: Holder = serde_encom::from_str(&s).unwrap();
+        assert_eq!(holder, des, "{s}");
+    }
+}
+
+/// A top level scalar is read as a one element array, so only map shaped
+/// variants round-trip on their own.
+#[test]
+fn top_level() {
+    let shapes = [
+        Shape::Point { x: 1, y: 2 },
+        Shape::Named {
+            name: "top".to_owned(),
+            tags: vec!["t".to_owned()],
+        },
+    ];
+    for shape in shapes {
+        let s = serde_encom::to_string(&shape).unwrap();
+        let des: Shape = serde_encom::from_str(&s).unwrap();
+        assert_eq!(shape, des, "{s}");
+    }
+}
@@ -0,0 +1,131 @@
+use serde::{Deserialize, Serialize};
+use serde_encom::Options;
+use std::collections::BTreeMap;
+
+pub type ExType = Vec<Command>;
+pub fn get_example() -> ExType {
+    vec![
+        Command::Stop,
+        Command::Move(-4),
+        Command::Say("hi there".to_owned()),
+        Command::Jump(3, 7),
+        Command::Teleport { x: 10, y: -20 },
+    ]
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub enum Command {
+    Stop,
+    Move(i64),
+    Say(String),
+    Jump(u64, u64),
+    Teleport { x: i64, y: i64 },
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct Script {
+    first: Command,
+    commands: Vec<Command>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, PartialOrd, Ord, Eq)]
+pub enum Key {
+    Left,
+    Right,
+}
+
+fn options() -> Options {
+    Options::new().variants_by_index(true)
+}
+
+#[test]
+fn self_test() {
+    let example = get_example();
+    let example_str = options().to_string(&example).unwrap();
+    assert_eq!(example_str, "0 1:-4 2:8=hi there {3[3 7]} {4{x:10 y:-20}}");
+
+    let example_des: ExType = options().from_slice(example_str.as_bytes()).unwrap();
+    assert_eq!(example, example_des);
+}
+
+#[test]
+fn top_level() {
+    for command in get_example() {
+        let s = options().to_string(&command).unwrap();
+        let des: Command = options().from_str(&s).expect(&s);
+        assert_eq!(command, des, "{s}");
+    }
+}
+
+#[test]
+fn nested() {
+    for first in get_example() {
+        let script = Script {
+            first,
+            commands: get_example(),
+        };
+        let s = options().to_string(&script).unwrap();
+        let des: Script = options().from_str(&s).unwrap();
+        assert_eq!(script, des, "{s}");
+
+        let s = options().to_string_pretty(&script).unwrap();
+        let des: Script = options().from_str(&s).unwrap();
+        assert_eq!(script, des, "{s}");
+    }
+}
+
+#[test]
+fn nested_output() {
+    let script = Script {
+        first: Command::Move(7),
+        commands: vec![Command::Stop],
+    };
+    let s = options().to_string(&script).unwrap();
+    assert_eq!(s, "first{1:7} commands[0]");
+}
+
+#[test]
+fn map_keys_keep_names() {
+    let mut map = BTreeMap::new();
+    map.insert(Key::Left, Command::Stop);
+    map.insert(Key::Right, Command::Move(1));
+    let s = options().to_string(&map).unwrap();
+    assert_eq!(s, "Left:0 Right{1:1}");
+
+    let des: BTreeMap<Key, Command> = options().from_str(&s).unwrap();
+    assert_eq!(map, des);
+}
+
+#[test]
+fn names_still_accepted() {
+    let des: ExType = options()
+        .from_str("4=Stop Move:-4 {Teleport{x:10 y:-20}}")
+        .unwrap();
+    assert_eq!(
+        des,
+        vec![
+            Command::Stop,
+            Command::Move(-4),
+            Command::Teleport { x: 10, y: -20 }
+        ]
+    );
+}
+
+#[test]
+fn default_unchanged() {
+    let example = get_example();
+    assert_eq!(
+        Options::new().to_string(&example).unwrap(),
+        serde_encom::to_string(&example).unwrap()
+    );
+    assert!(serde_encom::from_str::<ExType>("0 1:-4").is_err());
+}
+
+#[test]
+fn out_of_range() {
+    let err = options().from_str::<Command>("9").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "invalid value: integer `9`, expected variant index 0 <= i < 5"
+    );
+}
@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+use serde_encom::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+pub struct W1 {
+    #[serde(with = "serde_encom::with::bytes")]
+    bytes: Vec<u8>,
+    #[serde(with = "serde_encom::with::bytes")]
+    boxed: Box<[u8]>,
+    #[serde(with = "serde_encom::with::display_fromstr")]
+    big: u128,
+    #[serde(with = "serde_encom::with::display_fromstr")]
+    neg: i128,
+    #[serde(with = "serde_encom::with::duration")]
+    duration: Duration,
+    #[serde(with = "serde_encom::with::system_time")]
+    time: SystemTime,
+    #[serde(with = "serde_encom::with::pairs")]
+    pairs: BTreeMap<(u8, bool), Vec<String>>,
+    #[serde(with = "serde_encom::with::pairs")]
+    hash: HashMap<i32, f64>,
+}
+
+pub fn get_example() -> W1 {
+    W1 {
+        bytes: vec![0, 0xff, b'=', b' '],
+        boxed: Box::new([1, 2]),
+        big: u128::MAX,
+        neg: i128::MIN,
+        duration: Duration::new(3, 5),
+        time: UNIX_EPOCH + Duration::new(1_700_000_000, 123),
+        pairs: BTreeMap::from([
+            ((1, true), vec!["a".to_owned()]),
+            ((2, false), vec!["b c".to_owned(), "d".to_owned()]),
+        ]),
+        hash: HashMap::from([(-1, 0.5)]),
+    }
+}
+
+fn round_trip<T>(value: &T) -> T
+where
+    T: Serialize + for<'de> Deserialize<'de>,
+{
+    let vec = serde_encom::to_vec(value).unwrap();
+    let from_slice: T = serde_encom::from_slice(&vec).unwrap();
+    let string = serde_encom::to_string(value).unwrap();
+    let from_str: T = serde_encom::from_str(&string).unwrap();
+    let from_value: T = serde_encom::from_value(serde_encom::to_value(value).unwrap()).unwrap();
+    assert_eq!(
+        serde_encom::to_vec(&from_str).unwrap(),
+        vec,
+        "from_str of {}",
+        string
+    );
+    assert_eq!(serde_encom::to_vec(&from_value).unwrap(), vec);
+    from_slice
+}
+
+#[test]
+fn self_test() {
+    let example = get_example();
+    assert_eq!(round_trip(&example), example);
+}
+
+#[test]
+fn test_with_encoding() {
+    let example = get_example();
+    let value = serde_encom::to_value(&example).unwrap();
+    assert_eq!(value["bytes"], Value::Bytes(vec![0, 0xff, b'=', b' ']));
+    assert_eq!(value["big"], "340282366920938463463374607431768211455");
+    assert_eq!(value["neg"], "-170141183460469231731687303715884105728");
+    assert_eq!(value["duration"], 3_000_000_005u64);
+    assert_eq!(value["time"], 1_700_000_000_000_000_123i64);
+    assert_eq!(
+        value["pairs"],
+        serde_encom::encom_from_json!([[[1, true], ["a"]], [[2, false], ["b c", "d"]]])
+    );
+    assert_eq!(value["hash"], serde_encom::encom_from_json!([[-1, 0.5]]));
+
+    let vec = serde_encom::to_vec(&example).unwrap();
+    let text = String::from_utf8_lossy(&vec);
+    assert!(text.contains("duration:3000000005 "), "{}", text);
+    assert!(
+        text.contains("pairs[[[1 t] [1=a]] [[2 f] [3=b c 1=d]]]"),
+        "{}",
+        text
+    );
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Times {
+    #[serde(with = "serde_encom::with::duration")]
+    duration: Duration,
+    #[serde(with = "serde_encom::with::system_time")]
+    time: SystemTime,
+}
+
+#[test]
+fn test_with_time_range() {
+    let times = Times {
+        duration: Duration::MAX,
+        time: UNIX_EPOCH - Duration::new(1, 1),
+    };
+    let vec = serde_encom::to_vec(&times).unwrap();
+    assert_eq!(
+        vec,
+        b"duration:18446744073709551615999999999 time:-1000000001"
+    );
+    assert_eq!(serde_encom::from_slice::<Times>(&vec).unwrap(), times);
+
+    // Over an i64 of nanoseconds, in the year 2262.
+    let times = Times {
+        duration: Duration::ZERO,
+        time: UNIX_EPOCH + Duration::from_secs(1 << 33),
+    };
+    let vec = serde_encom::to_vec(&times).unwrap();
+    assert_eq!(vec, b"duration:0 time:8589934592000000000");
+    assert_eq!(serde_encom::from_slice::<Times>(&vec).unwrap(), times);
+
+    let err = serde_encom::from_slice::<Times>(b"duration:18446744073709551616000000000 time:0")
+        .unwrap_err();
+    assert!(err.is_data());
+    assert!(err
+        .to_string()
+        .starts_with("overflow deserializing Duration"));
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Loose {
+    #[serde(with = "serde_encom::with::bytes")]
+    a: Vec<u8>,
+    #[serde(with = "serde_encom::with::pairs")]
+    b: BTreeMap<u8, u8>,
+}
+
+#[test]
+fn test_with_lenient_input() {
+    let loose: Loose = serde_encom::from_str("a:2=hi b[[1 2] [1 3]]").unwrap();
+    assert_eq!(loose.a, b"hi");
+    // The last pair with a key wins.
+    assert_eq!(loose.b, BTreeMap::from([(1, 3)]));
+
+    // Arrays of numbers are bytes in a `Value`.
+    let value = serde_encom::encom_from_json!({ "a": [1, 2], "b": [] });
+    let loose: Loose = serde_encom::from_value(value).unwrap();
+    assert_eq!(loose.a, [1, 2]);
+
+    let err = serde_encom::from_str::<Loose>("a:0= b[[1 2] [1]]").unwrap_err();
+    assert!(err.is_data());
+    let err = serde_encom::from_str::<Loose>("a:1 b[]").unwrap_err();
+    assert!(err.is_data());
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct Number {
+    #[serde(with = "serde_encom::with::display_fromstr")]
+    n: u64,
+}
+
+#[test]
+fn test_with_display_fromstr_errors() {
+    let err = serde_encom::from_str::<Number>("n:2=-1").unwrap_err();
+    assert!(err.is_data());
+    assert_eq!(
+        err.to_string(),
+        "invalid digit found in string at line 1 column 6"
+    );
+
+    let err = serde_encom::from_str::<Number>("n:1").unwrap_err();
+    assert!(err.is_data());
+}
@@ -0,0 +1,107 @@
+use serde_encom::{encom_from_json, Value};
+use std::borrow::Cow;
+
+#[test]
+fn test_bytes_accessors() {
+    let mut v = encom_from_json!({ "a": b"\x01\xff", "b": [1, 255], "c": "ab" });
+
+    assert!(v["a"].is_bytes());
+    assert!(!v["b"].is_bytes());
+    assert!(!v["c"].is_bytes());
+    assert_eq!(v["a"].as_bytes(), Some(&[1, 255][..]));
+    assert_eq!(v["c"].as_bytes(), None);
+
+    v["a"].as_bytes_mut().unwrap().clear();
+    assert_eq!(v["a"], Value::Bytes(Vec::new()));
+    assert_eq!(v["b"].as_bytes_mut(), None);
+}
+
+#[test]
+fn test_bytes_eq() {
+    let v = Value::Bytes(vec![1, 255]);
+    let bytes: &[u8] = &[1, 255];
+
+    assert_eq!(v, *bytes);
+    assert_eq!(v, bytes);
+    assert_eq!(v, [1, 255]);
+    assert_eq!(v, b"\x01\xff");
+    assert_eq!(v, vec![1u8, 255]);
+    assert_eq!(*bytes, v);
+    assert_eq!(bytes, v);
+    assert_eq!(vec![1u8, 255], v);
+
+    assert_ne!(v, b"\x01");
+    assert_ne!(encom_from_json!([1, 255]), bytes);
+    assert_ne!(encom_from_json!("ab"), b"ab");
+}
+
+#[test]
+fn test_bytes_from() {
+    let borrowed: Value = Cow::Borrowed(&b"ab"[..]).into();
+    let owned: Value = Cow::<[u8]>::Owned(vec![1, 2]).into();
+    assert_eq!(borrowed, Value::Bytes(b"ab".to_vec()));
+    assert_eq!(owned, Value::Bytes(vec![1, 2]));
+
+    // Vectors and slices of any type, u8 included, are arrays.
+    let array: Value = vec![1u8, 2].into();
+    assert_eq!(array, encom_from_json!([1, 2]));
+    let array: Value = (&[1u8, 2][..]).into();
+    assert_eq!(array, encom_from_json!([1, 2]));
+}
+
+#[test]
+fn test_bytes_macro() {
+    let key = [1u8, 2];
+    let neg = 3;
+    let v = encom_from_json!({
+        "a": b"ab",
+        "b": [b"", b"\xff", 1, -1, "s"],
+        "c": key,
+        "d": -neg,
+        "e": b"x"
+    });
+
+    assert_eq!(v["a"], Value::Bytes(b"ab".to_vec()));
+    assert_eq!(
+        v["b"],
+        Value::Array(vec![
+            Value::Bytes(Vec::new()),
+            Value::Bytes(vec![255]),
+            1.into(),
+            (-1).into(),
+            "s".into(),
+        ])
+    );
+    assert_eq!(v["c"], encom_from_json!([1, 2]));
+    assert_eq!(v["d"], -3);
+    assert_eq!(v["e"], b"x");
+    assert_eq!(encom_from_json!(b"ab"), Value::Bytes(b"ab".to_vec()));
+    assert_eq!(encom_from_json!([b"ab"]), encom_from_json!([b"ab",]));
+}
+
+#[test]
+fn test_bytes_index_and_pointer() {
+    let mut v = encom_from_json!({ "data": b"\x01\x02" });
+
+    assert_eq!(v["data"][0], Value::Null);
+    assert_eq!(v["data"].get(0), None);
+    assert_eq!(v.pointer("/data"), Some(&Value::Bytes(vec![1, 2])));
+    assert_eq!(v.pointer("/data/0"), None);
+    assert_eq!(v.pointer_mut("/data/0"), None);
+}
+
+#[test]
+#[should_panic(expected = "cannot access index 0 of EnCom bytes")]
+fn test_bytes_index_mut() {
+    let mut v = encom_from_json!({ "data": b"\x01\x02" });
+    v["data"][0] = Value::Null;
+}
+
+#[test]
+fn test_bytes_roundtrip() {
+    let v = encom_from_json!({ "data": b"\x01\xff" });
+    let s = serde_encom::to_vec(&v).unwrap();
+    assert_eq!(s, b"data:2~\x01\xff");
+    let back: Value = serde_encom::from_slice(&s).unwrap();
+    assert_eq!(back, v);
+}
@@ -0,0 +1,125 @@
+use serde_encom::{encom_from_json, value::BytesPolicy, Value};
+use serde_json::json;
+
+#[test]
+fn test_from_json() {
+    let json = json!({
+        "name": "encom",
+        "count": 3,
+        "offset": -7,
+        "ratio": 2.0,
+        "tags": ["a", null, true],
+        "nested": { "empty": {} }
+    });
+    let v = Value::from(&json);
+    assert_eq!(v, Value::from(json));
+
+    assert_eq!(
+        v,
+        encom_from_json!({
+            "name": "encom",
+            "count": 3,
+            "offset": -7,
+            "ratio": 2.0,
+            "tags": ["a", null, true],
+            "nested": { "empty": {} }
+        })
+    );
+    assert!(v["count"].is_u64());
+    assert!(v["offset"].is_i64());
+    assert!(v["ratio"].is_f64());
+}
+
+#[test]
+fn test_to_json() {
+    let v: Value = serde_encom::from_str("a:1 b:-1 c:1.5 d:n e:t f[3=xyz] g{h:f}").unwrap();
+    let json = serde_json::Value::try_from(&v).unwrap();
+    assert_eq!(
+        json,
+        json!({ "a": 1, "b": -1, "c": 1.5, "d": null, "e": true, "f": ["xyz"], "g": { "h": false } })
+    );
+    assert!(json["a"].is_u64());
+    assert!(json["b"].is_i64());
+    assert!(json["c"].is_f64());
+
+    assert_eq!(serde_json::Value::try_from(v).unwrap(), json);
+}
+
+#[test]
+fn test_float_stays_float() {
+    let v = Value::from(json!(1.0));
+    assert!(v.is_f64());
+    let json = serde_json::Value::try_from(&v).unwrap();
+    assert!(json.is_f64());
+    assert_eq!(json.to_string(), "1.0");
+}
+
+#[test]
+fn test_bytes_policy() {
+    let v: Value = serde_encom::from_str("a[2~hi] b:0~").unwrap();
+
+    assert_eq!(
+        v.to_json(BytesPolicy::Base64).unwrap(),
+        json!({ "a": ["aGk="], "b": "" })
+    );
+    assert_eq!(
+        v.to_json(BytesPolicy::Array).unwrap(),
+        json!({ "a": [[104, 105]], "b": [] })
+    );
+
+    let err = v.to_json(BytesPolicy::Error).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "bytes at `/a/0` can't be represented in JSON"
+    );
+    let err = serde_json::Value::try_from(v).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "bytes at `/a/0` can't be represented in JSON"
+    );
+}
+
+#[test]
+fn test_bytes_error_path_escaped() {
+    let v = encom_from_json!({ "a/b": [1, Value::Bytes(vec![1])] });
+    let err = v.into_json(BytesPolicy::Error).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "bytes at `/a~1b/1` can't be represented in JSON"
+    );
+}
+
+#[cfg(feature = "preserve_order")]
+#[test]
+fn test_key_order() {
+    let json: serde_json::Value =
+        serde_json::from_str(r#"{"z":1,"a":{"y":2,"b":3},"m":4}"#).unwrap();
+    let v = Value::from(&json);
+    let keys: Vec<_> = v.as_object().unwrap().keys().cloned().collect();
+    assert_eq!(keys, ["z", "a", "m"]);
+    assert_eq!(serde_encom::to_string(&v).unwrap(), "z:1 a{y:2 b:3} m:4");
+
+    let back = v.into_json(BytesPolicy::Error).unwrap();
+    assert_eq!(back.to_string(), r#"{"z":1,"a":{"y":2,"b":3},"m":4}"#);
+}
+
+#[test]
+fn test_wide_integer() {
+    let v: Value = serde_encom::from_str("a[1 170141183460469231731687303715884105727]").unwrap();
+    let err = v.to_json(BytesPolicy::Base64).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "integer 170141183460469231731687303715884105727 at `/a/1` can't be represented in JSON"
+    );
+    let err = serde_json::Value::try_from(v).unwrap_err();
+    assert!(err.to_string().starts_with("integer "));
+
+    let v: Value = serde_encom::from_str("n:-18446744073709551616").unwrap();
+    assert!(v.into_json(BytesPolicy::Array).is_err());
+
+    let v: Value = serde_encom::from_str("n:-9223372036854775808 m:18446744073709551615").unwrap();
+    assert_eq!(
+        v.into_json(BytesPolicy::Error).unwrap(),
+        json!({ "n": i64::MIN, "m": u64::MAX })
+    );
+}
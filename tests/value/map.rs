@@ -178,5 +178,5 @@ fn test_number_neg_float() {
 #[test]
 fn test_boolean_like() {
     let data = "fields[33 22] trees:0=";
-    let v: Value = serde_encom::from_str(data).unwrap();
+    let _v: Value = serde_encom::from_str(data).unwrap();
 }
@@ -1,2 +1,8 @@
+mod bytes;
+#[cfg(feature = "serde_json")]
+mod json;
 mod map;
+mod number;
+mod patch;
+mod query;
 mod seq;
@@ -0,0 +1,76 @@
+use serde_encom::{Number, Value};
+
+#[test]
+fn int128() {
+    for (text, expected) in [
+        ("18446744073709551616", Number::from(u64::MAX as u128 + 1)),
+        (
+            "340282366920938463463374607431768211455",
+            Number::from(u128::MAX),
+        ),
+        ("-9223372036854775809", Number::from(i64::MIN as i128 - 1)),
+        ("-18446744073709551615", Number::from(-(u64::MAX as i128))),
+        (
+            "-170141183460469231731687303715884105728",
+            Number::from(i128::MIN),
+        ),
+    ] {
+        let encom = format!("n:{text}");
+        let value: Value = serde_encom::from_str(&encom).unwrap();
+        let number = value["n"].as_number().unwrap();
+        assert_eq!(*number, expected);
+        assert_eq!(number.to_string(), text);
+        assert_eq!(serde_encom::to_string(&value).unwrap(), encom);
+        assert!(!number.is_i64() && !number.is_u64() && !number.is_f64());
+    }
+
+    let value: Value = serde_encom::from_str("n:18446744073709551616").unwrap();
+    assert_eq!(value["n"].as_u128(), Some(1 << 64));
+    assert_eq!(value["n"].as_i128(), Some(1 << 64));
+    assert_eq!(value["n"].as_u64(), None);
+    assert_eq!(value["n"], 1u128 << 64);
+}
+
+#[test]
+fn int128_normalized() {
+    // Integers that fit in 64 bits are the same, however they were made.
+    assert_eq!(Number::from(5u128), Number::from(5u64));
+    assert_eq!(Number::from(-5i128), Number::from(-5i64));
+    assert_eq!(Number::from(u64::MAX as i128), Number::from(u64::MAX));
+    assert_eq!(Number::from(7i128).as_u64(), Some(7));
+    assert_eq!(Value::from(-7i128).as_i64(), Some(-7));
+}
+
+#[test]
+fn int128_in_containers() {
+    let text = "a:340282366920938463463374607431768211455 b:-100000000000000000000 c:1";
+    let value: Value = serde_encom::from_str(text).unwrap();
+    assert_eq!(value["a"].as_u128(), Some(u128::MAX));
+    assert_eq!(value["b"].as_i128(), Some(-100_000_000_000_000_000_000));
+    assert_eq!(value["c"].as_u64(), Some(1));
+    assert_eq!(serde_encom::to_string(&value).unwrap(), text);
+
+    let text = "a[18446744073709551616 -100000000000000000000 1 -1]";
+    let value: Value = serde_encom::from_str(text).unwrap();
+    assert_eq!(value["a"][0].as_u128(), Some(1 << 64));
+    assert_eq!(value["a"][1].as_i128(), Some(-100_000_000_000_000_000_000));
+    assert_eq!(value["a"][2], 1);
+    assert_eq!(value["a"][3], -1);
+
+    // 128-bit integers read back through a `Value`.
+    let amounts: Vec<i128> = serde_encom::from_value(value["a"].clone()).unwrap();
+    assert_eq!(amounts, [1 << 64, -100_000_000_000_000_000_000, 1, -1]);
+    let value = serde_encom::to_value([u128::MAX]).unwrap();
+    assert_eq!(value[0].as_u128(), Some(u128::MAX));
+}
+
+#[test]
+fn int128_out_of_range() {
+    assert!(serde_encom::from_str::<Value>("n:340282366920938463463374607431768211456").is_err());
+    assert!(serde_encom::from_str::<Value>("n:-170141183460469231731687303715884105729").is_err());
+    assert!(serde_encom::from_str::<Value>("a[340282366920938463463374607431768211456]").is_err());
+
+    // Floats keep being floats, however long their integer part.
+    let value: Value = serde_encom::from_str("n:-9223372036854775809.5").unwrap();
+    assert!(value["n"].is_f64());
+}
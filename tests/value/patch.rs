@@ -0,0 +1,125 @@
+use serde_encom::{
+    encom_from_json,
+    value::{diff, Patch, PatchOperation},
+    Value,
+};
+
+#[test]
+fn test_merge() {
+    let mut v: Value = serde_encom::from_str("a:1 b{c:2 d:3} e[1 2]").unwrap();
+    let patch: Value = serde_encom::from_str("a:n b{c:4 f:5} e[3] g:1=x").unwrap();
+    v.merge(&patch);
+
+    let res = encom_from_json!({
+        "b": { "c": 4, "d": 3, "f": 5 },
+        "e": [3],
+        "g": "x"
+    });
+    assert_eq!(v, res);
+}
+
+#[test]
+fn test_merge_non_object() {
+    let mut v = encom_from_json!([1, 2]);
+    v.merge(&encom_from_json!({ "a": { "b": null, "c": 1 } }));
+    assert_eq!(v, encom_from_json!({ "a": { "c": 1 } }));
+
+    v.merge(&encom_from_json!("replaced"));
+    assert_eq!(v, encom_from_json!("replaced"));
+}
+
+#[test]
+fn test_diff_apply() {
+    let a = encom_from_json!({
+        "a": 1,
+        "b": { "c": [1, 2, 3], "d": "x" },
+        "e/f": true,
+        "g~h": null
+    });
+    let b = encom_from_json!({
+        "a": 1,
+        "b": { "c": [1, 5], "i": 1.5 },
+        "e/f": false,
+        "j": [1, [2]]
+    });
+    let patch = diff(&a, &b);
+    assert!(patch.iter().any(|op| op.path() == "/e~1f"));
+    assert!(patch.iter().any(|op| op.path() == "/g~0h"));
+
+    let mut c = a.clone();
+    c.apply(&patch).unwrap();
+    assert_eq!(c, b);
+
+    let mut c = b.clone();
+    c.apply(&diff(&b, &a)).unwrap();
+    assert_eq!(c, a);
+}
+
+#[test]
+fn test_diff_equal() {
+    let a = encom_from_json!({ "a": [1, { "b": 2 }] });
+    assert!(diff(&a, &a).is_empty());
+}
+
+#[test]
+fn test_diff_bytes() {
+    let a = Value::Array(vec![Value::Bytes(vec![1, 2, 3]), Value::Bytes(vec![4])]);
+    let b = Value::Array(vec![Value::Bytes(vec![1, 2]), Value::Bytes(vec![4])]);
+    let patch = diff(&a, &b);
+    assert_eq!(
+        patch.0,
+        vec![PatchOperation::Replace {
+            path: "/0".to_owned(),
+            value: Value::Bytes(vec![1, 2]),
+        }]
+    );
+
+    let mut c = a.clone();
+    c.apply(&patch).unwrap();
+    assert_eq!(c, b);
+}
+
+#[test]
+fn test_apply_append() {
+    let mut v = encom_from_json!({ "a": [1] });
+    let patch = Patch(vec![
+        PatchOperation::Add {
+            path: "/a/-".to_owned(),
+            value: 2.into(),
+        },
+        PatchOperation::Add {
+            path: "/a/0".to_owned(),
+            value: 0.into(),
+        },
+    ]);
+    v.apply(&patch).unwrap();
+    assert_eq!(v, encom_from_json!({ "a": [0, 1, 2] }));
+}
+
+#[test]
+fn test_apply_missing() {
+    let mut v = encom_from_json!({ "a": [1] });
+    for op in [
+        PatchOperation::Remove {
+            path: "/b".to_owned(),
+        },
+        PatchOperation::Remove {
+            path: "/a/1".to_owned(),
+        },
+        PatchOperation::Replace {
+            path: "/b/c".to_owned(),
+            value: Value::Null,
+        },
+        PatchOperation::Add {
+            path: "/a/2".to_owned(),
+            value: Value::Null,
+        },
+        PatchOperation::Add {
+            path: "a".to_owned(),
+            value: Value::Null,
+        },
+    ] {
+        assert!(v.apply(&Patch(vec![op])).is_err());
+    }
+    assert_eq!(v, encom_from_json!({ "a": [1] }));
+}
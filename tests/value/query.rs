@@ -0,0 +1,111 @@
+use serde_encom::{encom_from_json, value::Query, Value};
+
+fn admins() -> Value {
+    encom_from_json!({
+        "admins": [
+            { "name": "admin", "balance": 10.4, "folder": "main", "number": 2 },
+            { "name": "", "balance": -2.3, "folder": "other", "number": 5 },
+            { "name": "root", "balance": 7.5, "folder": "main", "number": 9 }
+        ],
+        "mapping": { "files": "/static/*", "tools": "/tools/*" },
+        "limit": 5
+    })
+}
+
+#[test]
+fn test_child() {
+    let v = admins();
+    assert_eq!(v.query("$.mapping.files"), [&"/static/*"]);
+    assert_eq!(v.query("mapping['tools']"), [&"/tools/*"]);
+    assert_eq!(v.query("$[\"mapping\"].*"), [&"/static/*", &"/tools/*"]);
+    assert!(v.query("$.missing.files").is_empty());
+}
+
+#[test]
+fn test_index() {
+    let v = admins();
+    assert_eq!(v.query("$.admins[0].name"), [&"admin"]);
+    assert_eq!(v.query("$.admins[-1].name"), [&"root"]);
+    assert_eq!(v.query("$.admins[0:2].number"), [&2, &5]);
+    assert_eq!(v.query("$.admins[::2].number"), [&2, &9]);
+    assert_eq!(v.query("$.admins[-2:].number"), [&5, &9]);
+    assert_eq!(v.query("$.admins[2, 0].number"), [&2, &9]);
+    assert!(v.query("$.admins[3]").is_empty());
+}
+
+#[test]
+fn test_recursive() {
+    let v = admins();
+    assert_eq!(v.query("$..number"), [&2, &5, &9]);
+    assert_eq!(v.query("$..[1].number"), [&5]);
+    assert_eq!(v.query("$..*").len(), 3 + 4 * 3 + 2 + 1 + 2);
+}
+
+#[test]
+fn test_filter() {
+    let v = admins();
+    assert_eq!(
+        v.query("$.admins[?(@.folder == 'main')].balance"),
+        [&10.4, &7.5]
+    );
+    assert_eq!(
+        v.query("$.admins[?(@.number > 2 && @.folder != \"main\")].number"),
+        [&5]
+    );
+    assert_eq!(
+        v.query("$.admins[?(@.balance < 0 || @.name == 'root')].number"),
+        [&5, &9]
+    );
+    assert_eq!(v.query("$.admins[?(@.number >= $.limit)].number"), [&5, &9]);
+    assert_eq!(v.query("$.admins[?(!(@.number <= 5))].number"), [&9]);
+    assert_eq!(v.query("$..[?(@.files)].tools"), [&"/tools/*"]);
+    assert_eq!(v.query("$.admins[?@.name == ''].number"), [&5]);
+}
+
+#[test]
+fn test_document_order() {
+    let mut v: Value = serde_encom::from_str("a{id:1} id:2").unwrap();
+    assert_eq!(v.query("$..id"), [&1, &2]);
+    let shared: Vec<Value> = v.query("$..id").into_iter().cloned().collect();
+    let unique: Vec<Value> = v
+        .query_mut("$..id")
+        .into_iter()
+        .map(|v| v.clone())
+        .collect();
+    assert_eq!(shared, unique);
+
+    let v = admins();
+    assert_eq!(v.query("$..*")[..2], [&v["admins"], &v["admins"][0]]);
+}
+
+#[test]
+fn test_reuse() {
+    let query: Query = "$.items[*].id".parse().unwrap();
+    let a: Value = serde_encom::from_str("items[{id:1} {id:2}]").unwrap();
+    let b: Value = serde_encom::from_str("items[{id:3}]").unwrap();
+    assert_eq!(query.select(&a), [&1, &2]);
+    assert_eq!(query.select(&b), [&3]);
+}
+
+#[test]
+fn test_query_mut() {
+    let mut v = admins();
+    for balance in v.query_mut("$.admins[?(@.folder == 'main')].balance") {
+        *balance = 0.0.into();
+    }
+    assert_eq!(v.query("$.admins[*].balance"), [&0.0, &-2.3, &0.0]);
+
+    let mut v = encom_from_json!({ "a": { "a": 1 } });
+    assert_eq!(v.query_mut("$..a").len(), 1);
+}
+
+#[test]
+fn test_invalid() {
+    for query in ["$.", "$[", "$[?(@.a ==)]", "$[1:2:0]", "$['a]", "$ a"] {
+        assert!(Query::parse(query).is_err(), "{query}");
+    }
+    assert!(admins().query("$[").is_empty());
+    assert!(admins().try_query("$[").is_err());
+    assert!(admins().try_query_mut("$[").is_err());
+    assert_eq!(admins().try_query("$.limit").unwrap(), [&5]);
+}
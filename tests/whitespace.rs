@@ -0,0 +1,62 @@
+#![cfg(feature = "std")]
+
+use serde::Deserialize;
+use serde_encom::{encom_from_json, Value};
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Point {
+    x: u64,
+    y: i64,
+}
+
+#[test]
+fn test_whitespace_runs() {
+    // Runs shorter and longer than the vectors they are scanned with, and
+    // of every control byte.
+    for len in [0, 1, 15, 16, 17, 31, 32, 33, 100] {
+        for ws in [" ", "\n", "\t", "\r", "\x00", "\x1f", "\n  "] {
+            let pad = ws.repeat(len);
+            let encom = format!("{pad}x:1{pad} y:-2{pad}");
+            let point: Point = serde_encom::from_str(&encom).unwrap();
+            assert_eq!(point, Point { x: 1, y: -2 });
+        }
+    }
+}
+
+#[test]
+fn test_whitespace_pretty() {
+    let value = encom_from_json!({
+        "records": [
+            {"id": 1, "name": "first", "tags": ["a", "b"], "score": 1.5, "ok": true},
+            {"id": 2, "name": "second", "tags": ["c"], "score": -2.25, "ok": false}
+        ],
+        "nested": {"deeper": {"deepest": {"value": [1, 2, 3]}}}
+    });
+    let pretty = serde_encom::to_string_pretty(&value).unwrap();
+    assert_eq!(serde_encom::from_str::<Value>(&pretty).unwrap(), value);
+
+    // Deeper than a vector of indentation.
+    let indent = " ".repeat(40);
+    let encom = format!("a{{\n{indent}b:1\n{indent}c{{\n{indent}{indent}d:t\n{indent}}}\n}}");
+    let value: Value = serde_encom::from_str(&encom).unwrap();
+    assert_eq!(value, encom_from_json!({"a": {"b": 1, "c": {"d": true}}}));
+}
+
+#[test]
+fn test_long_first_token() {
+    // What a value in braces is shows only at the delimiter after its first
+    // token, which may be longer than a vector.
+    let key = "k".repeat(70);
+    let value: Value = serde_encom::from_str(&format!("a{{{key}:1}}")).unwrap();
+    assert_eq!(value["a"][key.as_str()], 1);
+
+    let value: Value = serde_encom::from_str(&format!("a{{{key}[1 2]}}")).unwrap();
+    assert_eq!(value["a"][key.as_str()][1], 2);
+
+    let value: Value = serde_encom::from_str(&format!("a{{{key}{{b:t}}}}")).unwrap();
+    assert_eq!(value["a"][key.as_str()]["b"], true);
+
+    let text = "x".repeat(50);
+    let value: Value = serde_encom::from_str(&format!("a[{{{key}:50={text}}}]")).unwrap();
+    assert_eq!(value["a"][0][key.as_str()], text.as_str());
+}